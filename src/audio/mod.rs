@@ -2,11 +2,18 @@
 use bevy::prelude::*;
 use bevy_seedling::prelude::*;
 
+use crate::settings::Settings;
+
 pub(super) fn plugin(app: &mut App) {
     app.register_type::<MusicPool>();
     app.register_type::<SpatialPool>();
 
     app.add_systems(Startup, initialize_audio);
+    app.add_systems(
+        Update,
+        apply_volume_settings
+            .run_if(resource_changed::<Settings>.and(not(resource_added::<Settings>))),
+    );
 }
 
 #[derive(PoolLabel, Reflect, PartialEq, Eq, Debug, Hash, Clone)]
@@ -24,17 +31,25 @@ pub(crate) struct MusicPool;
 /// Set somewhere below 0 dB so that the user can turn the volume up if they want to.
 pub(crate) const DEFAULT_MAIN_VOLUME: Volume = Volume::Linear(0.5);
 
-fn initialize_audio(mut master: Single<&mut VolumeNode, With<MainBus>>, mut commands: Commands) {
-    master.volume = DEFAULT_MAIN_VOLUME;
-    // mute volume while we do not have any real sounds
-    const DEFAULT_POOL_VOLUME: Volume = Volume::Linear(1.0);
+/// Pool volumes at 100% in the settings menu.
+const MUSIC_POOL_VOLUME: f32 = 1.0;
+const SFX_POOL_VOLUME: f32 = 2.5;
+const UI_POOL_VOLUME: f32 = 1.0;
+
+fn initialize_audio(
+    mut master: Single<&mut VolumeNode, With<MainBus>>,
+    mut commands: Commands,
+    settings: Res<Settings>,
+) {
+    let volume = &settings.volume;
+    master.volume = Volume::Linear(volume.master);
 
     // For each new pool, we can provide non-default initial values for the volume.
     commands.spawn((
         Name::new("Music audio sampler pool"),
         SamplerPool(MusicPool),
         VolumeNode {
-            volume: DEFAULT_POOL_VOLUME,
+            volume: Volume::Linear(MUSIC_POOL_VOLUME * volume.music),
             ..Default::default()
         },
     ));
@@ -44,7 +59,7 @@ fn initialize_audio(mut master: Single<&mut VolumeNode, With<MainBus>>, mut comm
         SamplerPool(SpatialPool),
         sample_effects![(SpatialBasicNode::default(), SpatialScale(Vec3::splat(2.5)))],
         VolumeNode {
-            volume: Volume::Linear(2.5),
+            volume: Volume::Linear(SFX_POOL_VOLUME * volume.sfx),
             ..Default::default()
         },
     ));
@@ -53,8 +68,39 @@ fn initialize_audio(mut master: Single<&mut VolumeNode, With<MainBus>>, mut comm
         Name::new("UI SFX audio sampler pool"),
         SamplerPool(SfxPool),
         VolumeNode {
-            volume: DEFAULT_POOL_VOLUME,
+            volume: Volume::Linear(UI_POOL_VOLUME * volume.ui),
             ..Default::default()
         },
     ));
 }
+
+/// Apply the volumes from the [`Settings`] to the main bus and the sampler pools.
+fn apply_volume_settings(
+    settings: Res<Settings>,
+    mut master: Single<&mut VolumeNode, With<MainBus>>,
+    mut pools: Query<
+        (
+            &mut VolumeNode,
+            Has<SamplerPool<MusicPool>>,
+            Has<SamplerPool<SpatialPool>>,
+            Has<SamplerPool<SfxPool>>,
+        ),
+        Without<MainBus>,
+    >,
+) {
+    let volume = &settings.volume;
+    master.volume = Volume::Linear(volume.master);
+
+    for (mut node, is_music, is_sfx, is_ui) in &mut pools {
+        let linear = if is_music {
+            MUSIC_POOL_VOLUME * volume.music
+        } else if is_sfx {
+            SFX_POOL_VOLUME * volume.sfx
+        } else if is_ui {
+            UI_POOL_VOLUME * volume.ui
+        } else {
+            continue;
+        };
+        node.volume = Volume::Linear(linear);
+    }
+}
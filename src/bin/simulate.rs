@@ -0,0 +1,99 @@
+//! Runs headless balance simulations and prints one CSV row per run.
+//!
+//! ```text
+//! cargo run --bin simulate -- --minutes 10 --plan rush --policy circle:250 --seeds 0..20
+//! ```
+
+use std::{ops::Range, process::ExitCode};
+
+use bevy_survivors::sim::{self, SimConfig, SimReport};
+
+const USAGE: &str = "usage: simulate [--minutes N] [--plan standard|rush] \
+[--policy idle|kite|circle[:RADIUS]] [--seed N | --seeds A..B] [--weapon KIND]...";
+
+fn main() -> ExitCode {
+    let (config, seeds) = match parse_args(std::env::args().skip(1)) {
+        Ok(parsed) => parsed,
+        Err(err) => {
+            eprintln!("{err}\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("seed,plan,policy,seconds,kills,damage_taken,died_at,level,dps");
+    for seed in seeds {
+        let report = sim::run(&SimConfig {
+            seed,
+            ..config.clone()
+        });
+        println!("{}", csv_row(seed, &config, &report));
+    }
+
+    ExitCode::SUCCESS
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<(SimConfig, Range<u64>), String> {
+    let mut config = SimConfig::default();
+    let mut seeds = 0..1;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("{arg} needs a value"));
+        match arg.as_str() {
+            "--minutes" => {
+                config.minutes = value()?
+                    .parse()
+                    .map_err(|err| format!("--minutes: {err}"))?;
+            }
+            "--plan" => {
+                let name = value()?;
+                config.wave_plan =
+                    sim::parse_wave_plan(&name).ok_or(format!("unknown wave plan {name:?}"))?;
+            }
+            "--policy" => config.policy = value()?.parse()?,
+            "--seed" => {
+                let seed: u64 = value()?.parse().map_err(|err| format!("--seed: {err}"))?;
+                seeds = seed..seed + 1;
+            }
+            "--seeds" => {
+                let range = value()?;
+                let (start, end) = range
+                    .split_once("..")
+                    .ok_or(format!("--seeds expects A..B, got {range:?}"))?;
+                let parse = |s: &str| s.parse::<u64>().map_err(|err| format!("--seeds: {err}"));
+                seeds = parse(start)?..parse(end)?;
+            }
+            "--weapon" => {
+                let name = value()?;
+                config
+                    .weapons
+                    .push(sim::parse_weapon(&name).ok_or(format!("unknown weapon {name:?}"))?);
+            }
+            _ => return Err(format!("unknown argument {arg:?}")),
+        }
+    }
+
+    Ok((config, seeds))
+}
+
+fn csv_row(seed: u64, config: &SimConfig, report: &SimReport) -> String {
+    let dps = report
+        .dps
+        .iter()
+        .map(|(kind, dps)| format!("{kind:?}={dps:.1}"))
+        .collect::<Vec<_>>()
+        .join(";");
+    let died_at = report
+        .died_at
+        .map(|seconds| format!("{seconds:.0}"))
+        .unwrap_or_default();
+
+    format!(
+        "{seed},{:?},{:?},{:.0},{},{:.0},{died_at},{},{dps}",
+        config.wave_plan,
+        config.policy,
+        report.seconds,
+        report.kills,
+        report.damage_taken,
+        report.level,
+    )
+}
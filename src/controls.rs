@@ -0,0 +1,226 @@
+//! Rebindable controls, persisted to `controls.ron` in the config directory.
+
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use bevy_enhanced_input::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::persistence;
+
+const CONTROLS_FILE: &str = "controls.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<Rebinding>();
+    app.add_systems(Startup, load_controls);
+    app.add_systems(
+        Update,
+        save_controls.run_if(resource_changed::<Controls>.and(not(resource_added::<Controls>))),
+    );
+}
+
+/// Everything the player can rebind.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+pub(crate) enum Control {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    QAbility,
+    EAbility,
+    RAbility,
+    Interact,
+    Pause,
+    Stats,
+}
+
+impl Control {
+    pub const ALL: [Control; 10] = [
+        Control::MoveUp,
+        Control::MoveDown,
+        Control::MoveLeft,
+        Control::MoveRight,
+        Control::QAbility,
+        Control::EAbility,
+        Control::RAbility,
+        Control::Interact,
+        Control::Pause,
+        Control::Stats,
+    ];
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Control::MoveUp => "Move Up",
+            Control::MoveDown => "Move Down",
+            Control::MoveLeft => "Move Left",
+            Control::MoveRight => "Move Right",
+            Control::QAbility => "Ability 1",
+            Control::EAbility => "Ability 2",
+            Control::RAbility => "Ability 3",
+            Control::Interact => "Interact",
+            Control::Pause => "Pause",
+            Control::Stats => "Run Stats",
+        }
+    }
+}
+
+/// The keyboard and gamepad binding of every [`Control`].
+/// Escape and the left stick are always bound in addition, and the right stick aims.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub(crate) struct Controls {
+    pub keys: HashMap<Control, KeyCode>,
+    pub gamepad: HashMap<Control, GamepadButton>,
+}
+
+impl Default for Controls {
+    fn default() -> Self {
+        Self {
+            keys: HashMap::from_iter([
+                (Control::MoveUp, KeyCode::KeyW),
+                (Control::MoveDown, KeyCode::KeyS),
+                (Control::MoveLeft, KeyCode::KeyA),
+                (Control::MoveRight, KeyCode::KeyD),
+                (Control::QAbility, KeyCode::KeyQ),
+                (Control::EAbility, KeyCode::KeyE),
+                (Control::RAbility, KeyCode::KeyR),
+                (Control::Interact, KeyCode::KeyF),
+                (Control::Pause, KeyCode::KeyP),
+                (Control::Stats, KeyCode::Tab),
+            ]),
+            gamepad: HashMap::from_iter([
+                (Control::MoveUp, GamepadButton::DPadUp),
+                (Control::MoveDown, GamepadButton::DPadDown),
+                (Control::MoveLeft, GamepadButton::DPadLeft),
+                (Control::MoveRight, GamepadButton::DPadRight),
+                (Control::QAbility, GamepadButton::West),
+                (Control::EAbility, GamepadButton::North),
+                (Control::RAbility, GamepadButton::RightTrigger),
+                (Control::Interact, GamepadButton::LeftTrigger),
+                (Control::Pause, GamepadButton::Start),
+                (Control::Stats, GamepadButton::Select),
+            ]),
+        }
+    }
+}
+
+impl Controls {
+    pub fn key(&self, control: Control) -> Option<KeyCode> {
+        self.keys.get(&control).copied()
+    }
+
+    pub fn button(&self, control: Control) -> Option<GamepadButton> {
+        self.gamepad.get(&control).copied()
+    }
+
+    pub fn key_binding(&self, control: Control) -> Binding {
+        self.key(control).map_or(Binding::None, Binding::from)
+    }
+
+    pub fn button_binding(&self, control: Control) -> Binding {
+        self.button(control).map_or(Binding::None, Binding::from)
+    }
+
+    /// Bind controls missing from an older `controls.ron` to their default.
+    fn with_defaults(mut self) -> Self {
+        let defaults = Controls::default();
+        for (control, key) in defaults.keys {
+            self.keys.entry(control).or_insert(key);
+        }
+        for (control, button) in defaults.gamepad {
+            self.gamepad.entry(control).or_insert(button);
+        }
+        self
+    }
+
+    /// Controls that share a key or gamepad button with another control.
+    pub fn conflicts(&self) -> HashSet<Control> {
+        fn shared<T: Eq + std::hash::Hash + Copy>(
+            bindings: &HashMap<Control, T>,
+        ) -> impl Iterator<Item = Control> + '_ {
+            let mut seen: HashMap<T, usize> = HashMap::default();
+            for input in bindings.values() {
+                *seen.entry(*input).or_default() += 1;
+            }
+            bindings
+                .iter()
+                .filter(move |(_, input)| seen[*input] > 1)
+                .map(|(control, _)| *control)
+        }
+
+        shared(&self.keys).chain(shared(&self.gamepad)).collect()
+    }
+}
+
+/// Which slot of a [`Control`] is being rebound.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum BindingSlot {
+    Keyboard,
+    Gamepad,
+}
+
+/// Set while the settings menu waits for a new input.
+#[derive(Resource, Default, Debug)]
+pub(crate) struct Rebinding(pub Option<(Control, BindingSlot)>);
+
+/// Run condition: the [`Control`] was just pressed on the keyboard or any gamepad.
+/// Never true while a control is being rebound.
+pub(crate) fn control_just_pressed(
+    control: Control,
+) -> impl Fn(Res<Controls>, Res<Rebinding>, Res<ButtonInput<KeyCode>>, Query<&Gamepad>) -> bool + Clone
+{
+    move |controls, rebinding, keyboard, gamepads| {
+        if rebinding.0.is_some() {
+            return false;
+        }
+
+        controls
+            .key(control)
+            .is_some_and(|key| keyboard.just_pressed(key))
+            || controls
+                .button(control)
+                .is_some_and(|button| gamepads.iter().any(|gamepad| gamepad.just_pressed(button)))
+    }
+}
+
+/// Short human readable name, e.g. `W` instead of `KeyW`.
+pub(crate) fn key_name(key: Option<KeyCode>) -> String {
+    let Some(key) = key else {
+        return "-".to_string();
+    };
+    let name = format!("{key:?}");
+    name.strip_prefix("Key")
+        .or_else(|| name.strip_prefix("Digit"))
+        .unwrap_or(&name)
+        .to_string()
+}
+
+pub(crate) fn button_name(button: Option<GamepadButton>) -> String {
+    button.map_or_else(|| "-".to_string(), |button| format!("{button:?}"))
+}
+
+fn load_controls(mut commands: Commands) {
+    let controls = persistence::config_path(CONTROLS_FILE)
+        .map(|path| persistence::load_ron::<Controls>(&path))
+        .transpose()
+        .unwrap_or_else(|err| {
+            warn!("Failed to load controls, using defaults: {err}");
+            None
+        })
+        .flatten()
+        .unwrap_or_default()
+        .with_defaults();
+
+    commands.insert_resource(controls);
+}
+
+fn save_controls(controls: Res<Controls>) {
+    let Some(path) = persistence::config_path(CONTROLS_FILE) else {
+        return;
+    };
+
+    if let Err(err) = persistence::save_ron(&path, &*controls) {
+        warn!("Failed to save controls: {err}");
+    }
+}
@@ -11,7 +11,13 @@ use bevy_inspector_egui::{
     quick::{StateInspectorPlugin, WorldInspectorPlugin},
 };
 
-use crate::{gameplay::overlays::experience::LevelUpEvent, screens::Screen};
+use crate::{
+    gameplay::{
+        overlays::experience::GainXpEvent,
+        player::{Level, Player, XP, xp_curve::XpCurve},
+    },
+    screens::Screen,
+};
 
 const TOGGLE_DEBUG_UI_KEY: KeyCode = KeyCode::Backquote;
 const TRIGGER_LEVEL_UP_KEY: KeyCode = KeyCode::F1;
@@ -65,6 +71,11 @@ fn toggle_colliders(mut gizmo_config_store: ResMut<GizmoConfigStore>) {
     gizmo_config.enabled = !gizmo_config.enabled;
 }
 
-fn trigger_level_up(mut commands: Commands) {
-    commands.trigger(LevelUpEvent);
+fn trigger_level_up(
+    mut commands: Commands,
+    player: Single<(&Level, &XP), With<Player>>,
+    curve: Res<XpCurve>,
+) {
+    let (level, xp) = *player;
+    commands.trigger(GainXpEvent(curve.xp_needed(level.0) - xp.0));
 }
@@ -4,7 +4,7 @@ use crate::gameplay::abilities::{
 };
 use crate::gameplay::damage_numbers::{DamageMessage, DamageType};
 use crate::gameplay::healthbar::HealthBarMaterial;
-use crate::gameplay::player::Player;
+use crate::gameplay::player::{PLAYER_MAX_HEALTH, Player};
 use bevy::prelude::*;
 
 #[derive(Component)]
@@ -39,9 +39,9 @@ fn on_use_heal(
     };
 
     let heal_amount = 30.0;
-    health.0 = (health.0 + heal_amount).min(100.0);
+    health.0 = (health.0 + heal_amount).min(PLAYER_MAX_HEALTH);
 
-    let per = health.0 / 100.;
+    let per = health.0 / PLAYER_MAX_HEALTH;
 
     let handle = healthbar_material_q.single()?.clone();
     let material = health_bar_materials.get_mut(&handle).unwrap();
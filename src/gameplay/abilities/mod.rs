@@ -1,4 +1,6 @@
-use crate::{PausableSystems, screens::Screen};
+use crate::{
+    GameplaySystems, PausableSystems, gameplay::player::movement::AccumulatedInput, screens::Screen,
+};
 use bevy::prelude::*;
 use bevy_enhanced_input::prelude::{InputAction, Start};
 
@@ -16,6 +18,13 @@ pub(crate) fn plugin(app: &mut App) {
             .run_if(in_state(Screen::Gameplay))
             .in_set(PausableSystems),
     );
+    app.add_systems(
+        FixedUpdate,
+        use_abilities
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems)
+            .in_set(GameplaySystems::MovementModify),
+    );
 
     app.add_observer(on_q_pressed);
     app.add_observer(on_e_pressed);
@@ -39,39 +48,40 @@ pub struct UseAbility {
     pub ability_entity: Entity,
 }
 
-fn on_q_pressed(
-    _trigger: On<Start<UseQAbility>>,
-    q_ability: Query<Entity, With<QAbility>>,
-    mut commands: Commands,
-) {
-    if let Ok(entity) = q_ability.single() {
-        commands.trigger(UseAbility {
-            ability_entity: entity,
-        });
-    }
+fn on_q_pressed(_trigger: On<Start<UseQAbility>>, mut input: Single<&mut AccumulatedInput>) {
+    input.q_ability = true;
 }
 
-fn on_e_pressed(
-    _trigger: On<Start<UseEAbility>>,
-    e_ability: Query<Entity, With<EAbility>>,
-    mut commands: Commands,
-) {
-    if let Ok(entity) = e_ability.single() {
-        commands.trigger(UseAbility {
-            ability_entity: entity,
-        });
-    }
+fn on_e_pressed(_trigger: On<Start<UseEAbility>>, mut input: Single<&mut AccumulatedInput>) {
+    input.e_ability = true;
+}
+
+fn on_r_pressed(_trigger: On<Start<UseRAbility>>, mut input: Single<&mut AccumulatedInput>) {
+    input.r_ability = true;
 }
 
-fn on_r_pressed(
-    _trigger: On<Start<UseRAbility>>,
+/// Use the abilities pressed since the last tick.
+///
+/// Presses go through [`AccumulatedInput`] so they land on a fixed tick and can be replayed.
+fn use_abilities(
+    mut input: Single<&mut AccumulatedInput>,
+    q_ability: Query<Entity, With<QAbility>>,
+    e_ability: Query<Entity, With<EAbility>>,
     r_ability: Query<Entity, With<RAbility>>,
     mut commands: Commands,
 ) {
-    if let Ok(entity) = r_ability.single() {
-        commands.trigger(UseAbility {
-            ability_entity: entity,
-        });
+    let pressed = [
+        (std::mem::take(&mut input.q_ability), q_ability.single()),
+        (std::mem::take(&mut input.e_ability), e_ability.single()),
+        (std::mem::take(&mut input.r_ability), r_ability.single()),
+    ];
+
+    for (pressed, ability) in pressed {
+        if pressed && let Ok(entity) = ability {
+            commands.trigger(UseAbility {
+                ability_entity: entity,
+            });
+        }
     }
 }
 
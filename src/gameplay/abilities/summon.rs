@@ -289,6 +289,7 @@ fn spawn_minion_death_effect(
                 entity_hit: enemy_entity,
                 dmg: explosion_damage,
                 damage_type: crate::gameplay::damage_numbers::DamageType::Physical,
+                source: None,
             });
         }
     }
@@ -0,0 +1,276 @@
+use bevy::{
+    color::palettes::tailwind::{AMBER_400, SKY_400, SLATE_200},
+    platform::collections::HashMap,
+    prelude::*,
+};
+use bevy_rand::prelude::WyRand;
+use bevy_seedling::sample::SamplePlayer;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    PLAYER_SIZE, PausableSystems,
+    audio::SfxPool,
+    gameplay::{
+        Speed,
+        enemy::{EnemyDeathEvent, EnemyType},
+        player::{Player, XpCollectionRange},
+        rng::LootRng,
+    },
+    screens::Screen,
+};
+
+pub(crate) fn plugin(app: &mut App) {
+    app.insert_resource(MaterialDropTable(HashMap::from([
+        (
+            EnemyType::Walker,
+            vec![
+                MaterialDrop::new(CraftingMaterial::PowerRune, MaterialQuality::Common, 0.08),
+                MaterialDrop::new(CraftingMaterial::PowerRune, MaterialQuality::Rare, 0.01),
+            ],
+        ),
+        (
+            EnemyType::Shooter,
+            vec![
+                MaterialDrop::new(CraftingMaterial::FireEssence, MaterialQuality::Common, 0.1),
+                MaterialDrop::new(CraftingMaterial::FireEssence, MaterialQuality::Rare, 0.03),
+            ],
+        ),
+        (
+            EnemyType::Sprinter,
+            vec![
+                MaterialDrop::new(CraftingMaterial::StormCore, MaterialQuality::Common, 0.1),
+                MaterialDrop::new(CraftingMaterial::StormCore, MaterialQuality::Rare, 0.03),
+            ],
+        ),
+        (
+            EnemyType::Jumper,
+            vec![
+                MaterialDrop::new(CraftingMaterial::StoneHeart, MaterialQuality::Rare, 0.1),
+                MaterialDrop::new(CraftingMaterial::StoneHeart, MaterialQuality::Epic, 0.02),
+            ],
+        ),
+    ])));
+
+    app.add_systems(
+        Update,
+        collect_materials
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+
+    app.add_observer(spawn_material_drops);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum CraftingMaterial {
+    PowerRune,
+    FireEssence,
+    StormCore,
+    StoneHeart,
+}
+
+impl CraftingMaterial {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            CraftingMaterial::PowerRune => "Power Rune",
+            CraftingMaterial::FireEssence => "Fire Essence",
+            CraftingMaterial::StormCore => "Storm Core",
+            CraftingMaterial::StoneHeart => "Stone Heart",
+        }
+    }
+}
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Reflect, Serialize, Deserialize,
+)]
+pub enum MaterialQuality {
+    Common,
+    Rare,
+    Epic,
+}
+
+impl MaterialQuality {
+    pub fn color(self) -> Color {
+        match self {
+            MaterialQuality::Common => SLATE_200.into(),
+            MaterialQuality::Rare => SKY_400.into(),
+            MaterialQuality::Epic => AMBER_400.into(),
+        }
+    }
+}
+
+/// A single entry in an enemy's drop table.
+#[derive(Debug, Clone)]
+pub(crate) struct MaterialDrop {
+    pub material: CraftingMaterial,
+    pub quality: MaterialQuality,
+    /// Chance in `0.0..=1.0` that this entry drops when the enemy dies.
+    pub chance: f64,
+}
+
+impl MaterialDrop {
+    pub const fn new(material: CraftingMaterial, quality: MaterialQuality, chance: f64) -> Self {
+        Self {
+            material,
+            quality,
+            chance,
+        }
+    }
+}
+
+/// Crafting materials each enemy type can drop on death.
+#[derive(Resource, Deref, DerefMut)]
+pub(crate) struct MaterialDropTable(pub HashMap<EnemyType, Vec<MaterialDrop>>);
+
+/// A crafting material lying on the ground, waiting to be collected.
+#[derive(Component, Reflect)]
+pub(crate) struct MaterialPickup {
+    pub material: CraftingMaterial,
+    pub quality: MaterialQuality,
+}
+
+/// All crafting materials the player collected during the current run.
+#[derive(Component, Default, Debug, Reflect)]
+pub(crate) struct MaterialPouch(HashMap<(CraftingMaterial, MaterialQuality), u32>);
+
+impl MaterialPouch {
+    pub fn count(&self, material: CraftingMaterial, quality: MaterialQuality) -> u32 {
+        self.0.get(&(material, quality)).copied().unwrap_or(0)
+    }
+
+    pub fn add(&mut self, material: CraftingMaterial, quality: MaterialQuality, amount: u32) {
+        *self.0.entry((material, quality)).or_insert(0) += amount;
+    }
+
+    /// Removes `amount` materials. Returns `false` and leaves the pouch untouched
+    /// if there are not enough.
+    pub fn take(
+        &mut self,
+        material: CraftingMaterial,
+        quality: MaterialQuality,
+        amount: u32,
+    ) -> bool {
+        let Some(count) = self.0.get_mut(&(material, quality)) else {
+            return amount == 0;
+        };
+        if *count < amount {
+            return false;
+        }
+        *count -= amount;
+        true
+    }
+}
+
+fn spawn_material_drops(
+    trigger: On<EnemyDeathEvent>,
+    drop_table: Res<MaterialDropTable>,
+    mut rng: Single<&mut WyRand, With<LootRng>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let Some(drops) = drop_table.get(&trigger.enemy_type) else {
+        return;
+    };
+
+    let enemy_pos = trigger.transform.translation;
+
+    for drop in drops {
+        if !rng.random_bool(drop.chance) {
+            continue;
+        }
+
+        let offset = Vec2::new(rng.random_range(-8.0..8.0), rng.random_range(-8.0..8.0));
+
+        commands.spawn((
+            Name::new(format!("{:?} {:?}", drop.quality, drop.material)),
+            Sprite {
+                image: asset_server.load("xp_gem.png"),
+                color: drop.quality.color(),
+                ..default()
+            },
+            Transform::from_xyz(enemy_pos.x + offset.x, enemy_pos.y + offset.y, 10.),
+            MaterialPickup {
+                material: drop.material,
+                quality: drop.quality,
+            },
+            Speed(200.),
+            DespawnOnExit(Screen::Gameplay),
+        ));
+    }
+}
+
+fn collect_materials(
+    player_q: Single<(&Transform, &XpCollectionRange, &mut MaterialPouch), With<Player>>,
+    mut pickup_q: Query<(Entity, &mut Transform, &Speed, &MaterialPickup), Without<Player>>,
+    time: Res<Time>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let (player_transform, collection_range, mut pouch) = player_q.into_inner();
+
+    for (entity, mut transform, speed, pickup) in &mut pickup_q {
+        let distance = player_transform.translation.distance(transform.translation);
+
+        if distance <= collection_range.0 {
+            let direction = (player_transform.translation - transform.translation).normalize();
+            transform.translation += direction * (speed.0 * time.delta_secs());
+        }
+
+        if distance <= PLAYER_SIZE / 2.0 {
+            pouch.add(pickup.material, pickup.quality, 1);
+            commands.spawn((
+                SamplePlayer::new(asset_server.load("audio/sound_effects/xp.wav")),
+                SfxPool,
+            ));
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pouch_counts_materials_by_quality() {
+        let mut pouch = MaterialPouch::default();
+        pouch.add(CraftingMaterial::FireEssence, MaterialQuality::Common, 2);
+        pouch.add(CraftingMaterial::FireEssence, MaterialQuality::Common, 1);
+        pouch.add(CraftingMaterial::FireEssence, MaterialQuality::Rare, 1);
+
+        assert_eq!(
+            pouch.count(CraftingMaterial::FireEssence, MaterialQuality::Common),
+            3
+        );
+        assert_eq!(
+            pouch.count(CraftingMaterial::FireEssence, MaterialQuality::Rare),
+            1
+        );
+        assert_eq!(
+            pouch.count(CraftingMaterial::StormCore, MaterialQuality::Common),
+            0
+        );
+    }
+
+    #[test]
+    fn pouch_only_takes_what_it_has() {
+        let mut pouch = MaterialPouch::default();
+        pouch.add(CraftingMaterial::PowerRune, MaterialQuality::Rare, 2);
+
+        assert!(!pouch.take(CraftingMaterial::PowerRune, MaterialQuality::Rare, 3));
+        assert_eq!(
+            pouch.count(CraftingMaterial::PowerRune, MaterialQuality::Rare),
+            2
+        );
+
+        assert!(pouch.take(CraftingMaterial::PowerRune, MaterialQuality::Rare, 2));
+        assert_eq!(
+            pouch.count(CraftingMaterial::PowerRune, MaterialQuality::Rare),
+            0
+        );
+
+        assert!(!pouch.take(CraftingMaterial::StoneHeart, MaterialQuality::Epic, 1));
+        assert!(pouch.take(CraftingMaterial::StoneHeart, MaterialQuality::Epic, 0));
+    }
+}
@@ -0,0 +1,14 @@
+//! Crafting materials, recipes and crafting stations.
+//!
+//! Enemies drop materials of different qualities. At a crafting station the player
+//! can turn those materials (and owned weapons) into weapon variants.
+
+use bevy::prelude::*;
+
+pub(crate) mod materials;
+pub(crate) mod recipe;
+pub(crate) mod station;
+
+pub(crate) fn plugin(app: &mut App) {
+    app.add_plugins((materials::plugin, recipe::plugin, station::plugin));
+}
@@ -0,0 +1,244 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use serde::Deserialize;
+use serde_ron::de::from_bytes;
+use thiserror::Error;
+
+use crate::gameplay::{
+    crafting::materials::{CraftingMaterial, MaterialPouch, MaterialQuality},
+    damage_numbers::DamageType,
+    data,
+    player::{InInventoryOf, Player},
+    weapons::{
+        components::{BaseDamage, Weapon},
+        kind::WeaponKind,
+        spec::components::{HitSpec, OnHitEffect},
+        systems::cooldown::WeaponCooldown,
+    },
+};
+
+/// Every recipe, without recipes if the file is missing.
+const RECIPES_FILE: &str = "crafting/all.recipes.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<RecipeBook>()
+        .register_asset_loader(RecipeBookLoader);
+    data::load_data::<RecipeBook>(app, RECIPES_FILE);
+
+    app.add_observer(craft_recipe);
+}
+
+/// A crafting recipe, one of the entries of [`RECIPES_FILE`].
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Recipe {
+    pub name: String,
+    pub materials: Vec<MaterialCost>,
+    /// Weapons that have to be owned besides the result's base weapon. They are used up by the craft.
+    #[serde(default)]
+    pub weapons: Vec<WeaponKind>,
+    pub result: WeaponVariantSpec,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MaterialCost {
+    pub material: CraftingMaterial,
+    pub quality: MaterialQuality,
+    pub amount: u32,
+}
+
+/// Turns an owned weapon into a special variant of itself.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct WeaponVariantSpec {
+    pub base: WeaponKind,
+    pub name: String,
+    #[serde(default = "one")]
+    pub damage_multiplier: f32,
+    #[serde(default = "one")]
+    pub cooldown_multiplier: f32,
+    pub damage_type: Option<DamageType>,
+    #[serde(default)]
+    pub effects: Vec<OnHitEffect>,
+}
+
+fn one() -> f32 {
+    1.0
+}
+
+impl Recipe {
+    pub fn can_craft(&self, pouch: &MaterialPouch, owned: &[WeaponKind]) -> bool {
+        let has_materials = self
+            .materials
+            .iter()
+            .all(|cost| pouch.count(cost.material, cost.quality) >= cost.amount);
+
+        let has_weapons = std::iter::once(&self.result.base)
+            .chain(&self.weapons)
+            .all(|kind| owned.contains(kind));
+
+        has_materials && has_weapons
+    }
+}
+
+/// Marks a weapon that was changed by a crafting recipe.
+#[derive(Component, Debug, Clone, Reflect)]
+pub(crate) struct WeaponVariant(pub String);
+
+/// All recipes that can be crafted at a crafting station.
+#[derive(Asset, Resource, TypePath, Deserialize, Deref, Debug, Clone, Default)]
+#[serde(transparent)]
+pub(crate) struct RecipeBook(Vec<Recipe>);
+
+/// Craft the recipe at `index` in the [`RecipeBook`].
+#[derive(Event, Debug)]
+pub(crate) struct CraftRecipeEvent {
+    pub index: usize,
+}
+
+fn craft_recipe(
+    trigger: On<CraftRecipeEvent>,
+    recipes: Res<RecipeBook>,
+    player: Single<(Entity, &mut MaterialPouch), With<Player>>,
+    mut weapon_q: Query<
+        (
+            Entity,
+            &WeaponKind,
+            &InInventoryOf,
+            &mut BaseDamage,
+            &mut WeaponCooldown,
+            &mut HitSpec,
+            &mut Name,
+        ),
+        With<Weapon>,
+    >,
+    mut commands: Commands,
+) {
+    let Some(recipe) = recipes.get(trigger.index) else {
+        error!("No recipe with index {}", trigger.index);
+        return;
+    };
+
+    let (player, mut pouch) = player.into_inner();
+
+    let owned: Vec<WeaponKind> = weapon_q
+        .iter()
+        .filter(|(_, _, owner, ..)| owner.0 == player)
+        .map(|(_, kind, ..)| *kind)
+        .collect();
+
+    if !recipe.can_craft(&pouch, &owned) {
+        warn!(
+            "Tried to craft {} without the required ingredients",
+            recipe.name
+        );
+        return;
+    }
+
+    for cost in &recipe.materials {
+        pouch.take(cost.material, cost.quality, cost.amount);
+    }
+
+    let variant = &recipe.result;
+    for (entity, kind, owner, mut damage, mut cooldown, mut hit, mut name) in &mut weapon_q {
+        if owner.0 != player {
+            continue;
+        }
+
+        if *kind == variant.base {
+            damage.0 *= variant.damage_multiplier;
+            let duration = cooldown.0.duration().mul_f32(variant.cooldown_multiplier);
+            cooldown.0.set_duration(duration);
+            if let Some(damage_type) = variant.damage_type {
+                hit.damage_type = damage_type;
+            }
+            hit.effects.extend(variant.effects.iter().cloned());
+            name.set(variant.name.clone());
+            commands
+                .entity(entity)
+                .insert(WeaponVariant(variant.name.clone()));
+        } else if recipe.weapons.contains(kind) {
+            commands.entity(entity).despawn();
+        }
+    }
+
+    info!("Crafted {}", recipe.name);
+}
+
+#[derive(Default, TypePath)]
+struct RecipeBookLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+enum RecipeBookLoaderError {
+    #[error("Could not read the file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Could not parse RON: {0}")]
+    RonError(#[from] serde_ron::error::SpannedError),
+}
+
+impl AssetLoader for RecipeBookLoader {
+    type Asset = RecipeBook;
+    type Settings = ();
+    type Error = RecipeBookLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(from_bytes::<RecipeBook>(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["recipes.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recipe() -> Recipe {
+        from_bytes(
+            br#"(
+                name: "Burning Fireball",
+                materials: [(material: FireEssence, quality: Rare, amount: 2)],
+                weapons: [Aoe],
+                result: (base: Fireball, name: "Burning Fireball"),
+            )"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn crafting_needs_materials_and_weapons() {
+        let recipe = recipe();
+        let mut pouch = MaterialPouch::default();
+        pouch.add(CraftingMaterial::FireEssence, MaterialQuality::Rare, 2);
+
+        assert!(recipe.can_craft(&pouch, &[WeaponKind::Fireball, WeaponKind::Aoe]));
+        assert!(!recipe.can_craft(&pouch, &[WeaponKind::Fireball]));
+        assert!(!recipe.can_craft(&pouch, &[WeaponKind::Aoe]));
+        assert!(!recipe.can_craft(
+            &MaterialPouch::default(),
+            &[WeaponKind::Fireball, WeaponKind::Aoe]
+        ));
+    }
+
+    #[test]
+    fn crafting_needs_the_right_quality() {
+        let mut pouch = MaterialPouch::default();
+        pouch.add(CraftingMaterial::FireEssence, MaterialQuality::Common, 5);
+        pouch.add(CraftingMaterial::FireEssence, MaterialQuality::Rare, 1);
+
+        assert!(!recipe().can_craft(&pouch, &[WeaponKind::Fireball, WeaponKind::Aoe]));
+    }
+}
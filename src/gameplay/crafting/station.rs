@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    gameplay::{overlays::Overlay, player::Player},
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<CraftingStation>();
+
+    app.add_systems(
+        Update,
+        open_crafting_in_range
+            .run_if(in_state(Screen::Gameplay).and(in_state(Overlay::None)))
+            .in_set(PausableSystems),
+    );
+}
+
+/// A crafting station placed in the Tiled map.
+/// Walking into its range opens the crafting overlay.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub(crate) struct CraftingStation {
+    pub range: f32,
+}
+
+impl Default for CraftingStation {
+    fn default() -> Self {
+        Self { range: 48.0 }
+    }
+}
+
+/// Present on a [`CraftingStation`] while the player stands in its range.
+/// The overlay only opens again after the player left and re-entered.
+#[derive(Component)]
+struct PlayerInRange;
+
+fn open_crafting_in_range(
+    player: Single<&Transform, With<Player>>,
+    station_q: Query<(
+        Entity,
+        &GlobalTransform,
+        &CraftingStation,
+        Has<PlayerInRange>,
+    )>,
+    mut next_overlay: ResMut<NextState<Overlay>>,
+    mut commands: Commands,
+) {
+    let player_pos = player.translation.truncate();
+
+    for (station, station_tf, crafting_station, in_range) in &station_q {
+        let distance = station_tf.translation().truncate().distance(player_pos);
+
+        if distance <= crafting_station.range {
+            if !in_range {
+                commands.entity(station).insert(PlayerInRange);
+                next_overlay.set(Overlay::Crafting);
+            }
+        } else if in_range {
+            commands.entity(station).remove::<PlayerInRange>();
+        }
+    }
+}
@@ -2,7 +2,7 @@ use bevy::{prelude::*, sprite::Text2dShadow, text::FontSmoothing};
 use bevy_asset_loader::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::{AssetStates, screens::Screen};
+use crate::{AssetStates, screens::Screen, settings::Settings};
 
 #[derive(Copy, Clone, Reflect, Deserialize, Serialize, Debug)]
 pub enum DamageType {
@@ -61,7 +61,13 @@ fn spawn_damage_numbers_from_messages(
     mut commands: Commands,
     mut reader: MessageReader<DamageMessage>,
     damage_assets: Res<DamageAssets>,
+    settings: Res<Settings>,
 ) {
+    if !settings.accessibility.damage_numbers {
+        reader.clear();
+        return;
+    }
+
     for msg in reader.read() {
         //TODO: Move this into a function for extensibility or better implement over DamageType
         let is_heal = matches!(msg.damage_type, DamageType::Heal);
@@ -0,0 +1,52 @@
+//! Game data read from `assets/` into a resource with a built-in default.
+//!
+//! Unlike the asset collections of [`AssetStates::AssetLoading`](crate::AssetStates), these
+//! files don't block loading. A missing or broken file keeps the default and logs a warning.
+
+use bevy::{asset::AssetLoadFailedEvent, prelude::*};
+
+/// Load `path` into the resource `A`, replacing its default once loaded and on hot reloads.
+pub(crate) fn load_data<A>(app: &mut App, path: &'static str)
+where
+    A: Asset + Resource + Clone + Default,
+{
+    app.init_resource::<A>();
+    app.add_systems(
+        Startup,
+        move |mut commands: Commands, asset_server: Res<AssetServer>| {
+            commands.insert_resource(DataHandle::<A>(asset_server.load(path)));
+        },
+    );
+    app.add_systems(PreUpdate, (apply_data::<A>, warn_failed_data::<A>));
+}
+
+#[derive(Resource)]
+struct DataHandle<A: Asset>(Handle<A>);
+
+fn apply_data<A: Asset + Resource + Clone>(
+    mut events: MessageReader<AssetEvent<A>>,
+    handle: Option<Res<DataHandle<A>>>,
+    assets: Res<Assets<A>>,
+    mut commands: Commands,
+) {
+    let Some(handle) = handle else {
+        return;
+    };
+
+    for event in events.read() {
+        if (event.is_loaded_with_dependencies(&handle.0) || event.is_modified(&handle.0))
+            && let Some(data) = assets.get(&handle.0)
+        {
+            commands.insert_resource(data.clone());
+        }
+    }
+}
+
+fn warn_failed_data<A: Asset>(mut events: MessageReader<AssetLoadFailedEvent<A>>) {
+    for event in events.read() {
+        warn!(
+            "Failed to load {}, using the default: {}",
+            event.path, event.error
+        );
+    }
+}
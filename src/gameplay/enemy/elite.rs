@@ -0,0 +1,39 @@
+//! Elites are rare, tougher versions of regular enemies with better loot.
+
+use bevy::prelude::*;
+use bevy_rand::prelude::WyRand;
+use rand::Rng;
+
+use crate::gameplay::{Health, enemy::Enemy, rng::SpawnRng};
+
+/// Chance for any spawned enemy to be an elite.
+const ELITE_CHANCE: f64 = 0.02;
+const ELITE_HEALTH_MULTIPLIER: f32 = 5.0;
+const ELITE_SCALE_MULTIPLIER: f32 = 1.4;
+
+pub(crate) fn plugin(app: &mut App) {
+    app.register_type::<Elite>();
+    app.add_observer(roll_elite);
+}
+
+/// Marks an enemy that drops from the elite loot table.
+#[derive(Component, Reflect)]
+pub(crate) struct Elite;
+
+fn roll_elite(
+    add: On<Add, Enemy>,
+    mut enemy_q: Query<(&mut Health, &mut Transform), With<Enemy>>,
+    mut rng: Single<&mut WyRand, With<SpawnRng>>,
+    mut commands: Commands,
+) {
+    if !rng.random_bool(ELITE_CHANCE) {
+        return;
+    }
+    let Ok((mut health, mut transform)) = enemy_q.get_mut(add.entity) else {
+        return;
+    };
+
+    health.0 *= ELITE_HEALTH_MULTIPLIER;
+    transform.scale *= ELITE_SCALE_MULTIPLIER;
+    commands.entity(add.entity).insert(Elite);
+}
@@ -1,19 +1,22 @@
 use avian2d::prelude::{ColliderDisabled, SpatialQuery};
 use bevy::prelude::*;
-use bevy_rand::{global::GlobalRng, prelude::WyRand};
+use bevy_rand::prelude::WyRand;
+use rand::Rng;
 
 use crate::{
-    ENEMY_SIZE,
+    ENEMY_SIZE, XP_GAIN_GEM,
     gameplay::{
         Health, Speed,
         character_controller::CharacterController,
         enemy::{
             AbilityDamage, AbilityDuration, AbilitySpeed, AbilityTick, Cooldown, DamageCooldown,
             Enemy, EnemyType, HazardousTerrain, HitDamage, Jump, Meele, Owner, Range, Size,
-            get_valid_spawn_position,
+            XpValue, get_valid_spawn_position,
         },
-        player::{Direction, Player},
+        player::{Direction, Player, movement::CameraBounds},
+        rng::{EnemyRng, SpawnRng},
         simple_animation::{AnimationIndices, AnimationTimer},
+        waves::zones::SpawnZones,
     },
     screens::Screen,
 };
@@ -21,6 +24,7 @@ use crate::{
 pub(crate) fn plugin(app: &mut App) {
     app.insert_resource(JumperStats {
         health: 10.0,
+        xp: XP_GAIN_GEM * 2.0,
         damage: 1.0,
         ability_damage: 5.0,
         ability_speed: 200.0,
@@ -57,6 +61,7 @@ pub(crate) struct Jumper;
 #[derive(Resource)]
 pub(crate) struct JumperStats {
     health: f32,
+    xp: f32,
     damage: f32,
     ability_damage: f32,
     ability_speed: f32,
@@ -82,7 +87,7 @@ pub(crate) struct JumperVisual;
 pub(crate) struct JumperAttackIndicator;
 
 #[derive(Event)]
-pub(crate) struct JumperSpawnEvent;
+pub(crate) struct JumperSpawnEvent(pub Option<Vec2>);
 
 #[derive(Event)]
 pub(crate) struct JumperPatchEvent(pub f32, pub String);
@@ -91,12 +96,14 @@ const JUMPER_BUFFER: f32 = 10.0;
 const CURVATURE_COEFFICIENT: f32 = 6.0 / 5.0;
 
 fn spawn_jumper(
-    _trigger: On<JumperSpawnEvent>,
+    trigger: On<JumperSpawnEvent>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     player_q: Query<&Transform, With<Player>>,
-    rng: Single<&mut WyRand, With<GlobalRng>>,
+    rng: Single<&mut WyRand, With<SpawnRng>>,
     spatial_q: SpatialQuery,
+    zones: SpawnZones,
+    camera_bounds: Res<CameraBounds>,
     jumper_q: Query<&Jumper>,
     jumper_stats: Res<JumperStats>,
     mut texture_atlas_layout: ResMut<Assets<TextureAtlasLayout>>,
@@ -107,9 +114,16 @@ fn spawn_jumper(
 
     let stats = jumper_stats;
 
-    let Some(enemy_pos) =
-        get_valid_spawn_position(spatial_q, player_pos.translation.truncate(), rng)
-    else {
+    let Some(enemy_pos) = trigger.0.or_else(|| {
+        get_valid_spawn_position(
+            spatial_q,
+            &zones,
+            &camera_bounds,
+            EnemyType::Jumper,
+            player_pos.translation.truncate(),
+            rng,
+        )
+    }) else {
         // No valid pos
         return Ok(());
     };
@@ -136,6 +150,7 @@ fn spawn_jumper(
                 Transform::from_xyz(enemy_pos.x, enemy_pos.y, 0.0),
                 Visibility::Visible,
                 Health(stats.health),
+                XpValue(stats.xp),
                 HitDamage(stats.damage),
                 AbilityDamage(stats.ability_damage),
                 AbilitySpeed(stats.ability_speed),
@@ -206,6 +221,7 @@ fn patch_jumper(trigger: On<JumperPatchEvent>, mut stats: ResMut<JumperStats>) {
     let (power_level, sprite) = (trigger.0, &trigger.1);
 
     stats.health *= power_level;
+    stats.xp *= power_level;
     stats.damage *= power_level;
     stats.ability_damage *= power_level;
     stats.ability_speed += 50.0 * power_level;
@@ -223,6 +239,7 @@ fn jumper_attack(
     >,
     mut visual_q: Query<&mut Visibility, (With<AbilityVisual>, Without<Jumper>)>,
     player_q: Query<&Transform, With<Player>>,
+    mut rng: Single<&mut WyRand, With<EnemyRng>>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
 ) -> Result {
@@ -239,10 +256,7 @@ fn jumper_attack(
     };
 
     let jumper_pos = transform.translation.truncate();
-    let target_offset = Vec2::new(
-        rand::random_range(-7.5..=7.5) as f32,
-        rand::random_range(-7.5..=7.5) as f32,
-    );
+    let target_offset = Vec2::new(rng.random_range(-7.5..=7.5), rng.random_range(-7.5..=7.5));
 
     let target_pos = player_pos + target_offset;
 
@@ -2,29 +2,35 @@ use std::f32::consts::PI;
 
 use avian2d::prelude::*;
 use bevy::{ecs::relationship::RelationshipSourceCollection, prelude::*};
-use bevy_rand::{global::GlobalRng, prelude::WyRand};
+use bevy_rand::prelude::WyRand;
 use rand::Rng;
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    GameLayer, PLAYER_SIZE, PROJECTILE_SIZE, PausableSystems, PostPhysicsAppSystems,
-    SPAWN_ATTEMPTS, SPAWN_RADIUS,
+    ENEMY_SIZE, GameLayer, PLAYER_SIZE, PROJECTILE_SIZE, PausableSystems, PostPhysicsAppSystems,
+    SPAWN_ATTEMPTS, SPAWN_RADIUS, XP_GAIN_GEM,
     gameplay::{
         Despawn, Health, Speed,
         character_controller::CharacterController,
         damage_numbers::{DamageMessage, DamageType},
         enemy::{
+            elite::Elite,
             jumper::{JumperAttackEvent, JumperAttackIndicator},
             shooter::{ShooterAttackEvent, ShooterProjectileHitEvent},
             sprinter::SprinterAttackEvent,
         },
-        player::{Direction, PlayerHitEvent},
+        player::{Direction, PlayerHitEvent, movement::CameraBounds},
+        rng::{CritRng, SpawnRng},
         simple_animation::HurtAnimationTimer,
+        waves::zones::SpawnZones,
+        weapons::kind::WeaponKind,
     },
     screens::Screen,
 };
 
 use super::player::Player;
 
+pub(crate) mod elite;
 pub(crate) mod jumper;
 pub(crate) mod shooter;
 pub(crate) mod sprinter;
@@ -32,6 +38,7 @@ pub(crate) mod walker;
 
 pub(crate) fn plugin(app: &mut App) {
     app.add_plugins((
+        elite::plugin,
         jumper::plugin,
         walker::plugin,
         shooter::plugin,
@@ -84,6 +91,7 @@ pub(crate) struct DamageCooldown(pub Timer);
     RigidBody::Dynamic,
     Collider = Collider::circle(16.),
     Friction = Friction::ZERO,
+    XpValue(XP_GAIN_GEM),
     CollisionLayers = CollisionLayers::new(GameLayer::Enemy,[
     GameLayer::Enemy,
     GameLayer::Default,
@@ -91,6 +99,10 @@ pub(crate) struct DamageCooldown(pub Timer);
 ]))]
 pub(crate) struct Enemy;
 
+/// Experience dropped by an enemy on death.
+#[derive(Component, Reflect)]
+pub(crate) struct XpValue(pub f32);
+
 #[derive(Event, Reflect)]
 pub(crate) struct PlayerPushingEvent(pub Entity);
 
@@ -99,6 +111,8 @@ pub(crate) struct EnemyDamageEvent {
     pub entity_hit: Entity,
     pub dmg: f32,
     pub damage_type: DamageType,
+    /// The weapon that dealt the damage, `None` for bombs, summons and the like.
+    pub source: Option<WeaponKind>,
 }
 
 #[derive(Event, Reflect)]
@@ -109,7 +123,14 @@ pub(crate) struct EnemyKnockbackEvent {
 }
 
 #[derive(Event, Reflect)]
-pub(crate) struct EnemyDeathEvent(pub Transform);
+pub(crate) struct EnemyDeathEvent {
+    pub transform: Transform,
+    pub enemy_type: EnemyType,
+    pub elite: bool,
+    pub xp: f32,
+    /// The weapon that dealt the final blow.
+    pub killed_by: Option<WeaponKind>,
+}
 
 #[derive(Component, Reflect)]
 pub(crate) struct Colliding;
@@ -135,12 +156,15 @@ pub(crate) struct ProjectileOf(pub Entity);
 #[derive(Reflect)]
 pub(crate) struct EnemyProjectiles(Vec<Entity>);
 
-#[derive(Component, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+#[derive(
+    Component, PartialEq, Eq, Hash, Clone, Copy, Debug, Default, Reflect, Serialize, Deserialize,
+)]
 pub(crate) enum EnemyType {
     Walker,
     Shooter,
     Sprinter,
     Jumper,
+    #[default]
     None,
 }
 
@@ -292,7 +316,11 @@ fn update_animation_movement(mut enemies_q: Query<(&Direction, &mut Sprite), Wit
 fn enemy_take_dmg(
     trigger: On<EnemyDamageEvent>,
     mut damage_writer: MessageWriter<DamageMessage>,
-    mut enemy_q: Query<(&mut Health, &Transform), (With<Enemy>, Without<Despawn>)>,
+    mut enemy_q: Query<
+        (&mut Health, &Transform, &EnemyType, &XpValue, Has<Elite>),
+        (With<Enemy>, Without<Despawn>),
+    >,
+    mut rng: Single<&mut WyRand, With<CritRng>>,
     mut commands: Commands,
 ) {
     let enemy_entity = trigger.entity_hit;
@@ -301,12 +329,11 @@ fn enemy_take_dmg(
         .entity(enemy_entity)
         .insert(HurtAnimationTimer::default());
 
-    if let Ok((mut health, transform)) = enemy_q.get_mut(enemy_entity) {
+    if let Ok((mut health, transform, enemy_type, xp, elite)) = enemy_q.get_mut(enemy_entity) {
         health.0 -= trigger.dmg;
 
         //TODO: GET REAL CRIT
         //TODO: DamageType only really used for effects
-        let mut rng = rand::rng();
         let is_crit = rng.random_bool(0.10);
         damage_writer.write(DamageMessage {
             amount: trigger.dmg as i32,
@@ -316,7 +343,13 @@ fn enemy_take_dmg(
         });
 
         if health.0 <= 0.0 {
-            commands.trigger(EnemyDeathEvent(*transform));
+            commands.trigger(EnemyDeathEvent {
+                transform: *transform,
+                enemy_type: *enemy_type,
+                elite,
+                xp: xp.0,
+                killed_by: trigger.source,
+            });
             commands.entity(enemy_entity).insert(Despawn);
         }
     }
@@ -396,10 +429,8 @@ fn enemy_timer_handle(
 
         if cooldown_timer.0.is_finished() {
             match enemy_type {
-                EnemyType::Shooter => {
-                    if halt.is_some() {
-                        commands.trigger(ShooterAttackEvent(enemy));
-                    }
+                EnemyType::Shooter if halt.is_some() => {
+                    commands.trigger(ShooterAttackEvent(enemy));
                 }
                 //We calculate only in the case so we dont cluter the update loop with unneeded calculations
                 EnemyType::Sprinter => {
@@ -506,7 +537,11 @@ fn terrain_manager(
         ticker.0.tick(time.delta());
 
         if ticker.0.is_finished() && distance <= size.0 {
-            commands.trigger(PlayerHitEvent { dmg: damage.0 });
+            // Only jumpers leave hazardous terrain behind.
+            commands.trigger(PlayerHitEvent {
+                dmg: damage.0,
+                source: EnemyType::Jumper,
+            });
             info!("Terrain_dmg");
             ticker.0.reset();
         }
@@ -519,11 +554,49 @@ fn terrain_manager(
     Ok(())
 }
 
+/// Pick an off-screen spawn position that is not inside a wall.
+///
+/// Points inside the level's [`SpawnZones`] are preferred, then points on the edge of the
+/// camera's [`CameraBounds::spawn_area`], kept inside the map.
+/// Falls back to an unobstructed point on the [`SPAWN_RADIUS`] circle around the player.
 fn get_valid_spawn_position(
     spatial_q: SpatialQuery,
+    zones: &SpawnZones,
+    camera_bounds: &CameraBounds,
+    enemy_type: EnemyType,
     player_pos: Vec2,
-    mut rng: Single<&mut WyRand, With<GlobalRng>>,
+    mut rng: Single<&mut WyRand, With<SpawnRng>>,
 ) -> Option<Vec2> {
+    let collision_filter = SpatialQueryFilter::from_mask(GameLayer::Default);
+
+    if !camera_bounds.viewport.is_empty() {
+        // Far enough out that no part of the enemy is visible.
+        let visible_area = camera_bounds.viewport.inflate(ENEMY_SIZE / 2.0);
+        let is_valid = |point: Vec2| {
+            !visible_area.contains(point)
+                && spatial_q
+                    .point_intersections(point, &collision_filter)
+                    .is_empty()
+        };
+
+        if let Some(position) = zones.pick_position(enemy_type, &mut **rng, is_valid) {
+            return Some(position);
+        }
+
+        let spawn_area = camera_bounds.spawn_area();
+        for _ in 0..SPAWN_ATTEMPTS {
+            let mut desired = random_point_on_edge(spawn_area, &mut **rng);
+            if let Some(map) = camera_bounds.map {
+                let map = map.inflate(-ENEMY_SIZE / 2.0);
+                desired = desired.clamp(map.min, map.max);
+            }
+
+            if is_valid(desired) {
+                return Some(desired);
+            }
+        }
+    }
+
     let mut chosen: Option<Vec2> = None;
     for _ in 0..SPAWN_ATTEMPTS {
         let random_angle: f32 = rng.random_range(0.0..(2. * PI));
@@ -538,8 +611,6 @@ fn get_valid_spawn_position(
             return None;
         };
 
-        let collision_filter = SpatialQueryFilter::from_mask(GameLayer::Default);
-
         if spatial_q
             .cast_ray(desired, direction, distance, true, &collision_filter)
             .is_some()
@@ -552,3 +623,23 @@ fn get_valid_spawn_position(
     }
     chosen
 }
+
+/// A uniformly random point on the outline of `rect`.
+fn random_point_on_edge(rect: Rect, rng: &mut WyRand) -> Vec2 {
+    let size = rect.size();
+    let mut t = rng.random_range(0.0..2.0 * (size.x + size.y));
+
+    if t < size.x {
+        return Vec2::new(rect.min.x + t, rect.min.y);
+    }
+    t -= size.x;
+    if t < size.y {
+        return Vec2::new(rect.max.x, rect.min.y + t);
+    }
+    t -= size.y;
+    if t < size.x {
+        return Vec2::new(rect.max.x - t, rect.max.y);
+    }
+    t -= size.x;
+    Vec2::new(rect.min.x, rect.max.y - t)
+}
@@ -2,24 +2,27 @@ use avian2d::prelude::*;
 
 use bevy::prelude::*;
 
-use bevy_rand::{global::GlobalRng, prelude::WyRand};
+use bevy_rand::prelude::WyRand;
 
 use crate::{
-    GameLayer,
+    GameLayer, XP_GAIN_GEM,
     gameplay::{
         Health, Speed,
         character_controller::CharacterController,
         enemy::{
             AbilityDamage, Cooldown, DamageCooldown, Enemy, EnemyProjectile, EnemyType, HitDamage,
-            ProjectileOf, Range, Ranged, get_valid_spawn_position,
+            ProjectileOf, Range, Ranged, XpValue, get_valid_spawn_position,
         },
-        player::{Direction, Player, PlayerHitEvent},
+        player::{Direction, Player, PlayerHitEvent, movement::CameraBounds},
+        rng::SpawnRng,
+        waves::zones::SpawnZones,
     },
 };
 
 pub(crate) fn plugin(app: &mut App) {
     app.insert_resource(ShooterStats {
         health: 10.0,
+        xp: XP_GAIN_GEM * 1.5,
         damage: 1.0,
         ability_damage: 5.0,
         projectile_speed: 25.0,
@@ -46,6 +49,7 @@ pub(crate) struct Shooter;
 #[derive(Resource)]
 pub(crate) struct ShooterStats {
     health: f32,
+    xp: f32,
     damage: f32,
     ability_damage: f32,
     projectile_speed: f32,
@@ -64,18 +68,20 @@ pub(crate) struct ShooterProjectileHitEvent {
 }
 
 #[derive(Event)]
-pub(crate) struct ShooterSpawnEvent;
+pub(crate) struct ShooterSpawnEvent(pub Option<Vec2>);
 
 #[derive(Event)]
 pub(crate) struct ShooterPatchEvent(pub f32, pub String);
 
 fn spawn_shooter(
-    _trigger: On<ShooterSpawnEvent>,
+    trigger: On<ShooterSpawnEvent>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     player_q: Query<&Transform, With<Player>>,
-    rng: Single<&mut WyRand, With<GlobalRng>>,
+    rng: Single<&mut WyRand, With<SpawnRng>>,
     spatial_q: SpatialQuery,
+    zones: SpawnZones,
+    camera_bounds: Res<CameraBounds>,
     shooter_q: Query<&Shooter>,
     shooter_stats: Res<ShooterStats>,
 ) {
@@ -85,9 +91,16 @@ fn spawn_shooter(
 
     let stats = shooter_stats;
 
-    let Some(enemy_pos) =
-        get_valid_spawn_position(spatial_q, player_pos.translation.truncate(), rng)
-    else {
+    let Some(enemy_pos) = trigger.0.or_else(|| {
+        get_valid_spawn_position(
+            spatial_q,
+            &zones,
+            &camera_bounds,
+            EnemyType::Shooter,
+            player_pos.translation.truncate(),
+            rng,
+        )
+    }) else {
         // No valid pos
         return;
     };
@@ -138,6 +151,7 @@ fn spawn_shooter(
             ..default()
         },
         Health(stats.health),
+        XpValue(stats.xp),
         HitDamage(stats.damage),
         AbilityDamage(stats.ability_damage),
         Range(stats.range),
@@ -149,6 +163,7 @@ fn patch_shooter(trigger: On<ShooterPatchEvent>, mut stats: ResMut<ShooterStats>
     let (power_level, sprite) = (trigger.0, &trigger.1);
 
     stats.health *= power_level;
+    stats.xp *= power_level;
     stats.damage *= power_level;
     stats.ability_damage *= power_level;
     stats.projectile_speed += 50.0 * power_level;
@@ -192,17 +207,20 @@ fn shooter_attack(
 
 fn shooter_projectile_hit(
     trigger: On<ShooterProjectileHitEvent>,
-    shooter_q: Query<&AbilityDamage, With<Shooter>>,
+    shooter_q: Query<(&AbilityDamage, &EnemyType), With<Shooter>>,
     mut commands: Commands,
 ) {
     let projectile = trigger.projectile;
     let shooter = trigger.source;
 
-    let Ok(damage) = shooter_q.get(shooter) else {
+    let Ok((damage, enemy_type)) = shooter_q.get(shooter) else {
         return;
     };
 
-    commands.trigger(PlayerHitEvent { dmg: damage.0 });
+    commands.trigger(PlayerHitEvent {
+        dmg: damage.0,
+        source: *enemy_type,
+    });
 
     commands.entity(projectile).despawn();
 }
@@ -1,17 +1,19 @@
 use avian2d::prelude::*;
 use bevy::prelude::*;
-use bevy_rand::{global::GlobalRng, prelude::WyRand};
+use bevy_rand::prelude::WyRand;
 
 use crate::{
-    ENEMY_SIZE, GameLayer,
+    ENEMY_SIZE, GameLayer, XP_GAIN_GEM,
     gameplay::{
         Health, Speed,
         character_controller::CharacterController,
         enemy::{
             AbilityDamage, AbilitySpeed, Charge, Cooldown, DamageCooldown, Enemy, EnemyType, Halt,
-            HitDamage, Meele, RANGE_BUFFER, Range, get_valid_spawn_position,
+            HitDamage, Meele, RANGE_BUFFER, Range, XpValue, get_valid_spawn_position,
         },
-        player::{Direction, Player, PlayerHitEvent},
+        player::{Direction, Player, PlayerHitEvent, movement::CameraBounds},
+        rng::SpawnRng,
+        waves::zones::SpawnZones,
     },
     screens::Screen,
 };
@@ -19,6 +21,7 @@ use crate::{
 pub(crate) fn plugin(app: &mut App) {
     app.insert_resource(SprinterStats {
         health: 10.0,
+        xp: XP_GAIN_GEM * 1.5,
         damage: 1.0,
         ability_damage: 5.0,
         ability_speed: 500.0,
@@ -53,6 +56,7 @@ pub(crate) struct Sprinter;
 #[derive(Resource)]
 pub(crate) struct SprinterStats {
     health: f32,
+    xp: f32,
     damage: f32,
     ability_damage: f32,
     ability_speed: f32,
@@ -68,18 +72,20 @@ pub(crate) struct SprinterAttackEvent(pub Entity);
 pub(crate) struct SprinterAbilityHitEvent(pub Entity);
 
 #[derive(Event)]
-pub(crate) struct SprinterSpawnEvent;
+pub(crate) struct SprinterSpawnEvent(pub Option<Vec2>);
 
 #[derive(Event)]
 pub(crate) struct SprinterPatchEvent(pub f32, pub String);
 
 fn spawn_sprinter(
-    _trigger: On<SprinterSpawnEvent>,
+    trigger: On<SprinterSpawnEvent>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     player_q: Query<&Transform, With<Player>>,
     spatial_q: SpatialQuery,
-    rng: Single<&mut WyRand, With<GlobalRng>>,
+    zones: SpawnZones,
+    camera_bounds: Res<CameraBounds>,
+    rng: Single<&mut WyRand, With<SpawnRng>>,
     sprinter_q: Query<&Sprinter>,
     sprinter_stats: Res<SprinterStats>,
 ) -> Result {
@@ -89,9 +95,16 @@ fn spawn_sprinter(
 
     let stats = sprinter_stats;
 
-    let Some(enemy_pos) =
-        get_valid_spawn_position(spatial_q, player_pos.translation.truncate(), rng)
-    else {
+    let Some(enemy_pos) = trigger.0.or_else(|| {
+        get_valid_spawn_position(
+            spatial_q,
+            &zones,
+            &camera_bounds,
+            EnemyType::Sprinter,
+            player_pos.translation.truncate(),
+            rng,
+        )
+    }) else {
         // No valid pos
         return Ok(());
     };
@@ -115,6 +128,7 @@ fn spawn_sprinter(
             ..default()
         },
         Health(stats.health),
+        XpValue(stats.xp),
         HitDamage(stats.damage),
         AbilityDamage(stats.ability_damage),
         AbilitySpeed(stats.ability_speed),
@@ -129,6 +143,7 @@ fn patch_sprinter(trigger: On<SprinterPatchEvent>, mut stats: ResMut<SprinterSta
     let (power_level, sprite) = (trigger.0, &trigger.1);
 
     stats.health *= power_level;
+    stats.xp *= power_level;
     stats.damage *= power_level;
     stats.ability_damage *= power_level;
     stats.ability_speed += 50.0 * power_level;
@@ -237,13 +252,16 @@ fn move_charging_sprinter(
 
 fn sprinter_ability_hit(
     trigger: On<SprinterAbilityHitEvent>,
-    sprinter_q: Query<&AbilityDamage, With<Sprinter>>,
+    sprinter_q: Query<(&AbilityDamage, &EnemyType), With<Sprinter>>,
     mut commands: Commands,
 ) {
     let sprinter = trigger.0;
 
-    let Ok(damage) = sprinter_q.get(sprinter) else {
+    let Ok((damage, enemy_type)) = sprinter_q.get(sprinter) else {
         return;
     };
-    commands.trigger(PlayerHitEvent { dmg: damage.0 });
+    commands.trigger(PlayerHitEvent {
+        dmg: damage.0,
+        source: *enemy_type,
+    });
 }
@@ -1,21 +1,26 @@
 use avian2d::prelude::SpatialQuery;
 use bevy::prelude::*;
-use bevy_rand::{global::GlobalRng, prelude::WyRand};
+use bevy_rand::prelude::WyRand;
 
 use crate::{
-    ENEMY_SIZE,
+    ENEMY_SIZE, XP_GAIN_GEM,
     gameplay::{
         Health, Speed,
         character_controller::CharacterController,
-        enemy::{DamageCooldown, Enemy, EnemyType, HitDamage, Meele, get_valid_spawn_position},
-        player::Player,
+        enemy::{
+            DamageCooldown, Enemy, EnemyType, HitDamage, Meele, XpValue, get_valid_spawn_position,
+        },
+        player::{Player, movement::CameraBounds},
+        rng::SpawnRng,
         simple_animation::{AnimationIndices, AnimationTimer},
+        waves::zones::SpawnZones,
     },
 };
 
 pub(crate) fn plugin(app: &mut App) {
     app.insert_resource(WalkerStats {
         health: 10.0,
+        xp: XP_GAIN_GEM,
         damage: 2.0,
         speed: 30.0,
         sprite: "enemies/walker.png".to_string(),
@@ -31,24 +36,27 @@ pub(crate) struct Walker;
 #[derive(Resource)]
 pub(crate) struct WalkerStats {
     health: f32,
+    xp: f32,
     damage: f32,
     speed: f32,
     sprite: String,
 }
 
 #[derive(Event)]
-pub(crate) struct WalkerSpawnEvent;
+pub(crate) struct WalkerSpawnEvent(pub Option<Vec2>);
 
 #[derive(Event)]
 pub(crate) struct WalkerPatchEvent(pub f32, pub String);
 
 fn spawn_walker(
-    _trigger: On<WalkerSpawnEvent>,
+    trigger: On<WalkerSpawnEvent>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     player_q: Query<&Transform, With<Player>>,
     spatial_q: SpatialQuery,
-    rng: Single<&mut WyRand, With<GlobalRng>>,
+    zones: SpawnZones,
+    camera_bounds: Res<CameraBounds>,
+    rng: Single<&mut WyRand, With<SpawnRng>>,
     mut texture_atlas_layout: ResMut<Assets<TextureAtlasLayout>>,
     walker_stats: Res<WalkerStats>,
 ) {
@@ -58,9 +66,16 @@ fn spawn_walker(
 
     let stats = walker_stats;
 
-    let Some(enemy_pos) =
-        get_valid_spawn_position(spatial_q, player_pos.translation.truncate(), rng)
-    else {
+    let Some(enemy_pos) = trigger.0.or_else(|| {
+        get_valid_spawn_position(
+            spatial_q,
+            &zones,
+            &camera_bounds,
+            EnemyType::Walker,
+            player_pos.translation.truncate(),
+            rng,
+        )
+    }) else {
         // No valid pos
         return;
     };
@@ -75,6 +90,7 @@ fn spawn_walker(
         Walker,
         HitDamage(stats.damage),
         Health(stats.health),
+        XpValue(stats.xp),
         Speed(stats.speed),
         Transform::from_xyz(enemy_pos.x, enemy_pos.y, 0.0)
             .with_scale(Vec3::splat((ENEMY_SIZE / 24.0) * 0.7)),
@@ -113,6 +129,7 @@ fn patch_walker(trigger: On<WalkerPatchEvent>, mut stats: ResMut<WalkerStats>) {
     let (power_level, sprite) = (trigger.0, &trigger.1);
     stats.damage *= power_level;
     stats.health *= power_level;
+    stats.xp *= power_level;
     stats.speed += 10.0 * power_level;
     stats.sprite = sprite.clone();
 }
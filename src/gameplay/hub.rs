@@ -0,0 +1,247 @@
+//! The village hub, where quests are picked before travelling to a level.
+//!
+//! NPCs are placed in the hub's Tiled map as objects with a [`QuestBoard`] or [`Horse`]
+//! custom property. Walking up to them and pressing [`Control::Interact`] uses them.
+
+use bevy::{prelude::*, sprite::Text2dShadow};
+use bevy_asset_loader::prelude::*;
+use bevy_ecs_tiled::prelude::{TiledMap, TiledMapAsset};
+use bevy_seedling::sample::{AudioSample, SamplePlayer};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    AssetStates,
+    audio::MusicPool,
+    controls::{Control, Controls, control_just_pressed, key_name},
+    gameplay::{
+        level::{DUNGEON, LevelId, make_colliders_static},
+        player::Player,
+        waves::WavePlanKind,
+    },
+    menus::Menu,
+    screens::Screen,
+};
+
+/// How close the player has to be to use an NPC.
+const INTERACT_RANGE: f32 = 40.0;
+
+/// Without it the NPCs are placed by [`spawn_fallback_npcs`].
+const HUB_MAP: &str = "level/hub/tiled/hub.tmx";
+
+pub(super) fn plugin(app: &mut App) {
+    app.configure_loading_state(
+        LoadingStateConfig::new(AssetStates::AssetLoading).load_collection::<HubAssets>(),
+    );
+
+    app.register_type::<QuestBoard>();
+    app.register_type::<Horse>();
+    app.init_resource::<SelectedQuest>();
+
+    app.add_systems(
+        Update,
+        (
+            spawn_fallback_npcs,
+            show_interact_prompts,
+            interact.run_if(in_state(Menu::None).and(control_just_pressed(Control::Interact))),
+        )
+            .run_if(in_state(Screen::Hub)),
+    );
+
+    app.add_observer(add_interact_prompt::<QuestBoard>);
+    app.add_observer(add_interact_prompt::<Horse>);
+}
+
+#[derive(AssetCollection, Resource)]
+pub(crate) struct HubAssets {
+    #[asset(path = "audio/music/city.ogg")]
+    pub(crate) music: Handle<AudioSample>,
+}
+
+#[derive(Component)]
+struct Hub;
+
+/// Marks a hub whose map failed to load and got NPCs placed without it.
+#[derive(Component)]
+struct FallbackNpcs;
+
+pub fn spawn_hub(
+    mut commands: Commands,
+    hub_assets: Res<HubAssets>,
+    asset_server: Res<AssetServer>,
+) {
+    commands
+        .spawn((
+            Name::new("Hub"),
+            Hub,
+            DespawnOnExit(Screen::Hub),
+            TiledMap(asset_server.load::<TiledMapAsset>(HUB_MAP)),
+            children![(
+                Name::new("Hub Music"),
+                SamplePlayer::new(hub_assets.music.clone()).looping(),
+                MusicPool
+            )],
+        ))
+        .observe(make_colliders_static);
+}
+
+/// Place a quest board and a horse around the hub origin when the hub map is missing.
+fn spawn_fallback_npcs(
+    hub: Single<(Entity, &TiledMap), (With<Hub>, Without<FallbackNpcs>)>,
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+) {
+    let (hub, map) = hub.into_inner();
+    if !asset_server.load_state(&map.0).is_failed() {
+        return;
+    }
+
+    warn!("Failed to load the hub map {HUB_MAP}, placing the NPCs without it");
+    commands
+        .entity(hub)
+        .insert(FallbackNpcs)
+        .with_children(|hub| {
+            hub.spawn((
+                Name::new("Quest Board"),
+                QuestBoard,
+                Sprite::from_color(Color::srgb(0.55, 0.35, 0.2), Vec2::splat(16.0)),
+                Transform::from_xyz(-60.0, 0.0, 5.0),
+            ));
+            hub.spawn((
+                Name::new("Horse"),
+                Horse,
+                Sprite::from_color(Color::srgb(0.75, 0.6, 0.45), Vec2::splat(16.0)),
+                Transform::from_xyz(60.0, 0.0, 5.0),
+            ));
+        });
+}
+
+/// Opens the quest picker.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component, Default)]
+pub(crate) struct QuestBoard;
+
+/// Starts the selected quest.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
+#[reflect(Component, Default)]
+pub(crate) struct Horse;
+
+/// Shown above an NPC while the player is in range.
+#[derive(Component)]
+struct InteractPrompt;
+
+/// A run the player can start from the hub.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default, Reflect, Serialize, Deserialize)]
+pub(crate) enum Quest {
+    #[default]
+    ClearTheDungeon,
+    DungeonRush,
+}
+
+impl Quest {
+    pub const ALL: [Quest; 2] = [Quest::ClearTheDungeon, Quest::DungeonRush];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Quest::ClearTheDungeon => "Clear the Dungeon",
+            Quest::DungeonRush => "Dungeon Rush",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Quest::ClearTheDungeon => "Survive every wave in the dungeon.",
+            Quest::DungeonRush => "Shorter waves, twice the enemies.",
+        }
+    }
+
+    pub fn level(self) -> LevelId {
+        match self {
+            Quest::ClearTheDungeon | Quest::DungeonRush => LevelId::from(DUNGEON),
+        }
+    }
+
+    /// Overrides the wave plan of the level.
+    pub fn wave_plan(self) -> Option<WavePlanKind> {
+        match self {
+            Quest::ClearTheDungeon => None,
+            Quest::DungeonRush => Some(WavePlanKind::Rush),
+        }
+    }
+}
+
+/// The quest accepted at the [`QuestBoard`]. Runs started without one use the default quest.
+#[derive(Resource, Default, Debug)]
+pub(crate) struct SelectedQuest(pub Option<Quest>);
+
+impl SelectedQuest {
+    pub fn quest(&self) -> Quest {
+        self.0.unwrap_or_default()
+    }
+}
+
+fn add_interact_prompt<T: Component>(
+    add: On<Add, T>,
+    mut commands: Commands,
+    controls: Res<Controls>,
+) {
+    commands.entity(add.entity).with_child((
+        InteractPrompt,
+        Text2d::new(format!("[{}]", key_name(controls.key(Control::Interact)))),
+        TextFont::from_font_size(12.0),
+        Text2dShadow::default(),
+        Transform::from_xyz(0.0, 24.0, 10.0),
+        Visibility::Hidden,
+    ));
+}
+
+fn show_interact_prompts(
+    player: Single<&GlobalTransform, With<Player>>,
+    npc_q: Query<(&GlobalTransform, &Children), Or<(With<QuestBoard>, With<Horse>)>>,
+    mut prompt_q: Query<&mut Visibility, With<InteractPrompt>>,
+) {
+    let player_pos = player.translation().truncate();
+
+    for (transform, children) in &npc_q {
+        let visibility =
+            if transform.translation().truncate().distance(player_pos) <= INTERACT_RANGE {
+                Visibility::Visible
+            } else {
+                Visibility::Hidden
+            };
+
+        let mut prompts = prompt_q.iter_many_mut(children);
+        while let Some(mut prompt) = prompts.fetch_next() {
+            prompt.set_if_neq(visibility);
+        }
+    }
+}
+
+/// Use the closest NPC in range.
+fn interact(
+    player: Single<&GlobalTransform, With<Player>>,
+    npc_q: Query<(&GlobalTransform, Has<QuestBoard>), Or<(With<QuestBoard>, With<Horse>)>>,
+    selected_quest: Res<SelectedQuest>,
+    mut next_menu: ResMut<NextState<Menu>>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let player_pos = player.translation().truncate();
+
+    let closest = npc_q
+        .iter()
+        .map(|(transform, is_board)| {
+            (
+                transform.translation().truncate().distance(player_pos),
+                is_board,
+            )
+        })
+        .filter(|(distance, _)| *distance <= INTERACT_RANGE)
+        .min_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    match closest {
+        Some((_, true)) => next_menu.set(Menu::QuestBoard),
+        // The horse only leaves once a quest was accepted.
+        Some((_, false)) if selected_quest.0.is_some() => next_screen.set(Screen::Loading),
+        Some((_, false)) => next_menu.set(Menu::QuestBoard),
+        None => {}
+    }
+}
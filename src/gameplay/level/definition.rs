@@ -0,0 +1,149 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use serde::Deserialize;
+use serde_ron::de::from_bytes;
+use thiserror::Error;
+
+use crate::gameplay::{
+    enemy::EnemyType,
+    level::{LevelId, LevelMap},
+    waves::WavePlanKind,
+};
+
+/// Describes a level, one entry of a `*.levels.ron` file.
+///
+/// Only paths are stored, the map and music are loaded once the level is chosen.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct LevelDefinition {
+    pub id: LevelId,
+    pub name: String,
+    /// Path of the Tiled map.
+    pub map: String,
+    /// Path of the looping background music.
+    pub music: String,
+    pub wave_plan: WavePlanKind,
+    /// Replaces the sprite of an enemy type in every wave.
+    #[serde(default)]
+    pub enemy_sprites: HashMap<EnemyType, String>,
+}
+
+#[derive(Reflect)]
+pub(crate) struct LevelRonLoader {
+    extensions: Vec<&'static str>,
+}
+
+impl LevelRonLoader {
+    pub fn new(extensions: &[&'static str]) -> Self {
+        Self {
+            extensions: extensions.to_owned(),
+        }
+    }
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub(crate) enum LevelRonLoaderError {
+    #[error("Could not read the file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Could not parse RON: {0}")]
+    RonError(#[from] serde_ron::error::SpannedError),
+
+    #[error("A level has an empty id")]
+    EmptyId,
+
+    #[error("The id {0} is used by more than one level")]
+    DuplicateId(LevelId),
+}
+
+/// Every level needs its own id, or a quest couldn't tell which one to load.
+fn validate(definitions: &[LevelDefinition]) -> Result<(), LevelRonLoaderError> {
+    let mut ids = HashSet::new();
+    for definition in definitions {
+        if definition.id.is_empty() {
+            return Err(LevelRonLoaderError::EmptyId);
+        }
+        if !ids.insert(&definition.id) {
+            return Err(LevelRonLoaderError::DuplicateId(definition.id.clone()));
+        }
+    }
+    Ok(())
+}
+
+impl AssetLoader for LevelRonLoader {
+    type Asset = LevelMap;
+    type Settings = ();
+    type Error = LevelRonLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let definitions = from_bytes::<Vec<LevelDefinition>>(&bytes)?;
+        validate(&definitions)?;
+        Ok(LevelMap::from(definitions))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &self.extensions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(ron: &str) -> Vec<LevelDefinition> {
+        serde_ron::from_str(ron).unwrap()
+    }
+
+    #[test]
+    fn levels_are_keyed_by_their_id_from_data() {
+        let definitions = parse(
+            r#"[
+                (id: "dungeon", name: "Dungeon", map: "dungeon.tmx", music: "city.ogg", wave_plan: Standard),
+                (id: "mine", name: "Abandoned Mine", map: "mine.tmx", music: "city.ogg", wave_plan: Rush),
+            ]"#,
+        );
+
+        assert!(validate(&definitions).is_ok());
+        let levels = LevelMap::from(definitions);
+        assert_eq!(levels[&LevelId::from("mine")].name, "Abandoned Mine");
+        assert_eq!(levels[&LevelId::from("dungeon")].name, "Dungeon");
+    }
+
+    #[test]
+    fn level_ids_have_to_be_unique() {
+        let definitions = parse(
+            r#"[
+                (id: "dungeon", name: "Dungeon", map: "dungeon.tmx", music: "city.ogg", wave_plan: Standard),
+                (id: "dungeon", name: "Dungeon Again", map: "dungeon.tmx", music: "city.ogg", wave_plan: Rush),
+            ]"#,
+        );
+
+        assert!(matches!(
+            validate(&definitions),
+            Err(LevelRonLoaderError::DuplicateId(_))
+        ));
+    }
+
+    #[test]
+    fn level_ids_cant_be_empty() {
+        let definitions = parse(
+            r#"[(id: "", name: "Nowhere", map: "dungeon.tmx", music: "city.ogg", wave_plan: Standard)]"#,
+        );
+
+        assert!(matches!(
+            validate(&definitions),
+            Err(LevelRonLoaderError::EmptyId)
+        ));
+    }
+}
@@ -1,45 +1,160 @@
 use avian2d::prelude::{CollisionLayers, RigidBody};
-use bevy::prelude::*;
-use bevy_asset_loader::prelude::*;
+use std::fmt;
+
+use bevy::{platform::collections::HashMap, prelude::*};
 use bevy_ecs_tiled::prelude::{ColliderCreated, TiledEvent, TiledMap, TiledMapAsset};
 use bevy_seedling::sample::{AudioSample, SamplePlayer};
+use serde::Deserialize;
+
+use crate::{
+    GameLayer,
+    audio::MusicPool,
+    gameplay::{
+        data,
+        hub::SelectedQuest,
+        level::definition::{LevelDefinition, LevelRonLoader},
+        waves::WavePlanKind,
+    },
+    screens::Screen,
+};
 
-use crate::{AssetStates, GameLayer, audio::MusicPool, screens::Screen};
+pub(crate) mod definition;
+
+/// Every level, with only the built-in dungeon if the file is missing.
+const LEVELS_FILE: &str = "levels/all.levels.ron";
 
 pub(super) fn plugin(app: &mut App) {
-    app.configure_loading_state(
-        LoadingStateConfig::new(AssetStates::AssetLoading).load_collection::<LevelAssets>(),
-    );
+    app.init_asset::<LevelMap>()
+        .register_asset_loader(LevelRonLoader::new(&["levels.ron"]));
+    data::load_data::<LevelMap>(app, LEVELS_FILE);
+
+    app.add_systems(OnExit(Screen::Gameplay), unload_level);
+}
+
+/// The id of the built-in dungeon, the level of the default quest.
+pub(crate) const DUNGEON: &str = "dungeon";
+
+/// Names a level of [`LEVELS_FILE`], like `"dungeon"`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Reflect, Deserialize, Deref)]
+#[serde(transparent)]
+pub(crate) struct LevelId(String);
+
+impl From<&str> for LevelId {
+    fn from(id: &str) -> Self {
+        LevelId(id.to_string())
+    }
+}
+
+impl fmt::Display for LevelId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
 }
 
-pub fn spawn_level(mut commands: Commands, level_assets: Res<LevelAssets>) {
+/// All [`LevelDefinition`]s by their [`LevelId`].
+#[derive(Asset, Resource, TypePath, Deref, Debug, Clone)]
+pub(crate) struct LevelMap(HashMap<LevelId, LevelDefinition>);
+
+impl From<Vec<LevelDefinition>> for LevelMap {
+    fn from(definitions: Vec<LevelDefinition>) -> Self {
+        LevelMap(
+            definitions
+                .into_iter()
+                .map(|definition| (definition.id.clone(), definition))
+                .collect(),
+        )
+    }
+}
+
+impl Default for LevelMap {
+    fn default() -> Self {
+        LevelMap::from(vec![LevelDefinition {
+            id: LevelId::from(DUNGEON),
+            name: "Dungeon".to_string(),
+            map: "level/dungeon/tiled/dungeon.tmx".to_string(),
+            music: "audio/music/city.ogg".to_string(),
+            wave_plan: WavePlanKind::Standard,
+            enemy_sprites: HashMap::default(),
+        }])
+    }
+}
+
+/// The level chosen for the next run, with its assets.
+/// Inserted by [`load_selected_level`] before entering [`Screen::Gameplay`].
+#[derive(Resource)]
+pub(crate) struct CurrentLevel {
+    pub definition: LevelDefinition,
+    pub map: Handle<TiledMapAsset>,
+    pub music: Handle<AudioSample>,
+}
+
+impl CurrentLevel {
+    pub fn is_loaded(&self, asset_server: &AssetServer) -> bool {
+        asset_server.is_loaded_with_dependencies(&self.map)
+            && asset_server.is_loaded_with_dependencies(&self.music)
+    }
+
+    /// Whether the map, one of its tilesets or the music can't be loaded.
+    pub fn is_failed(&self, asset_server: &AssetServer) -> bool {
+        asset_server
+            .recursive_dependency_load_state(&self.map)
+            .is_failed()
+            || asset_server
+                .recursive_dependency_load_state(&self.music)
+                .is_failed()
+    }
+}
+
+/// Start loading the assets of the level picked by the [`SelectedQuest`].
+/// Returns to the hub if there is no such level.
+pub(crate) fn load_selected_level(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    levels: Res<LevelMap>,
+    selected_quest: Res<SelectedQuest>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let id = selected_quest.quest().level();
+    let Some(definition) = levels.get(&id) else {
+        error!("No level definition for {id}, returning to the hub");
+        next_screen.set(Screen::Hub);
+        return;
+    };
+
+    commands.insert_resource(CurrentLevel {
+        map: asset_server.load(&definition.map),
+        music: asset_server.load(&definition.music),
+        definition: definition.clone(),
+    });
+}
+
+/// Drop the level's handles, so its assets are unloaded between runs.
+fn unload_level(mut commands: Commands) {
+    commands.remove_resource::<CurrentLevel>();
+}
+
+pub fn spawn_level(mut commands: Commands, level: Res<CurrentLevel>) {
     commands
         .spawn((
-            Name::new("Level"),
+            Name::new(level.definition.name.clone()),
             DespawnOnExit(Screen::Gameplay),
-            TiledMap(level_assets.level.clone()),
+            TiledMap(level.map.clone()),
             children![(
                 Name::new("Gameplay Music"),
-                SamplePlayer::new(level_assets.music.clone()).looping(),
+                SamplePlayer::new(level.music.clone()).looping(),
                 MusicPool
             )],
         ))
-        .observe(
-            |collider_created: On<TiledEvent<ColliderCreated>>, mut commands: Commands| {
-                commands.entity(collider_created.event().origin).insert((
-                    RigidBody::Static,
-                    CollisionLayers::new(GameLayer::Default, [GameLayer::Player, GameLayer::Enemy]),
-                ));
-            },
-        );
-}
-
-/// A [`Resource`] that contains all the assets needed to spawn the level.
-/// We use this to preload assets before the level is spawned.
-#[derive(AssetCollection, Resource)]
-pub(crate) struct LevelAssets {
-    #[asset(path = "level/dungeon/tiled/dungeon.tmx")]
-    pub(crate) level: Handle<TiledMapAsset>,
-    #[asset(path = "audio/music/city.ogg")]
-    pub(crate) music: Handle<AudioSample>,
+        .observe(make_colliders_static);
+}
+
+/// Turn the colliders of a Tiled map into static walls for players and enemies.
+pub(crate) fn make_colliders_static(
+    collider_created: On<TiledEvent<ColliderCreated>>,
+    mut commands: Commands,
+) {
+    commands.entity(collider_created.event().origin).insert((
+        RigidBody::Static,
+        CollisionLayers::new(GameLayer::Default, [GameLayer::Player, GameLayer::Enemy]),
+    ));
 }
@@ -0,0 +1,305 @@
+//! Loot dropped by enemies: XP gems, pickups with an instant effect and chests.
+//!
+//! Every enemy type has a [`LootTable`], elites share a richer one.
+
+use bevy::{
+    color::palettes::tailwind::{AMBER_400, RED_500, SKY_400, ZINC_500},
+    platform::collections::HashMap,
+    prelude::*,
+};
+use bevy_rand::prelude::WyRand;
+use bevy_seedling::sample::SamplePlayer;
+use rand::Rng;
+
+use crate::{
+    PLAYER_SIZE, PausableSystems,
+    audio::SfxPool,
+    gameplay::{
+        Despawn, Health, Speed,
+        damage_numbers::{DamageMessage, DamageType},
+        enemy::{Enemy, EnemyDamageEvent, EnemyDeathEvent, EnemyType},
+        healthbar::HealthBarMaterial,
+        overlays::{
+            Overlay,
+            experience::{DropXpGemEvent, Magnetized, XpGem},
+            offers::pick_weighted,
+        },
+        player::{PLAYER_MAX_HEALTH, Player, XpCollectionRange, movement::CameraBounds},
+        rng::LootRng,
+    },
+    screens::Screen,
+};
+
+/// Health restored by a health pickup.
+const HEALTH_PICKUP_AMOUNT: f32 = 30.0;
+
+pub(crate) fn plugin(app: &mut App) {
+    app.insert_resource(LootTables {
+        enemies: HashMap::from([
+            (EnemyType::Walker, LootTable::regular(0.2)),
+            (EnemyType::Shooter, LootTable::regular(0.3)),
+            (EnemyType::Sprinter, LootTable::regular(0.3)),
+            (EnemyType::Jumper, LootTable::regular(0.5)),
+        ]),
+        elite: LootTable {
+            guaranteed: vec![Loot::Chest, Loot::XpGem, Loot::XpGem, Loot::XpGem],
+            weighted: vec![
+                (None, 2.0),
+                (Some(Loot::Health), 1.0),
+                (Some(Loot::Magnet), 1.0),
+                (Some(Loot::Bomb), 0.5),
+            ],
+        },
+    });
+
+    app.add_systems(
+        Update,
+        (collect_loot, fall_back_to_gem_sprite)
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+
+    app.add_observer(spawn_loot)
+        .add_observer(heal_player)
+        .add_observer(magnetize_gems)
+        .add_observer(detonate_bomb)
+        .add_observer(open_chest);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub(crate) enum Loot {
+    XpGem,
+    /// Restores [`HEALTH_PICKUP_AMOUNT`] health.
+    Health,
+    /// Pulls every XP gem towards the player.
+    Magnet,
+    /// Kills every enemy on screen.
+    Bomb,
+    /// Opens [`Overlay::Chest`] with one to three upgrades.
+    Chest,
+}
+
+impl Loot {
+    fn sprite(self) -> &'static str {
+        match self {
+            Loot::XpGem => "xp_gem.png",
+            Loot::Health => "pickups/health.png",
+            Loot::Magnet => "pickups/magnet.png",
+            Loot::Bomb => "pickups/bomb.png",
+            Loot::Chest => "pickups/chest.png",
+        }
+    }
+
+    /// Tint of the XP gem sprite standing in for a missing pickup sprite.
+    fn color(self) -> Color {
+        match self {
+            Loot::XpGem => Color::WHITE,
+            Loot::Health => RED_500.into(),
+            Loot::Magnet => SKY_400.into(),
+            Loot::Bomb => ZINC_500.into(),
+            Loot::Chest => AMBER_400.into(),
+        }
+    }
+}
+
+/// What an enemy drops on death.
+#[derive(Debug, Clone)]
+pub(crate) struct LootTable {
+    /// Dropped every time.
+    pub guaranteed: Vec<Loot>,
+    /// One entry is picked relative to its weight, `None` drops nothing.
+    pub weighted: Vec<(Option<Loot>, f32)>,
+}
+
+impl LootTable {
+    /// A gem, and a small chance for a pickup or chest.
+    fn regular(chest_weight: f32) -> Self {
+        Self {
+            guaranteed: vec![Loot::XpGem],
+            weighted: vec![
+                (None, 200.0),
+                (Some(Loot::Health), 2.0),
+                (Some(Loot::Magnet), 0.5),
+                (Some(Loot::Bomb), 0.3),
+                (Some(Loot::Chest), chest_weight),
+            ],
+        }
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct LootTables {
+    pub enemies: HashMap<EnemyType, LootTable>,
+    pub elite: LootTable,
+}
+
+/// Loot lying on the ground, waiting to be collected.
+#[derive(Component, Reflect)]
+pub(crate) struct LootPickup(pub Loot);
+
+/// Triggered when the player collects a [`LootPickup`].
+#[derive(Event, Reflect)]
+pub(crate) struct LootCollectedEvent(pub Loot);
+
+fn spawn_loot(
+    trigger: On<EnemyDeathEvent>,
+    tables: Res<LootTables>,
+    mut rng: Single<&mut WyRand, With<LootRng>>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let table = if trigger.elite {
+        &tables.elite
+    } else if let Some(table) = tables.enemies.get(&trigger.enemy_type) {
+        table
+    } else {
+        return;
+    };
+
+    let rolled = pick_weighted(table.weighted.clone(), 1, &mut **rng)
+        .into_iter()
+        .flatten();
+    let enemy_pos = trigger.transform.translation.truncate();
+
+    for loot in table.guaranteed.iter().copied().chain(rolled) {
+        let offset = Vec2::new(rng.random_range(-8.0..8.0), rng.random_range(-8.0..8.0));
+        let position = enemy_pos + offset;
+
+        if loot == Loot::XpGem {
+            commands.trigger(DropXpGemEvent {
+                position,
+                value: trigger.xp,
+            });
+            continue;
+        }
+
+        commands.spawn((
+            Name::new(format!("{loot:?} Pickup")),
+            Sprite {
+                image: asset_server.load(loot.sprite()),
+                ..default()
+            },
+            Transform::from_xyz(position.x, position.y, 10.),
+            LootPickup(loot),
+            Speed(200.),
+            DespawnOnExit(Screen::Gameplay),
+        ));
+    }
+}
+
+fn collect_loot(
+    player_q: Single<(&Transform, &XpCollectionRange), With<Player>>,
+    mut pickup_q: Query<(Entity, &mut Transform, &Speed, &LootPickup), Without<Player>>,
+    time: Res<Time>,
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let (player_transform, collection_range) = player_q.into_inner();
+
+    for (entity, mut transform, speed, pickup) in &mut pickup_q {
+        let distance = player_transform.translation.distance(transform.translation);
+
+        if distance <= collection_range.0 {
+            let direction = (player_transform.translation - transform.translation).normalize();
+            transform.translation += direction * (speed.0 * time.delta_secs());
+        }
+
+        if distance <= PLAYER_SIZE / 2.0 {
+            commands.trigger(LootCollectedEvent(pickup.0));
+            commands.spawn((
+                SamplePlayer::new(asset_server.load("audio/sound_effects/xp.wav")),
+                SfxPool,
+            ));
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Show pickups whose sprite can't be loaded as a tinted XP gem.
+fn fall_back_to_gem_sprite(
+    mut pickup_q: Query<(&mut Sprite, &LootPickup)>,
+    asset_server: Res<AssetServer>,
+) {
+    for (mut sprite, pickup) in &mut pickup_q {
+        if asset_server.load_state(&sprite.image).is_failed() {
+            sprite.image = asset_server.load(Loot::XpGem.sprite());
+            sprite.color = pickup.0.color();
+        }
+    }
+}
+
+fn heal_player(
+    trigger: On<LootCollectedEvent>,
+    mut player_q: Query<(&mut Health, &Transform), With<Player>>,
+    mut damage_writer: MessageWriter<DamageMessage>,
+    healthbar_material_q: Query<&MeshMaterial2d<HealthBarMaterial>>,
+    mut health_bar_materials: ResMut<Assets<HealthBarMaterial>>,
+) -> Result {
+    if trigger.0 != Loot::Health {
+        return Ok(());
+    }
+    let Ok((mut health, transform)) = player_q.single_mut() else {
+        return Ok(());
+    };
+
+    health.0 = (health.0 + HEALTH_PICKUP_AMOUNT).min(PLAYER_MAX_HEALTH);
+
+    let handle = healthbar_material_q.single()?;
+    if let Some(material) = health_bar_materials.get_mut(handle) {
+        material.percent = health.0 / PLAYER_MAX_HEALTH;
+    }
+
+    damage_writer.write(DamageMessage {
+        amount: HEALTH_PICKUP_AMOUNT as i32,
+        world_pos: transform.translation.truncate(),
+        crit: false,
+        damage_type: DamageType::Heal,
+    });
+
+    Ok(())
+}
+
+fn magnetize_gems(
+    trigger: On<LootCollectedEvent>,
+    gem_q: Query<Entity, With<XpGem>>,
+    mut commands: Commands,
+) {
+    if trigger.0 != Loot::Magnet {
+        return;
+    }
+
+    for gem in &gem_q {
+        commands.entity(gem).insert(Magnetized);
+    }
+}
+
+fn detonate_bomb(
+    trigger: On<LootCollectedEvent>,
+    enemy_q: Query<(Entity, &Transform, &Health), (With<Enemy>, Without<Despawn>)>,
+    camera_bounds: Res<CameraBounds>,
+    mut commands: Commands,
+) {
+    if trigger.0 != Loot::Bomb {
+        return;
+    }
+
+    for (entity, transform, health) in &enemy_q {
+        if camera_bounds
+            .viewport
+            .contains(transform.translation.truncate())
+        {
+            commands.trigger(EnemyDamageEvent {
+                entity_hit: entity,
+                dmg: health.0,
+                damage_type: DamageType::Fire,
+                source: None,
+            });
+        }
+    }
+}
+
+fn open_chest(trigger: On<LootCollectedEvent>, mut next_overlay: ResMut<NextState<Overlay>>) {
+    if trigger.0 == Loot::Chest {
+        next_overlay.set(Overlay::Chest);
+    }
+}
@@ -2,12 +2,19 @@ use bevy::prelude::*;
 
 pub(crate) mod abilities;
 pub(crate) mod character_controller;
+pub(crate) mod crafting;
 pub(crate) mod damage_numbers;
+pub(crate) mod data;
 pub(crate) mod enemy;
 pub(crate) mod healthbar;
+pub(crate) mod hub;
 pub(crate) mod level;
+pub(crate) mod loot;
 pub(crate) mod overlays;
 pub(crate) mod player;
+pub(crate) mod replay;
+pub(crate) mod rng;
+pub(crate) mod run_stats;
 pub(crate) mod simple_animation;
 pub(crate) mod waves;
 pub(crate) mod weapons;
@@ -24,12 +31,18 @@ pub(crate) struct Despawn;
 pub(crate) fn plugin(app: &mut App) {
     app.add_plugins((
         abilities::plugin,
+        crafting::plugin,
         damage_numbers::plugin,
         enemy::plugin,
         healthbar::plugin,
+        hub::plugin,
         level::plugin,
+        loot::plugin,
         overlays::plugin,
         player::plugin,
+        replay::plugin,
+        rng::plugin,
+        run_stats::plugin,
         weapons::plugin,
         waves::plugin,
         simple_animation::plugin,
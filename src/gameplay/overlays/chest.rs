@@ -0,0 +1,257 @@
+//! The treasure chest overlay, opened by collecting a chest.
+use bevy::{prelude::*, text::FontSmoothing};
+use bevy_rand::prelude::WyRand;
+use rand::Rng;
+
+use crate::{
+    gameplay::{
+        overlays::{
+            Overlay, OverlayChoice,
+            offers::{BanishedWeapons, Offer, owned_weapons, pick_weighted, roll_weapon_offers},
+        },
+        player::{InInventoryOf, Player},
+        rng::OfferRng,
+        weapons::{
+            components::{BaseDamage, Weapon, WeaponLevel},
+            kind::WeaponKind,
+            spec::WeaponMap,
+        },
+    },
+    save::SaveData,
+    theme::{palette::*, prelude::*},
+};
+
+/// Seconds the first reward spins before it is revealed.
+const ROLL_DURATION: f32 = 1.2;
+/// Extra seconds before each following reward is revealed.
+const REVEAL_STAGGER: f32 = 0.4;
+/// Seconds each icon is shown while spinning.
+const ROLL_FRAME: f32 = 0.08;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<ChestRewards>();
+    app.init_resource::<ChestRoll>();
+    app.add_systems(
+        OnEnter(Overlay::Chest),
+        (roll_chest, spawn_chest_menu).chain(),
+    );
+    // The game is paused while the chest is open, so this runs on real time.
+    app.add_systems(Update, animate_chest_roll.run_if(in_state(Overlay::Chest)));
+    app.add_observer(apply_chest_choice);
+}
+
+/// The upgrades granted by the open chest.
+#[derive(Resource, Default, Debug)]
+pub(crate) struct ChestRewards(Vec<Offer>);
+
+/// Progress of the roll animation.
+#[derive(Resource, Default, Debug)]
+struct ChestRoll {
+    elapsed: f32,
+    next_frame: f32,
+}
+
+impl ChestRoll {
+    fn is_revealed(&self, index: usize) -> bool {
+        self.elapsed >= ROLL_DURATION + index as f32 * REVEAL_STAGGER
+    }
+}
+
+/// The icon of the reward at this index in [`ChestRewards`].
+#[derive(Component)]
+struct RewardIcon(usize);
+
+/// The text of the reward at this index in [`ChestRewards`].
+#[derive(Component)]
+struct RewardText(usize);
+
+fn roll_chest(
+    mut rewards: ResMut<ChestRewards>,
+    mut roll: ResMut<ChestRoll>,
+    weapons: Res<WeaponMap>,
+    banished: Res<BanishedWeapons>,
+    player: Single<Entity, With<Player>>,
+    owned_q: Query<(&WeaponKind, &InInventoryOf, &WeaponLevel, &BaseDamage), With<Weapon>>,
+    mut rng: Single<&mut WyRand, With<OfferRng>>,
+    save: Res<SaveData>,
+) {
+    let count = pick_weighted(vec![(1, 6.0), (2, 3.0), (3, 1.0)], 1, &mut **rng)
+        .pop()
+        .unwrap_or(1);
+    let owned = owned_weapons(*player, &owned_q);
+
+    rewards.0 = roll_weapon_offers(count, &weapons, &banished, &owned, &save, &mut **rng);
+    *roll = ChestRoll::default();
+}
+
+fn spawn_chest_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    rewards: Res<ChestRewards>,
+) {
+    let border_image = asset_server.load("kenny/panel-border-011.png");
+    let font: Handle<Font> = asset_server.load("ui/compass.ttf");
+
+    commands
+        .spawn((widget::ui_root("ChestRoot"), DespawnOnExit(Overlay::Chest)))
+        .with_children(|parent| {
+            parent.spawn(widget::header("Treasure Chest"));
+
+            parent
+                .spawn((
+                    Name::new("Rewards"),
+                    Node {
+                        column_gap: Val::Px(40.0),
+                        padding: UiRect::all(Val::Px(40.0)),
+                        ..default()
+                    },
+                ))
+                .with_children(|parent| {
+                    if rewards.0.is_empty() {
+                        parent.spawn(widget::label("The chest is empty"));
+                    }
+
+                    for index in 0..rewards.0.len() {
+                        parent.spawn(reward_card(index, border_image.clone(), &font));
+                    }
+                });
+
+            parent.spawn(widget::button("Claim", claim));
+        });
+}
+
+fn reward_card(index: usize, border_image: Handle<Image>, font: &Handle<Font>) -> impl Bundle {
+    (
+        Name::new(format!("Reward {index}")),
+        Node {
+            width: Val::Px(240.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(20.0),
+            padding: UiRect::all(Val::Px(30.0)),
+            ..default()
+        },
+        ImageNode {
+            image: border_image,
+            image_mode: NodeImageMode::Sliced(TextureSlicer {
+                border: BorderRect::all(22.0),
+                center_scale_mode: SliceScaleMode::Stretch,
+                sides_scale_mode: SliceScaleMode::Stretch,
+                max_corner_scale: 1.0,
+            }),
+            ..default()
+        },
+        BackgroundColor(CARD_BACKGROUND.into()),
+        Children::spawn((
+            Spawn((
+                Node {
+                    width: Val::Px(128.),
+                    height: Val::Px(128.),
+                    ..default()
+                },
+                ImageNode::default(),
+                RewardIcon(index),
+            )),
+            Spawn((
+                Text::new("?"),
+                TextFont {
+                    font: font.clone(),
+                    font_size: 24.0,
+                    font_smoothing: FontSmoothing::None,
+                    ..default()
+                },
+                TextLayout::new_with_justify(Justify::Center),
+                TextColor(Color::WHITE),
+                RewardText(index),
+            )),
+        )),
+    )
+}
+
+fn animate_chest_roll(
+    time: Res<Time<Real>>,
+    mut roll: ResMut<ChestRoll>,
+    rewards: Res<ChestRewards>,
+    weapons: Res<WeaponMap>,
+    mut icon_q: Query<(&mut ImageNode, &RewardIcon)>,
+    mut text_q: Query<(&mut Text, &mut TextColor, &RewardText)>,
+) {
+    roll.elapsed += time.delta_secs();
+    let spin = roll.elapsed >= roll.next_frame;
+    if spin {
+        roll.next_frame = roll.elapsed + ROLL_FRAME;
+    }
+
+    let icons: Vec<Handle<Image>> = weapons.values().map(|spec| spec.icon.clone()).collect();
+    // The spin depends on the frame rate, so it must not draw from the seeded run RNGs.
+    let mut rng = rand::rng();
+
+    for (mut icon, RewardIcon(index)) in &mut icon_q {
+        let Some(reward) = rewards.0.get(*index) else {
+            continue;
+        };
+
+        if roll.is_revealed(*index) {
+            let final_icon = &weapons[&reward.kind].icon;
+            if icon.image != *final_icon {
+                icon.image = final_icon.clone();
+            }
+        } else if spin && !icons.is_empty() {
+            icon.image = icons[rng.random_range(0..icons.len())].clone();
+        }
+    }
+
+    for (mut text, mut color, RewardText(index)) in &mut text_q {
+        let Some(reward) = rewards.0.get(*index) else {
+            continue;
+        };
+
+        if roll.is_revealed(*index) && text.0 == "?" {
+            text.0 = format!(
+                "{}\n{}",
+                reward.effect.title(),
+                reward.effect.description(reward.kind)
+            );
+            color.0 = reward.rarity.color();
+        }
+    }
+}
+
+/// Skip the roll animation, or grant every reward once all are revealed.
+fn claim(
+    _: On<Pointer<Click>>,
+    mut roll: ResMut<ChestRoll>,
+    rewards: Res<ChestRewards>,
+    mut commands: Commands,
+) {
+    let last = rewards.0.len().saturating_sub(1);
+    if !rewards.0.is_empty() && !roll.is_revealed(last) {
+        roll.elapsed = ROLL_DURATION + last as f32 * REVEAL_STAGGER;
+        return;
+    }
+
+    commands.trigger(OverlayChoice::ClaimChest);
+}
+
+/// Claim the chest without waiting for the roll, for runs without anyone at the menu.
+pub(crate) fn claim_now(mut commands: Commands) {
+    commands.trigger(OverlayChoice::ClaimChest);
+}
+
+/// Grant every reward and close the chest.
+fn apply_chest_choice(
+    choice: On<OverlayChoice>,
+    overlay: Res<State<Overlay>>,
+    rewards: Res<ChestRewards>,
+    mut next_overlay: ResMut<NextState<Overlay>>,
+    mut commands: Commands,
+) {
+    if *choice.event() != OverlayChoice::ClaimChest || *overlay.get() != Overlay::Chest {
+        return;
+    }
+
+    for reward in &rewards.0 {
+        reward.apply(&mut commands);
+    }
+    next_overlay.set(Overlay::None);
+}
@@ -0,0 +1,213 @@
+//! The crafting menu, opened at a crafting station.
+use bevy::{
+    prelude::*, text::FontSmoothing, ui::auto_directional_navigation::AutoDirectionalNavigation,
+};
+
+use crate::{
+    gameplay::{
+        crafting::{
+            materials::MaterialPouch,
+            recipe::{CraftRecipeEvent, Recipe, RecipeBook},
+        },
+        overlays::{Overlay, OverlayChoice},
+        player::{InInventoryOf, Player},
+        weapons::{components::Weapon, kind::WeaponKind},
+    },
+    theme::{palette::*, prelude::*},
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Overlay::Crafting), spawn_crafting_menu);
+    app.add_observer(apply_crafting_choice);
+}
+
+#[derive(Component)]
+struct RecipeIndex(usize);
+
+fn spawn_crafting_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    recipes: Res<RecipeBook>,
+    player: Single<(Entity, &MaterialPouch), With<Player>>,
+    weapon_q: Query<(&WeaponKind, &InInventoryOf), With<Weapon>>,
+) {
+    let border_image = asset_server.load("kenny/panel-border-011.png");
+    let font: Handle<Font> = asset_server.load("ui/compass.ttf");
+
+    let (player, pouch) = player.into_inner();
+    let owned: Vec<WeaponKind> = weapon_q
+        .iter()
+        .filter(|(_, owner)| owner.0 == player)
+        .map(|(kind, _)| *kind)
+        .collect();
+
+    commands
+        .spawn((
+            widget::ui_root("CraftingRoot"),
+            DespawnOnExit(Overlay::Crafting),
+        ))
+        .with_children(|parent| {
+            parent.spawn(widget::header("Crafting Station"));
+
+            parent
+                .spawn((
+                    Name::new("Recipes"),
+                    Node {
+                        width: Val::Percent(100.0),
+                        column_gap: Val::Px(20.0),
+                        padding: UiRect::horizontal(Val::Px(50.0)),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                ))
+                .with_children(|parent| {
+                    if recipes.is_empty() {
+                        parent.spawn(widget::label("No recipes known"));
+                    }
+
+                    for (index, recipe) in recipes.iter().enumerate() {
+                        let craftable = recipe.can_craft(pouch, &owned);
+                        let mut card = parent.spawn((
+                            recipe_card(recipe, pouch, &owned, border_image.clone(), &font),
+                            RecipeIndex(index),
+                        ));
+
+                        if craftable {
+                            card.insert((
+                                Button,
+                                InteractionPalette {
+                                    none: CARD_BACKGROUND.into(),
+                                    hovered: CARD_HOVERED_BACKGROUND.into(),
+                                    pressed: CARD_PRESSED_BACKGROUND.into(),
+                                },
+                                AutoDirectionalNavigation::default(),
+                            ))
+                            .observe(craft);
+                        } else {
+                            card.insert(BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.9)));
+                        }
+                    }
+                });
+
+            parent.spawn(widget::button("Leave", leave));
+        });
+}
+
+fn recipe_card(
+    recipe: &Recipe,
+    pouch: &MaterialPouch,
+    owned: &[WeaponKind],
+    border_image: Handle<Image>,
+    font: &Handle<Font>,
+) -> impl Bundle {
+    let text_font = TextFont {
+        font: font.clone(),
+        font_size: 18.0,
+        font_smoothing: FontSmoothing::None,
+        ..default()
+    };
+
+    let mut lines: Vec<(String, Color)> = recipe
+        .materials
+        .iter()
+        .map(|cost| {
+            let have = pouch.count(cost.material, cost.quality);
+            let color = if have >= cost.amount {
+                cost.quality.color()
+            } else {
+                Color::srgb(0.6, 0.2, 0.2)
+            };
+            (
+                format!(
+                    "{have}/{} {:?} {}",
+                    cost.amount,
+                    cost.quality,
+                    cost.material.display_name()
+                ),
+                color,
+            )
+        })
+        .collect();
+
+    for kind in std::iter::once(&recipe.result.base).chain(&recipe.weapons) {
+        let color = if owned.contains(kind) {
+            Color::WHITE
+        } else {
+            Color::srgb(0.6, 0.2, 0.2)
+        };
+        lines.push((format!("Owned: {kind:?}"), color));
+    }
+
+    (
+        Name::new(format!("Recipe {}", recipe.name)),
+        Node {
+            width: Val::Px(280.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(10.0),
+            padding: UiRect::all(Val::Px(30.0)),
+            ..default()
+        },
+        ImageNode {
+            image: border_image,
+            image_mode: NodeImageMode::Sliced(TextureSlicer {
+                border: BorderRect::all(22.0),
+                center_scale_mode: SliceScaleMode::Stretch,
+                sides_scale_mode: SliceScaleMode::Stretch,
+                max_corner_scale: 1.0,
+            }),
+            ..default()
+        },
+        BackgroundColor(CARD_BACKGROUND.into()),
+        Children::spawn((
+            Spawn((
+                Text::new(recipe.name.clone()),
+                TextFont {
+                    font_size: 24.0,
+                    ..text_font.clone()
+                },
+                TextLayout::new_with_justify(Justify::Center),
+                TextColor(Color::WHITE),
+                Pickable::IGNORE,
+            )),
+            SpawnIter(lines.into_iter().map(move |(line, color)| {
+                (
+                    Text::new(line),
+                    text_font.clone(),
+                    TextColor(color),
+                    Pickable::IGNORE,
+                )
+            })),
+        )),
+    )
+}
+
+fn craft(trigger: On<Pointer<Click>>, recipe_q: Query<&RecipeIndex>, mut commands: Commands) {
+    let Ok(recipe) = recipe_q.get(trigger.entity) else {
+        return;
+    };
+
+    commands.trigger(OverlayChoice::Craft(recipe.0));
+}
+
+fn leave(_: On<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(OverlayChoice::LeaveCrafting);
+}
+
+fn apply_crafting_choice(
+    choice: On<OverlayChoice>,
+    overlay: Res<State<Overlay>>,
+    mut next_overlay: ResMut<NextState<Overlay>>,
+    mut commands: Commands,
+) {
+    if *overlay.get() != Overlay::Crafting {
+        return;
+    }
+
+    match *choice.event() {
+        OverlayChoice::Craft(index) => commands.trigger(CraftRecipeEvent { index }),
+        OverlayChoice::LeaveCrafting => {}
+        _ => return,
+    }
+    next_overlay.set(Overlay::None);
+}
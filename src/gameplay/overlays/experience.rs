@@ -1,5 +1,5 @@
 use bevy::{
-    color::palettes::tailwind::{AMBER_300, SLATE_900},
+    color::palettes::tailwind::{AMBER_300, EMERALD_400, ROSE_500, SKY_300, SLATE_900},
     prelude::*,
     render::render_resource::AsBindGroup,
     shader::ShaderRef,
@@ -8,13 +8,13 @@ use bevy::{
 use bevy_seedling::sample::SamplePlayer;
 
 use crate::{
-    PLAYER_SIZE, XP_GAIN_GEM,
+    PLAYER_SIZE,
     audio::SfxPool,
     gameplay::{
         Speed,
-        enemy::EnemyDeathEvent,
         overlays::Overlay,
-        player::{Level, Player, XP, XpCollectionRange},
+        player::{Level, Player, XP, XpCollectionRange, xp_curve::XpCurve},
+        run_stats::RunStats,
     },
     screens::Screen,
 };
@@ -22,10 +22,14 @@ use crate::{
 pub(crate) fn plugin(app: &mut App) {
     app.add_plugins(UiMaterialPlugin::<XpBarMaterial>::default());
 
-    app.add_systems(OnEnter(Screen::Gameplay), spawn_xp_bar);
+    app.init_resource::<PendingLevelUps>();
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        (spawn_xp_bar, reset_pending_level_ups),
+    );
     app.add_systems(
         Update,
-        (collect_xp_gem, update_xp_bar).run_if(in_state(Screen::Gameplay)),
+        (collect_xp_gem, update_gem_tier, update_xp_bar).run_if(in_state(Screen::Gameplay)),
     );
 
     app.world_mut().spawn((
@@ -38,40 +42,143 @@ pub(crate) fn plugin(app: &mut App) {
         .spawn((Observer::new(level_up), Name::new("level_up Observer")));
 }
 
+/// Most gems lying around at once, further drops merge into existing gems.
+const MAX_XP_GEMS: usize = 300;
+/// How far a dropped gem looks for an existing gem to merge into once the cap is reached.
+const GEM_MERGE_RADIUS: f32 = 96.0;
+/// Most levels a single XP grant can advance, in case the curve needs next to no XP.
+const MAX_LEVEL_UPS_PER_GAIN: u32 = 100;
+
 #[derive(Component, Reflect)]
-pub(crate) struct XpGem;
+pub(crate) struct XpGem {
+    pub value: f32,
+}
+
+/// The gem that collects all drops past [`MAX_XP_GEMS`] with no gem nearby.
+#[derive(Component, Reflect)]
+struct OverflowGem;
+
+/// A gem that flies to the player regardless of the collection range.
+#[derive(Component, Reflect)]
+pub(crate) struct Magnetized;
+
+/// Drop an [`XpGem`] worth `value` at `position`.
+#[derive(Event, Reflect)]
+pub(crate) struct DropXpGemEvent {
+    pub position: Vec2,
+    pub value: f32,
+}
 
 #[derive(Event, Reflect)]
-pub(crate) struct GainXpEvent;
+pub(crate) struct GainXpEvent(pub f32);
 
 #[derive(Event, Reflect)]
 pub(crate) struct LevelUpEvent;
 
-const BASE_LEVEL_XP: f32 = 100.;
+/// Level ups not yet claimed on the level up screen, including the one shown.
+#[derive(Resource, Default, Debug)]
+pub(crate) struct PendingLevelUps(pub u32);
+
+/// The level number shown next to the XP bar.
+#[derive(Component)]
+struct LevelLabel;
+
+/// How a gem looks, by its value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GemTier {
+    Small,
+    Medium,
+    Large,
+    Huge,
+}
+
+impl GemTier {
+    fn from_value(value: f32) -> Self {
+        match value {
+            v if v < 25.0 => GemTier::Small,
+            v if v < 100.0 => GemTier::Medium,
+            v if v < 500.0 => GemTier::Large,
+            _ => GemTier::Huge,
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            GemTier::Small => SKY_300.into(),
+            GemTier::Medium => EMERALD_400.into(),
+            GemTier::Large => AMBER_300.into(),
+            GemTier::Huge => ROSE_500.into(),
+        }
+    }
+
+    fn scale(self) -> f32 {
+        match self {
+            GemTier::Small => 1.0,
+            GemTier::Medium => 1.2,
+            GemTier::Large => 1.4,
+            GemTier::Huge => 1.7,
+        }
+    }
+}
 
 fn spawn_xp_gem(
-    trigger: On<EnemyDeathEvent>,
+    trigger: On<DropXpGemEvent>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    mut gem_q: Query<(&Transform, &mut XpGem, Has<OverflowGem>)>,
 ) {
-    let enemy_pos = trigger.0.translation;
+    let DropXpGemEvent { position, value } = *trigger.event();
+    let at_cap = gem_q.iter().count() >= MAX_XP_GEMS;
+
+    if at_cap {
+        let nearest = gem_q
+            .iter_mut()
+            .map(|(transform, gem, overflow)| {
+                let distance = transform.translation.truncate().distance(position);
+                (distance, gem, overflow)
+            })
+            .filter(|(distance, _, overflow)| *distance <= GEM_MERGE_RADIUS || *overflow)
+            // Any nearby gem beats the overflow gem.
+            .min_by(|(a, _, a_overflow), (b, _, b_overflow)| {
+                a_overflow.cmp(b_overflow).then(a.total_cmp(b))
+            });
+
+        if let Some((_, mut gem, _)) = nearest {
+            gem.value += value;
+            return;
+        }
+    }
 
-    commands.spawn((
+    let mut gem = commands.spawn((
         Name::new("XpGem"),
         Sprite {
             image: asset_server.load("xp_gem.png"),
             ..default()
         },
-        Transform::from_xyz(enemy_pos.x, enemy_pos.y, 10.),
-        XpGem,
+        Transform::from_xyz(position.x, position.y, 10.),
+        XpGem { value },
         Speed(200.),
+        DespawnOnExit(Screen::Gameplay),
     ));
+
+    if at_cap {
+        gem.insert(OverflowGem);
+    }
+}
+
+fn update_gem_tier(mut gem_q: Query<(&XpGem, &mut Sprite, &mut Transform), Changed<XpGem>>) {
+    for (gem, mut sprite, mut transform) in &mut gem_q {
+        let tier = GemTier::from_value(gem.value);
+        sprite.color = tier.color();
+        transform.scale = Vec3::splat(tier.scale());
+    }
 }
 
 fn collect_xp_gem(
     player_q: Query<(&Transform, &XpCollectionRange), With<Player>>,
-    mut gem_q: Query<(&mut Transform, &Speed, Entity), (With<XpGem>, Without<Player>)>,
+    mut gem_q: Query<(&mut Transform, &Speed, &XpGem, Entity, Has<Magnetized>), Without<Player>>,
     time: Res<Time>,
+    mut stats: ResMut<RunStats>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
 ) -> Result {
@@ -79,28 +186,24 @@ fn collect_xp_gem(
         return Ok(());
     };
 
-    for (mut gem_position, gem_speed, gem_entity) in &mut gem_q {
-        if (player_position
-            .translation
-            .distance(gem_position.translation))
-            <= collection_range.0
-        {
-            let direction = (player_position.translation - gem_position.translation).normalize();
-            let movement = direction * (gem_speed.0 * time.delta_secs());
-            gem_position.translation += movement;
-        }
+    let player_position = player_position.translation;
+    let pickup_range_squared = (PLAYER_SIZE / 2.0).powi(2);
+    let collection_range_squared = collection_range.0.powi(2);
+
+    for (mut gem_position, gem_speed, gem, gem_entity, magnetized) in &mut gem_q {
+        let distance_squared = player_position.distance_squared(gem_position.translation);
 
-        if (player_position
-            .translation
-            .distance(gem_position.translation))
-            <= PLAYER_SIZE / 2.0
-        {
-            commands.trigger(GainXpEvent);
+        if distance_squared <= pickup_range_squared {
+            commands.trigger(GainXpEvent(gem.value));
+            stats.gems_collected += 1;
             commands.spawn((
                 SamplePlayer::new(asset_server.load("audio/sound_effects/xp.wav")),
                 SfxPool,
             ));
             commands.entity(gem_entity).despawn();
+        } else if magnetized || distance_squared <= collection_range_squared {
+            let direction = (player_position - gem_position.translation).normalize();
+            gem_position.translation += direction * (gem_speed.0 * time.delta_secs());
         }
     }
 
@@ -108,24 +211,30 @@ fn collect_xp_gem(
 }
 
 fn gain_xp(
-    _trigger: On<GainXpEvent>,
-    mut player_q: Query<(&Level, &mut XP), With<Player>>,
+    trigger: On<GainXpEvent>,
+    mut player_q: Query<(&mut Level, &mut XP), With<Player>>,
+    curve: Res<XpCurve>,
     mut commands: Commands,
     asset_server: Res<AssetServer>,
 ) -> Result {
-    let (player_level, mut player_xp) = player_q.single_mut()?;
-    let xp_needed = BASE_LEVEL_XP * player_level.0.powf(2.);
+    let (mut player_level, mut player_xp) = player_q.single_mut()?;
+
+    player_xp.0 += trigger.0;
 
-    player_xp.0 += XP_GAIN_GEM;
+    let mut level_ups = 0;
+    // A big grant can skip several levels, the remainder carries over.
+    while player_xp.0 >= curve.xp_needed(player_level.0) && level_ups < MAX_LEVEL_UPS_PER_GAIN {
+        player_xp.0 -= curve.xp_needed(player_level.0);
+        player_level.0 += 1.;
+        commands.trigger(LevelUpEvent);
+        level_ups += 1;
+    }
 
-    if player_xp.0 >= xp_needed {
-        //Level Up
+    if level_ups > 0 {
         commands.spawn((
             SamplePlayer::new(asset_server.load("audio/sound_effects/level.wav")),
             SfxPool,
         ));
-        commands.trigger(LevelUpEvent);
-        player_xp.0 = 0.;
     }
 
     Ok(())
@@ -133,14 +242,20 @@ fn gain_xp(
 
 fn level_up(
     _trigger: On<LevelUpEvent>,
-    mut player_q: Query<&mut Level, With<Player>>,
+    mut pending: ResMut<PendingLevelUps>,
+    overlay: Res<State<Overlay>>,
     mut next_state: ResMut<NextState<Overlay>>,
-) -> Result {
-    let mut level = player_q.single_mut()?;
-    level.0 += 1.;
+) {
+    pending.0 += 1;
 
-    next_state.set(Overlay::LevelUp);
-    Ok(())
+    // Queued level ups are shown by the open level up screen, one after another.
+    if *overlay.get() != Overlay::LevelUp {
+        next_state.set(Overlay::LevelUp);
+    }
+}
+
+fn reset_pending_level_ups(mut pending: ResMut<PendingLevelUps>) {
+    pending.0 = 0;
 }
 
 fn spawn_xp_bar(mut commands: Commands, mut ui_materials: ResMut<Assets<XpBarMaterial>>) {
@@ -158,6 +273,17 @@ fn spawn_xp_bar(mut commands: Commands, mut ui_materials: ResMut<Assets<XpBarMat
             DespawnOnExit(Screen::Gameplay),
         ))
         .with_children(|parent| {
+            parent.spawn((
+                Name::new("Level Label"),
+                LevelLabel,
+                Text::new("Lv 1"),
+                TextFont::from_font_size(24.0),
+                TextColor(AMBER_300.into()),
+                Node {
+                    margin: UiRect::left(Px(20.)),
+                    ..default()
+                },
+            ));
             parent.spawn((
                 Node {
                     width: Percent(100.0),
@@ -181,14 +307,16 @@ fn spawn_xp_bar(mut commands: Commands, mut ui_materials: ResMut<Assets<XpBarMat
 
 fn update_xp_bar(
     player_q: Query<(&XP, &Level), With<Player>>,
+    curve: Res<XpCurve>,
     mut materials: ResMut<Assets<XpBarMaterial>>,
     xp_bar_material_q: Query<&MaterialNode<XpBarMaterial>>,
+    mut level_label: Single<&mut Text, With<LevelLabel>>,
 ) -> Result {
     let Ok((xp, level)) = player_q.single() else {
         return Ok(());
     };
 
-    let xp_needed = BASE_LEVEL_XP * level.0.powf(2.);
+    let xp_needed = curve.xp_needed(level.0);
 
     let factor = if xp_needed > 0. { xp.0 / xp_needed } else { 0. };
 
@@ -198,6 +326,11 @@ fn update_xp_bar(
         material.factor = factor;
     }
 
+    let label = format!("Lv {}", level.0 as u32);
+    if level_label.0 != label {
+        level_label.0 = label;
+    }
+
     Ok(())
 }
 
@@ -1,27 +1,54 @@
 //! The level up menu.
-use bevy::{color::palettes::basic, prelude::*, text::FontSmoothing};
-use bevy_rand::{global::GlobalRng, prelude::WyRand};
-use rand::Rng;
+use bevy::{
+    prelude::*, text::FontSmoothing, ui::auto_directional_navigation::AutoDirectionalNavigation,
+};
 
 use crate::{
     gameplay::{
-        overlays::Overlay,
-        weapons::{kind::WeaponKind, spec::WeaponMap, systems::pickup::PickUpWeaponEvent},
+        overlays::{
+            Overlay, OverlayChoice,
+            experience::PendingLevelUps,
+            offers::{
+                BanishedWeapons, LevelUpCharges, LevelUpOffers, Offer, OfferEffect, roll_offers,
+            },
+        },
+        weapons::spec::WeaponMap,
     },
-    theme::widget,
+    theme::{palette::*, prelude::*},
 };
 
-const NUMBER_OF_ITEM_CHOICES: usize = 3;
-
 pub(super) fn plugin(app: &mut App) {
-    app.add_systems(OnEnter(Overlay::LevelUp), spawn_level_up_menu);
+    app.init_resource::<BanishMode>();
+    app.add_systems(
+        OnEnter(Overlay::LevelUp),
+        (reset_banish_mode, roll_offers, spawn_level_up_menu).chain(),
+    );
+    app.add_observer(apply_level_up_choice);
+}
+
+/// Marks the root of the level up menu so it can be rebuilt after a reroll.
+#[derive(Component)]
+struct LevelUpMenu;
+
+/// Index of the [`Offer`] a card represents in [`LevelUpOffers`].
+#[derive(Component)]
+struct OfferIndex(usize);
+
+/// While active, clicking an offer banishes it instead of picking it.
+#[derive(Resource, Default)]
+struct BanishMode(bool);
+
+fn reset_banish_mode(mut banish_mode: ResMut<BanishMode>) {
+    banish_mode.0 = false;
 }
 
 fn spawn_level_up_menu(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     weapons: Res<WeaponMap>,
-    mut rng: Single<&mut WyRand, With<GlobalRng>>,
+    offers: Res<LevelUpOffers>,
+    charges: Res<LevelUpCharges>,
+    banish_mode: Res<BanishMode>,
 ) {
     let border_image = asset_server.load("kenny/panel-border-011.png");
     let font: Handle<Font> = asset_server.load("ui/compass.ttf");
@@ -29,6 +56,7 @@ fn spawn_level_up_menu(
     commands
         .spawn((
             widget::ui_root("LevelUpRoot"),
+            LevelUpMenu,
             DespawnOnExit(Overlay::LevelUp),
         ))
         .with_children(|parent| {
@@ -38,28 +66,49 @@ fn spawn_level_up_menu(
                     Node {
                         position_type: PositionType::Relative,
                         width: Val::Percent(100.0),
-                        height: Val::Percent(100.0),
+                        flex_grow: 1.0,
                         column_gap: Val::Percent(10.0),
                         padding: UiRect::all(Val::Px(50.0)),
                         ..default()
                     },
                 ))
                 .with_children(|parent| {
-                    for _ in 0..NUMBER_OF_ITEM_CHOICES {
-                        let weapon_index = rng.random_range(0..WeaponKind::ALL.len());
-                        let kind = WeaponKind::ALL[weapon_index];
-                        let spec = weapons.get(&kind).expect("expect spec for kind");
+                    if offers.0.is_empty() {
+                        parent.spawn(widget::header("Nothing left to learn"));
+                    }
 
+                    for (index, offer) in offers.0.iter().enumerate() {
+                        let spec = weapons.get(&offer.kind).expect("expect spec for kind");
                         let icon = spec.icon.clone();
 
                         parent
                             .spawn((
-                                item_choice_widget(border_image.clone(), icon, &font),
-                                WeaponKind::ALL[weapon_index],
+                                item_choice_widget(border_image.clone(), icon, &font, offer),
+                                OfferIndex(index),
                             ))
-                            .observe(upgrade);
+                            .observe(choose_offer);
                     }
                 });
+
+            let banish_label = if banish_mode.0 {
+                "Cancel".to_string()
+            } else {
+                format!("Banish ({})", charges.banishes)
+            };
+
+            parent.spawn((
+                Name::new("LevelUpActions"),
+                Node {
+                    column_gap: Val::Px(20.0),
+                    padding: UiRect::bottom(Val::Px(40.0)),
+                    ..default()
+                },
+                children![
+                    widget::button(format!("Reroll ({})", charges.rerolls), reroll),
+                    widget::button(banish_label, toggle_banish_mode),
+                    widget::button(format!("Skip ({})", charges.skips), skip),
+                ],
+            ));
         });
 }
 
@@ -67,6 +116,7 @@ fn item_choice_widget(
     border_image: Handle<Image>,
     weapon_image: Handle<Image>,
     font: &Handle<Font>,
+    offer: &Offer,
 ) -> impl Bundle {
     (
         Node {
@@ -90,9 +140,14 @@ fn item_choice_widget(
             }),
             ..default()
         },
-        //Dark semi-transparent
-        BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.6)),
+        BackgroundColor(CARD_BACKGROUND.into()),
+        InteractionPalette {
+            none: CARD_BACKGROUND.into(),
+            hovered: CARD_HOVERED_BACKGROUND.into(),
+            pressed: CARD_PRESSED_BACKGROUND.into(),
+        },
         Button,
+        AutoDirectionalNavigation::default(),
         Children::spawn((
             Spawn((
                 Node {
@@ -102,13 +157,13 @@ fn item_choice_widget(
                 },
                 ImageNode::new(weapon_image),
             )),
-            Spawn(item_desc(font)),
-            Spawn(item_txt(font)),
+            Spawn(item_desc(font, offer.effect.description(offer.kind))),
+            Spawn(item_txt(font, &offer.effect, offer.rarity.color())),
         )),
     )
 }
 
-fn item_desc(font: &Handle<Font>) -> impl Bundle {
+fn item_desc(font: &Handle<Font>, description: String) -> impl Bundle {
     (
         Node {
             width: Val::Px(100.),
@@ -117,7 +172,7 @@ fn item_desc(font: &Handle<Font>) -> impl Bundle {
             ..Default::default()
         },
         Children::spawn((Spawn((
-            Text::new("Damage Increase"),
+            Text::new(description),
             TextFont {
                 font: font.clone(),
                 font_size: 24.0,
@@ -130,11 +185,7 @@ fn item_desc(font: &Handle<Font>) -> impl Bundle {
     )
 }
 
-fn item_txt(font: &Handle<Font>) -> impl Bundle {
-    //TODO: Get this from weapon rarity proc
-    let colors = [basic::WHITE, basic::GREEN, basic::BLUE, basic::RED];
-    let rng = &mut rand::rng();
-    let color = colors[rng.random_range(0..colors.len())];
+fn item_txt(font: &Handle<Font>, effect: &OfferEffect, color: Color) -> impl Bundle {
     (
         Node {
             width: Val::Px(100.),
@@ -142,7 +193,7 @@ fn item_txt(font: &Handle<Font>) -> impl Bundle {
             ..Default::default()
         },
         Children::spawn((Spawn((
-            Text::new("Upgrade"),
+            Text::new(effect.title()),
             TextFont {
                 font: font.clone(),
                 font_size: 32.0,
@@ -150,28 +201,138 @@ fn item_txt(font: &Handle<Font>) -> impl Bundle {
                 ..default()
             },
             TextLayout::new_with_justify(Justify::Center),
-            TextColor(color.into()),
+            TextColor(color),
         )),)),
     )
 }
 
-fn upgrade(
+fn choose_offer(
     trigger: On<Pointer<Click>>,
+    offer_index: Query<&OfferIndex>,
+    banish_mode: Res<BanishMode>,
     mut commands: Commands,
+) {
+    let Ok(OfferIndex(index)) = offer_index.get(trigger.entity) else {
+        return;
+    };
+
+    commands.trigger(if banish_mode.0 {
+        OverlayChoice::BanishOffer(*index)
+    } else {
+        OverlayChoice::PickOffer(*index)
+    });
+}
+
+fn reroll(_: On<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(OverlayChoice::RerollOffers);
+}
+
+fn toggle_banish_mode(
+    _: On<Pointer<Click>>,
+    charges: Res<LevelUpCharges>,
+    mut banish_mode: ResMut<BanishMode>,
+    mut commands: Commands,
+) {
+    if charges.banishes == 0 && !banish_mode.0 {
+        return;
+    }
+
+    banish_mode.0 = !banish_mode.0;
+    commands.run_system_cached(respawn_level_up_menu);
+}
+
+fn skip(_: On<Pointer<Click>>, mut commands: Commands) {
+    commands.trigger(OverlayChoice::SkipLevelUp);
+}
+
+fn apply_level_up_choice(
+    choice: On<OverlayChoice>,
+    overlay: Res<State<Overlay>>,
+    offers: Res<LevelUpOffers>,
+    mut banish_mode: ResMut<BanishMode>,
+    mut banished: ResMut<BanishedWeapons>,
+    mut charges: ResMut<LevelUpCharges>,
+    mut pending: ResMut<PendingLevelUps>,
     mut next_menu: ResMut<NextState<Overlay>>,
-    weapon_types: Query<&WeaponKind>,
+    mut commands: Commands,
 ) {
-    let selected_weapon = trigger.entity;
+    if *overlay.get() != Overlay::LevelUp {
+        return;
+    }
 
-    let pickup_event = PickUpWeaponEvent {
-        kind: *weapon_types
-            .get(selected_weapon)
-            .expect("We should always find the WeaponType the player chose"),
-    };
+    match *choice.event() {
+        OverlayChoice::PickOffer(index) => {
+            let Some(offer) = offers.0.get(index) else {
+                return;
+            };
+            offer.apply(&mut commands);
+            finish_level_up(&mut commands, &mut pending, &mut next_menu);
+        }
+        OverlayChoice::BanishOffer(index) => {
+            let Some(offer) = offers.0.get(index) else {
+                return;
+            };
+            if charges.banishes == 0 {
+                return;
+            }
+            banish_mode.0 = false;
+            charges.banishes -= 1;
+            banished.0.insert(offer.kind);
+            commands.run_system_cached(roll_offers);
+            commands.run_system_cached(respawn_level_up_menu);
+        }
+        OverlayChoice::RerollOffers => {
+            if charges.rerolls == 0 {
+                return;
+            }
+            charges.rerolls -= 1;
+            banish_mode.0 = false;
+            commands.run_system_cached(roll_offers);
+            commands.run_system_cached(respawn_level_up_menu);
+        }
+        OverlayChoice::SkipLevelUp => {
+            // Skipping is free if there is nothing to choose from.
+            if !offers.0.is_empty() {
+                if charges.skips == 0 {
+                    return;
+                }
+                charges.skips -= 1;
+            }
+            finish_level_up(&mut commands, &mut pending, &mut next_menu);
+        }
+        _ => {}
+    }
+}
 
-    // Pickup weapon
-    commands.trigger(pickup_event);
+/// Show the next queued level up, or transition back to the gameplay.
+fn finish_level_up(
+    commands: &mut Commands,
+    pending: &mut PendingLevelUps,
+    next_menu: &mut NextState<Overlay>,
+) {
+    pending.0 = pending.0.saturating_sub(1);
+    if pending.0 == 0 {
+        next_menu.set(Overlay::None);
+        return;
+    }
+
+    commands.run_system_cached(reset_banish_mode);
+    commands.run_system_cached(roll_offers);
+    commands.run_system_cached(respawn_level_up_menu);
+}
+
+/// Take the first offer of every level up, for runs without anyone at the menu.
+pub(crate) fn pick_first_offer(offers: Res<LevelUpOffers>, mut commands: Commands) {
+    commands.trigger(if offers.0.is_empty() {
+        OverlayChoice::SkipLevelUp
+    } else {
+        OverlayChoice::PickOffer(0)
+    });
+}
 
-    // Transition back to the gameplay
-    next_menu.set(Overlay::None);
+fn respawn_level_up_menu(menu: Query<Entity, With<LevelUpMenu>>, mut commands: Commands) {
+    for entity in &menu {
+        commands.entity(entity).despawn();
+    }
+    commands.run_system_cached(spawn_level_up_menu);
 }
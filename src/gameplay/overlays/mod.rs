@@ -1,14 +1,21 @@
+pub(crate) mod chest;
+mod crafting;
 pub(crate) mod experience;
 mod hud;
-mod level_up;
+pub(crate) mod level_up;
+pub(crate) mod offers;
 
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Overlay>();
 
+    app.add_plugins(chest::plugin);
+    app.add_plugins(crafting::plugin);
     app.add_plugins(experience::plugin);
     app.add_plugins(level_up::plugin);
+    app.add_plugins(offers::plugin);
     app.add_plugins(hud::plugin);
 }
 
@@ -17,4 +24,36 @@ pub enum Overlay {
     #[default]
     None,
     LevelUp,
+    Crafting,
+    Chest,
+}
+
+/// A choice made on an overlay. Triggered by the overlay's buttons and recorded for replays.
+#[derive(Event, Copy, Clone, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub(crate) enum OverlayChoice {
+    /// Take the level up offer at this index.
+    PickOffer(usize),
+    /// Never offer the weapon of the level up offer at this index again.
+    BanishOffer(usize),
+    RerollOffers,
+    SkipLevelUp,
+    /// Grant every reward of the open chest.
+    ClaimChest,
+    /// Craft the recipe at this index of the recipe book.
+    Craft(usize),
+    LeaveCrafting,
+}
+
+impl OverlayChoice {
+    /// The overlay the choice is made on.
+    pub fn overlay(self) -> Overlay {
+        match self {
+            OverlayChoice::PickOffer(_)
+            | OverlayChoice::BanishOffer(_)
+            | OverlayChoice::RerollOffers
+            | OverlayChoice::SkipLevelUp => Overlay::LevelUp,
+            OverlayChoice::ClaimChest => Overlay::Chest,
+            OverlayChoice::Craft(_) | OverlayChoice::LeaveCrafting => Overlay::Crafting,
+        }
+    }
 }
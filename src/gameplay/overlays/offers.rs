@@ -0,0 +1,284 @@
+//! Rolling the choices presented on level up.
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use bevy_rand::prelude::WyRand;
+use rand::Rng;
+
+use crate::{
+    gameplay::{
+        player::{InInventoryOf, Player},
+        rng::OfferRng,
+        weapons::{
+            components::{BaseDamage, MAX_WEAPON_LEVEL, Weapon, WeaponLevel},
+            kind::WeaponKind,
+            rarity::Rarity,
+            spec::WeaponMap,
+            systems::pickup::{PickUpWeaponEvent, UpgradeWeaponEvent},
+        },
+    },
+    save::SaveData,
+    screens::Screen,
+};
+
+pub(crate) const NUMBER_OF_ITEM_CHOICES: usize = 3;
+
+/// Relative chance for a weapon the player does not own yet.
+const NEW_WEAPON_WEIGHT: f32 = 1.0;
+/// Relative chance for an upgrade of an owned weapon.
+const UPGRADE_WEIGHT: f32 = 1.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<LevelUpOffers>();
+    app.add_systems(OnEnter(Screen::Gameplay), reset_level_up_charges);
+}
+
+/// Per run limited actions on the level up screen.
+#[derive(Resource, Debug)]
+pub(crate) struct LevelUpCharges {
+    pub rerolls: u32,
+    pub banishes: u32,
+    pub skips: u32,
+}
+
+impl Default for LevelUpCharges {
+    fn default() -> Self {
+        Self {
+            rerolls: 3,
+            banishes: 2,
+            skips: 2,
+        }
+    }
+}
+
+/// Weapons that will never be offered again during this run.
+#[derive(Resource, Default, Debug)]
+pub(crate) struct BanishedWeapons(pub HashSet<WeaponKind>);
+
+/// The offers currently shown on the level up screen.
+#[derive(Resource, Default, Debug)]
+pub(crate) struct LevelUpOffers(pub Vec<Offer>);
+
+#[derive(Debug, Clone)]
+pub(crate) struct Offer {
+    pub kind: WeaponKind,
+    pub rarity: Rarity,
+    pub effect: OfferEffect,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) enum OfferEffect {
+    NewWeapon {
+        damage: f32,
+    },
+    Upgrade {
+        level: u32,
+        damage: f32,
+        next_damage: f32,
+    },
+}
+
+impl Offer {
+    /// Give the player the weapon or upgrade this offer stands for.
+    pub fn apply(&self, commands: &mut Commands) {
+        match self.effect {
+            OfferEffect::NewWeapon { .. } => {
+                commands.trigger(PickUpWeaponEvent { kind: self.kind })
+            }
+            OfferEffect::Upgrade { .. } => commands.trigger(UpgradeWeaponEvent {
+                kind: self.kind,
+                amount: 1,
+                rarity: self.rarity,
+            }),
+        }
+    }
+}
+
+impl OfferEffect {
+    pub fn title(&self) -> &'static str {
+        match self {
+            OfferEffect::NewWeapon { .. } => "New",
+            OfferEffect::Upgrade { .. } => "Upgrade",
+        }
+    }
+
+    pub fn description(&self, kind: WeaponKind) -> String {
+        match self {
+            OfferEffect::NewWeapon { damage } => format!("{kind:?}\nDamage {damage:.0}"),
+            OfferEffect::Upgrade {
+                level,
+                damage,
+                next_damage,
+            } => format!(
+                "{kind:?} Lv {level} > {}\nDamage {damage:.0} > {next_damage:.0}",
+                level + 1
+            ),
+        }
+    }
+}
+
+fn reset_level_up_charges(mut commands: Commands) {
+    commands.insert_resource(LevelUpCharges::default());
+    commands.insert_resource(BanishedWeapons::default());
+}
+
+/// Roll a fresh set of [`LevelUpOffers`].
+pub(crate) fn roll_offers(
+    weapons: Res<WeaponMap>,
+    banished: Res<BanishedWeapons>,
+    player: Single<Entity, With<Player>>,
+    owned_q: Query<(&WeaponKind, &InInventoryOf, &WeaponLevel, &BaseDamage), With<Weapon>>,
+    mut offers: ResMut<LevelUpOffers>,
+    mut rng: Single<&mut WyRand, With<OfferRng>>,
+    save: Res<SaveData>,
+) {
+    let owned = owned_weapons(*player, &owned_q);
+    offers.0 = roll_weapon_offers(
+        NUMBER_OF_ITEM_CHOICES,
+        &weapons,
+        &banished,
+        &owned,
+        &save,
+        &mut **rng,
+    );
+}
+
+/// Level and base damage of each weapon `player` owns.
+pub(crate) fn owned_weapons(
+    player: Entity,
+    owned_q: &Query<(&WeaponKind, &InInventoryOf, &WeaponLevel, &BaseDamage), With<Weapon>>,
+) -> HashMap<WeaponKind, (u32, f32)> {
+    owned_q
+        .iter()
+        .filter(|(_, owner, ..)| owner.0 == player)
+        .map(|(kind, _, level, damage)| (*kind, (level.0, damage.0)))
+        .collect()
+}
+
+/// Roll up to `count` offers for distinct weapons, either new ones or upgrades of `owned` ones.
+pub(crate) fn roll_weapon_offers(
+    count: usize,
+    weapons: &WeaponMap,
+    banished: &BanishedWeapons,
+    owned: &HashMap<WeaponKind, (u32, f32)>,
+    save: &SaveData,
+    rng: &mut WyRand,
+) -> Vec<Offer> {
+    let candidates: Vec<(WeaponKind, f32)> = WeaponKind::ALL
+        .iter()
+        .copied()
+        .filter(|kind| {
+            weapons.contains_key(kind)
+                && !save.locked_weapons.contains(kind)
+                && !banished.0.contains(kind)
+        })
+        .filter_map(|kind| match owned.get(&kind) {
+            Some((level, _)) if *level >= MAX_WEAPON_LEVEL => None,
+            Some(_) => Some((kind, UPGRADE_WEIGHT)),
+            None => Some((kind, NEW_WEAPON_WEIGHT)),
+        })
+        .collect();
+
+    pick_weighted(candidates, count, rng)
+        .into_iter()
+        .map(|kind| match owned.get(&kind) {
+            Some((level, damage)) => {
+                let rarity = Rarity::roll(rng);
+                Offer {
+                    kind,
+                    rarity,
+                    effect: OfferEffect::Upgrade {
+                        level: *level,
+                        damage: *damage,
+                        next_damage: damage * rarity.damage_multiplier(),
+                    },
+                }
+            }
+            None => Offer {
+                kind,
+                rarity: Rarity::Common,
+                effect: OfferEffect::NewWeapon {
+                    damage: weapons[&kind].base_damage,
+                },
+            },
+        })
+        .collect()
+}
+
+/// Pick up to `count` distinct candidates, each with a chance relative to its weight.
+pub(crate) fn pick_weighted<T>(
+    mut candidates: Vec<(T, f32)>,
+    count: usize,
+    rng: &mut impl Rng,
+) -> Vec<T> {
+    let mut picked = Vec::with_capacity(count);
+
+    while picked.len() < count && !candidates.is_empty() {
+        let total: f32 = candidates.iter().map(|(_, weight)| weight).sum();
+        let mut roll = rng.random_range(0.0..total.max(f32::EPSILON));
+
+        let index = candidates
+            .iter()
+            .position(|(_, weight)| {
+                if roll < *weight {
+                    return true;
+                }
+                roll -= weight;
+                false
+            })
+            .unwrap_or(candidates.len() - 1);
+
+        picked.push(candidates.swap_remove(index).0);
+    }
+
+    picked
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn candidates_are_picked_as_often_as_their_weight() {
+        const ROLLS: usize = 100_000;
+        let mut rng = WyRand::seed_from_u64(7);
+
+        let mut picked = [0usize; 3];
+        for _ in 0..ROLLS {
+            let candidates = vec![(0, 1.0), (1, 3.0), (2, 6.0)];
+            picked[pick_weighted(candidates, 1, &mut rng)[0]] += 1;
+        }
+
+        for (index, expected) in [0.1, 0.3, 0.6].into_iter().enumerate() {
+            let share = picked[index] as f32 / ROLLS as f32;
+            assert!(
+                (share - expected).abs() < 0.01,
+                "candidate {index} picked {share}, expected {expected}"
+            );
+        }
+    }
+
+    #[test]
+    fn candidates_are_picked_once() {
+        let mut rng = WyRand::seed_from_u64(7);
+        let mut picked = pick_weighted(vec![("a", 1.0), ("b", 1.0), ("c", 1.0)], 5, &mut rng);
+        picked.sort();
+
+        assert_eq!(picked, ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn zero_weights_are_picked_last() {
+        let mut rng = WyRand::seed_from_u64(7);
+
+        assert!(pick_weighted(Vec::<((), f32)>::new(), 3, &mut rng).is_empty());
+        for _ in 0..100 {
+            let picked = pick_weighted(vec![("never", 0.0), ("always", 1.0)], 1, &mut rng);
+            assert_eq!(picked, ["always"]);
+        }
+        assert_eq!(pick_weighted(vec![("only", 0.0)], 1, &mut rng), ["only"]);
+    }
+}
@@ -8,39 +8,52 @@ use bevy_enhanced_input::prelude::*;
 use bevy_enhanced_input::{action::Action, actions};
 use bevy_seedling::sample::AudioSample;
 
+use crate::controls::{Control, Controls};
 use crate::gameplay::abilities;
 use crate::gameplay::character_controller::CharacterController;
+use crate::gameplay::crafting::materials::MaterialPouch;
 use crate::gameplay::player::characters::Characters;
 use crate::gameplay::{
     Health,
+    enemy::EnemyType,
     healthbar::HealthBarMaterial,
     player::{
-        hit::player_hit,
-        movement::{AccumulatedInput, Move},
+        hit::{apply_player_hit, player_hit},
+        movement::{AccumulatedInput, Aim, Move},
     },
 };
+use crate::save::{SaveData, Upgrade};
 use crate::{AssetStates, GameLayer};
 
 pub(crate) mod animation;
 pub(crate) mod characters;
 pub(crate) mod hit;
 pub(crate) mod movement;
+pub(crate) mod xp_curve;
 
 use animation::PlayerAnimation;
 
+/// Health the player starts a run with and can be healed up to.
+pub(crate) const PLAYER_MAX_HEALTH: f32 = 100.0;
+
 pub(super) fn plugin(app: &mut App) {
     app.add_input_context::<Player>();
     app.configure_loading_state(
         LoadingStateConfig::new(AssetStates::AssetLoading).load_collection::<PlayerAssets>(),
     );
 
-    app.add_plugins((animation::plugin, movement::plugin));
+    app.add_plugins((animation::plugin, movement::plugin, xp_curve::plugin));
 
     app.register_type::<XP>().register_type::<Level>();
     app.register_type::<Player>();
 
     app.add_systems(FixedUpdate, player_hit);
+    app.add_systems(
+        Update,
+        rebind_player_actions.run_if(resource_changed::<Controls>),
+    );
 
+    app.add_observer(apply_player_hit);
     app.add_observer(setup_player);
     app.add_observer(patch_player_spawn_pos);
 }
@@ -51,6 +64,8 @@ pub struct PlayerSetupComplete;
 #[derive(Event, Reflect)]
 pub(crate) struct PlayerHitEvent {
     pub dmg: f32,
+    /// The kind of enemy that dealt the damage.
+    pub source: EnemyType,
 }
 
 #[derive(Component, Reflect, Default)]
@@ -69,6 +84,16 @@ impl PlayerFacing {
     }
 }
 
+/// The normalized direction the player last aimed in with the mouse or the right stick.
+#[derive(Component, Copy, Clone, Debug, PartialEq, Reflect)]
+pub(crate) struct AimDirection(pub Vec2);
+
+impl Default for AimDirection {
+    fn default() -> Self {
+        Self(Vec2::X)
+    }
+}
+
 #[derive(Component, Reflect)]
 pub(crate) struct XpCollectionRange(pub f32);
 
@@ -115,13 +140,15 @@ pub(crate) struct PlayerSpawnPoint;
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Default, Reflect)]
 #[require(
-    Health(100.),
+    Health(PLAYER_MAX_HEALTH),
     XpCollectionRange(150.0),
     XP(0.),
     Level(1.),
     CharacterController{speed: 100., ..default()},
     AccumulatedInput,
     PlayerFacing,
+    AimDirection,
+    MaterialPouch,
     DespawnOnExit::<Screen>(Screen::Gameplay),
 )]
 pub(crate) struct Player;
@@ -135,7 +162,14 @@ fn setup_player(
     player_assets: If<Res<PlayerAssets>>,
     mut mesh: ResMut<Assets<Mesh>>,
     mut texture_atlas_layout: ResMut<Assets<TextureAtlasLayout>>,
+    mut controller_q: Query<&mut CharacterController>,
+    controls: Res<Controls>,
+    save: Res<SaveData>,
 ) {
+    if let Ok(mut controller) = controller_q.get_mut(player_add.entity) {
+        controller.speed *= save.upgrade_multiplier(Upgrade::MoveSpeed);
+    }
+
     let layout = TextureAtlasLayout::from_grid(UVec2 { x: 64, y: 64 }, 11, 1, None, None);
     let texture_atlas_layout = texture_atlas_layout.add(layout);
 
@@ -150,7 +184,7 @@ fn setup_player(
 
     commands.entity(player_add.entity).insert((
         Name::new("Player"),
-        player_input_actions(),
+        player_input_actions(&controls),
         PlayerAnimation::new(),
         LockedAxes::ROTATION_LOCKED,
         Collider::circle(16.),
@@ -223,26 +257,59 @@ fn patch_player_spawn_pos(
     }
 }
 
-fn player_input_actions() -> impl Bundle {
+fn player_input_actions(controls: &Controls) -> impl Bundle {
     actions!(Player[
         (
             Action::<Move>::new(),
             Bindings::spawn((
-                Cardinal::wasd_keys(),
+                Cardinal {
+                    north: controls.key_binding(Control::MoveUp),
+                    east: controls.key_binding(Control::MoveRight),
+                    south: controls.key_binding(Control::MoveDown),
+                    west: controls.key_binding(Control::MoveLeft),
+                },
+                Cardinal {
+                    north: controls.button_binding(Control::MoveUp),
+                    east: controls.button_binding(Control::MoveRight),
+                    south: controls.button_binding(Control::MoveDown),
+                    west: controls.button_binding(Control::MoveLeft),
+                },
                 Axial::left_stick()
             )),
         ),
+        (Action::<Aim>::new(), Bindings::spawn(Axial::right_stick())),
         (
             Action::<abilities::UseQAbility>::new(),
-            bindings![KeyCode::KeyQ]
+            bindings![
+                controls.key_binding(Control::QAbility),
+                controls.button_binding(Control::QAbility)
+            ]
         ),
         (
             Action::<abilities::UseEAbility>::new(),
-            bindings![KeyCode::KeyE]
+            bindings![
+                controls.key_binding(Control::EAbility),
+                controls.button_binding(Control::EAbility)
+            ]
         ),
         (
             Action::<abilities::UseRAbility>::new(),
-            bindings![KeyCode::KeyR]
+            bindings![
+                controls.key_binding(Control::RAbility),
+                controls.button_binding(Control::RAbility)
+            ]
         )
     ])
 }
+
+/// Rebuild the player's actions after the controls were rebound in the settings.
+fn rebind_player_actions(
+    mut commands: Commands,
+    player: Single<Entity, With<Player>>,
+    controls: Res<Controls>,
+) {
+    commands
+        .entity(*player)
+        .despawn_related::<Actions<Player>>()
+        .insert(player_input_actions(&controls));
+}
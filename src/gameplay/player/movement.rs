@@ -1,17 +1,29 @@
 use avian2d::prelude::LinearVelocity;
 use bevy::{prelude::*, window::PrimaryWindow};
-use bevy_ecs_tiled::prelude::TiledMapAsset;
+use bevy_ecs_tiled::prelude::{TiledMap, TiledMapAsset};
 use bevy_enhanced_input::{EnhancedInputSystems, action::Action, prelude::InputAction};
+use serde::{Deserialize, Serialize};
 
 use crate::{
-    CAMERA_DECAY_RATE, GameplaySystems, PausableSystems, PostPhysicsAppSystems,
+    CAMERA_DECAY_RATE, GameplaySystems, PausableSystems, PostPhysicsAppSystems, SPAWN_MARGIN,
     fixed_update_inspection::did_fixed_update_happen,
     gameplay::{
         character_controller::CharacterController,
-        player::{Player, PlayerFacing},
+        player::{AimDirection, Player, PlayerFacing, PlayerHitEvent},
     },
+    screens::Screen,
+    settings::{AimMode, Settings},
 };
 
+/// Largest camera offset in world units at full trauma and full shake strength.
+const MAX_SHAKE_OFFSET: f32 = 6.0;
+/// Trauma lost per second.
+const SHAKE_DECAY: f32 = 1.5;
+/// Trauma added whenever the player takes damage.
+pub(crate) const HIT_TRAUMA: f32 = 0.4;
+/// Right stick deflection below which the stick doesn't aim.
+const AIM_DEADZONE: f32 = 0.3;
+
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         FixedUpdate,
@@ -19,32 +31,60 @@ pub(super) fn plugin(app: &mut App) {
             .in_set(PausableSystems)
             .in_set(GameplaySystems::Movement),
     );
+    app.add_systems(
+        FixedUpdate,
+        apply_aim
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems)
+            .in_set(GameplaySystems::MovementModify),
+    );
 
     app.add_systems(
         PreUpdate,
-        (record_player_directional_input).after(EnhancedInputSystems::Apply),
+        (record_player_directional_input, record_player_aim_input)
+            .after(EnhancedInputSystems::Apply),
     );
 
     app.add_systems(Update, clear_input.run_if(did_fixed_update_happen));
 
+    app.init_resource::<ScreenShake>();
+    app.init_resource::<CameraBounds>();
     app.add_systems(
         Update,
         translate_camera.in_set(PostPhysicsAppSystems::Update),
     );
+    app.add_observer(shake_on_player_hit);
 }
 
 #[derive(InputAction)]
 #[action_output(Vec2)]
 pub(crate) struct Move;
 
+#[derive(InputAction)]
+#[action_output(Vec2)]
+pub(crate) struct Aim;
+
 /// A vector representing the player's input, accumulated over all frames that ran
 /// since the last time the physics simulation was advanced.
-#[derive(Debug, Component, Clone, Copy, PartialEq, Default, Deref, DerefMut)]
+///
+/// This is everything a replay needs to reproduce the player's actions.
+#[derive(
+    Debug, Component, Clone, Copy, PartialEq, Default, Deref, DerefMut, Serialize, Deserialize,
+)]
 pub struct AccumulatedInput {
     // The player's movement input (WASD).
+    #[deref]
     pub last_move: Vec2,
-    // Other input that could make sense would be e.g.
-    // boost: bool
+    /// Ability keys pressed since the last tick, consumed by the ability systems.
+    #[serde(default)]
+    pub q_ability: bool,
+    #[serde(default)]
+    pub e_ability: bool,
+    #[serde(default)]
+    pub r_ability: bool,
+    /// The direction aimed in with the mouse or the right stick, `None` if the player didn't aim.
+    #[serde(default)]
+    pub aim: Option<Vec2>,
 }
 
 // Clear the input after it was processed in the fixed timestep.
@@ -55,12 +95,14 @@ fn clear_input(mut input: Single<&mut AccumulatedInput>) {
 fn record_player_directional_input(
     move_action: Single<&Action<Move>>,
     player_q: Single<(&mut AccumulatedInput, &mut super::PlayerFacing)>,
+    settings: Res<Settings>,
 ) {
     let (mut input, mut facing) = player_q.into_inner();
     let v = move_action.normalize_or_zero();
     input.last_move = v;
 
-    if v.x.abs() > 0.1 {
+    // With manual aim the player faces where they aim, see `apply_aim`.
+    if settings.gameplay.aim == AimMode::Auto && v.x.abs() > 0.1 {
         *facing = if v.x >= 0.0 {
             PlayerFacing::Right
         } else {
@@ -69,24 +111,127 @@ fn record_player_directional_input(
     }
 }
 
+/// Aim with the right stick while it is deflected, otherwise with the mouse once it moved.
+fn record_player_aim_input(
+    aim_action: Single<&Action<Aim>>,
+    player_q: Single<(&Transform, &mut AccumulatedInput), With<Player>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<Camera2d>>,
+    mut last_cursor: Local<Option<Vec2>>,
+    mut mouse_aim: Local<bool>,
+) {
+    let (player_tf, mut input) = player_q.into_inner();
+
+    let stick = ***aim_action;
+    if stick.length() > AIM_DEADZONE {
+        *mouse_aim = false;
+        input.aim = Some(stick.normalize());
+        return;
+    }
+
+    let cursor = window_q.single().ok().and_then(Window::cursor_position);
+    if cursor.is_some() && cursor != *last_cursor {
+        *mouse_aim = true;
+    }
+    *last_cursor = cursor;
+
+    if !*mouse_aim {
+        return;
+    }
+    let Some(cursor) = cursor else {
+        return;
+    };
+    let Ok((camera, camera_tf)) = camera_q.single() else {
+        return;
+    };
+    if let Ok(world) = camera.viewport_to_world_2d(camera_tf, cursor) {
+        input.aim = (world - player_tf.translation.truncate()).try_normalize();
+    }
+}
+
+fn apply_aim(
+    player_q: Single<(&AccumulatedInput, &mut AimDirection, &mut PlayerFacing), With<Player>>,
+    settings: Res<Settings>,
+) {
+    let (input, mut aim_direction, mut facing) = player_q.into_inner();
+    let Some(aim) = input.aim else {
+        return;
+    };
+    aim_direction.0 = aim;
+
+    if settings.gameplay.aim == AimMode::Manual && aim.x.abs() > 0.1 {
+        *facing = if aim.x >= 0.0 {
+            PlayerFacing::Right
+        } else {
+            PlayerFacing::Left
+        };
+    }
+}
+
+/// Camera shake, scaled by the screen shake accessibility setting.
+#[derive(Resource, Default, Debug)]
+pub(crate) struct ScreenShake {
+    /// Between `0.0` and `1.0`, decays over time.
+    trauma: f32,
+    /// Offset applied to the camera last frame.
+    offset: Vec2,
+}
+
+impl ScreenShake {
+    pub fn add_trauma(&mut self, trauma: f32) {
+        self.trauma = (self.trauma + trauma).min(1.0);
+    }
+}
+
+fn shake_on_player_hit(_: On<PlayerHitEvent>, mut shake: ResMut<ScreenShake>) {
+    shake.add_trauma(HIT_TRAUMA);
+}
+
+/// What the camera shows and where it can go, in world space.
+/// Updated by [`translate_camera`].
+#[derive(Resource, Default, Debug)]
+pub(crate) struct CameraBounds {
+    /// The area covered by the orthographic projection.
+    pub viewport: Rect,
+    /// The bounds of the current Tiled map, if one is loaded.
+    pub map: Option<Rect>,
+}
+
+impl CameraBounds {
+    /// The viewport grown by [`SPAWN_MARGIN`], enemies spawn on its edge.
+    pub fn spawn_area(&self) -> Rect {
+        self.viewport.inflate(SPAWN_MARGIN)
+    }
+}
+
 /// Sync the camera's position with the player's interpolated position
 fn translate_camera(
     time: Res<Time>,
-    mut camera_transform: Single<&mut Transform, (With<Camera>, Without<Player>)>,
+    camera_q: Single<(&mut Transform, &Projection), (With<Camera>, Without<Player>)>,
     tiled_map_assets: Res<Assets<TiledMapAsset>>,
+    map_q: Query<&TiledMap>,
     player: Single<&Transform, (With<Player>, Without<Camera>)>,
     window: Single<&Window, With<PrimaryWindow>>,
+    mut shake: ResMut<ScreenShake>,
+    mut camera_bounds: ResMut<CameraBounds>,
+    settings: Res<Settings>,
 ) {
+    let (mut camera_transform, projection) = camera_q.into_inner();
+
+    // Remove last frame's shake so it does not accumulate into the smoothing.
+    camera_transform.translation -= shake.offset.extend(0.0);
+
     let Vec3 { x, y, .. } = player.translation;
     let viewport_height = 504.;
 
     let aspect_ratio = window.width() / window.height();
     let viewport_width = viewport_height * aspect_ratio;
 
-    // Get map dimensions from the first loaded Tiled map
-    if let Some((_, tiled_map)) = tiled_map_assets.iter().next() {
+    // Get map dimensions from the spawned Tiled map, the hub or the level
+    if let Some(tiled_map) = map_q.iter().find_map(|map| tiled_map_assets.get(&map.0)) {
         let level_width = tiled_map.map.width as f32 * tiled_map.map.tile_width as f32;
         let level_height = tiled_map.map.height as f32 * tiled_map.map.tile_height as f32;
+        camera_bounds.map = Some(Rect::new(0.0, 0.0, level_width, level_height));
 
         let mut desired_x = x - viewport_width / 2.0;
         let mut desired_y = y - viewport_height / 2.0;
@@ -109,6 +254,23 @@ fn translate_camera(
             CAMERA_DECAY_RATE,
             time.delta_secs(),
         );
+    } else {
+        camera_bounds.map = None;
+    }
+
+    shake.trauma = (shake.trauma - SHAKE_DECAY * time.delta_secs()).max(0.0);
+    let t = time.elapsed_secs();
+    let amplitude = MAX_SHAKE_OFFSET * settings.accessibility.screen_shake * shake.trauma.powi(2);
+    // Two incommensurate frequencies look random enough without an RNG.
+    shake.offset = Vec2::new((t * 47.0).sin(), (t * 61.0).cos()) * amplitude;
+    camera_transform.translation += shake.offset.extend(0.0);
+
+    if let Projection::Orthographic(orthographic) = projection {
+        let offset = camera_transform.translation.truncate();
+        camera_bounds.viewport = Rect::from_corners(
+            orthographic.area.min + offset,
+            orthographic.area.max + offset,
+        );
     }
 }
 
@@ -0,0 +1,158 @@
+//! The experience needed for each level, loaded from `progression/xp.curve.ron`.
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use serde::Deserialize;
+use serde_ron::de::from_bytes;
+use thiserror::Error;
+
+use crate::gameplay::data;
+
+/// The curve of the game, the [`Default`] one is used if the file is missing or invalid.
+const XP_CURVE_FILE: &str = "progression/xp.curve.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<XpCurve>()
+        .register_asset_loader(XpCurveLoader);
+    data::load_data::<XpCurve>(app, XP_CURVE_FILE);
+}
+
+/// Experience needed to advance from each level to the next.
+#[derive(Asset, Resource, TypePath, Deserialize, Debug, Clone)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct XpCurve {
+    /// XP needed per level, starting at level 1.
+    pub levels: Vec<f32>,
+    /// Past the end of `levels`, each level needs this factor more than the previous one.
+    pub growth: f32,
+}
+
+impl XpCurve {
+    pub fn xp_needed(&self, level: f32) -> f32 {
+        let index = (level.max(1.0) as usize) - 1;
+        if let Some(xp) = self.levels.get(index) {
+            return *xp;
+        }
+
+        let last = self.levels.last().copied().unwrap_or(100.0);
+        let extra_levels = (index + 1 - self.levels.len()) as i32;
+        last * self.growth.powi(extra_levels)
+    }
+
+    /// Every level has to need some XP, or gaining XP would level up forever.
+    fn validate(&self) -> Result<(), XpCurveLoaderError> {
+        if let Some((index, &xp)) = self
+            .levels
+            .iter()
+            .enumerate()
+            .find(|(_, xp)| xp.is_nan() || **xp <= 0.0)
+        {
+            return Err(XpCurveLoaderError::FreeLevel {
+                level: index + 1,
+                xp,
+            });
+        }
+        if self.growth.is_nan() || self.growth < 1.0 {
+            return Err(XpCurveLoaderError::ShrinkingGrowth(self.growth));
+        }
+        Ok(())
+    }
+}
+
+impl Default for XpCurve {
+    fn default() -> Self {
+        XpCurve {
+            levels: vec![100.0],
+            growth: 1.5,
+        }
+    }
+}
+
+#[derive(Default, TypePath)]
+struct XpCurveLoader;
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+enum XpCurveLoaderError {
+    #[error("Could not read the file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Could not parse RON: {0}")]
+    RonError(#[from] serde_ron::error::SpannedError),
+
+    #[error("Level {level} needs {xp} XP, every level has to need more than 0")]
+    FreeLevel { level: usize, xp: f32 },
+
+    #[error("The growth is {0}, it has to be at least 1")]
+    ShrinkingGrowth(f32),
+}
+
+impl AssetLoader for XpCurveLoader {
+    type Asset = XpCurve;
+    type Settings = ();
+    type Error = XpCurveLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let curve = from_bytes::<XpCurve>(&bytes)?;
+        curve.validate()?;
+        Ok(curve)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["curve.ron"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xp_needed_grows_past_the_table() {
+        let curve = XpCurve {
+            levels: vec![100.0, 250.0],
+            growth: 2.0,
+        };
+
+        assert_eq!(curve.xp_needed(1.0), 100.0);
+        assert_eq!(curve.xp_needed(2.0), 250.0);
+        assert_eq!(curve.xp_needed(3.0), 500.0);
+        assert_eq!(curve.xp_needed(4.0), 1000.0);
+    }
+
+    #[test]
+    fn levels_have_to_need_xp() {
+        let curve = XpCurve {
+            levels: vec![100.0, 0.0],
+            growth: 1.5,
+        };
+
+        assert!(matches!(
+            curve.validate(),
+            Err(XpCurveLoaderError::FreeLevel { level: 2, .. })
+        ));
+        assert!(XpCurve::default().validate().is_ok());
+    }
+
+    #[test]
+    fn growth_cant_shrink() {
+        let curve = XpCurve {
+            levels: vec![100.0],
+            growth: 0.5,
+        };
+
+        assert!(matches!(
+            curve.validate(),
+            Err(XpCurveLoaderError::ShrinkingGrowth(_))
+        ));
+    }
+}
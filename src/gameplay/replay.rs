@@ -0,0 +1,269 @@
+//! Recording the player's input every fixed tick, and feeding a recording back.
+//!
+//! Together with the [`RunSeed`](crate::gameplay::rng::RunSeed) an [`InputLog`] reproduces a
+//! run, including the choices made on overlays like level up offers. The last live run is
+//! saved when it ends and can be replayed from the main menu. Permanent upgrades bought since
+//! are not undone, so a replay only matches the run until they make a difference.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    GameplaySystems, PausableSystems,
+    gameplay::{
+        hub::{Quest, SelectedQuest},
+        overlays::{Overlay, OverlayChoice},
+        player::{Player, characters::Characters, movement::AccumulatedInput},
+        rng::{RunSeed, seed_run},
+    },
+    persistence,
+    screens::Screen,
+};
+
+/// Where the last live run is written, next to the settings.
+const REPLAY_FILE: &str = "last_run.replay.ron";
+
+pub(crate) fn plugin(app: &mut App) {
+    app.init_resource::<InputSource>();
+    app.init_resource::<InputLog>();
+
+    app.add_systems(OnEnter(Screen::Gameplay), start_recording.after(seed_run));
+    app.add_systems(OnExit(Screen::Gameplay), (save_replay, stop_replay).chain());
+    app.add_systems(
+        FixedUpdate,
+        (feed_replay_input, record_input)
+            .chain()
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems)
+            .in_set(GameplaySystems::Input),
+    );
+    app.add_systems(
+        Update,
+        feed_replay_choices.run_if(in_state(Screen::Gameplay)),
+    );
+
+    app.add_observer(record_choice);
+}
+
+/// The seed, the player's input on every fixed tick and the overlay choices of a run.
+#[derive(Resource, Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+pub(crate) struct InputLog {
+    pub seed: u64,
+    /// `None` for runs started without picking a character, like simulations.
+    #[serde(default)]
+    pub character: Option<Characters>,
+    #[serde(default)]
+    pub quest: Quest,
+    pub ticks: Vec<AccumulatedInput>,
+    #[serde(default)]
+    pub choices: Vec<RecordedChoice>,
+}
+
+/// An overlay choice and the number of ticks recorded before it was made.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub(crate) struct RecordedChoice {
+    pub tick: usize,
+    pub choice: OverlayChoice,
+}
+
+/// Where the player's input comes from.
+#[derive(Resource, Default, Debug)]
+pub(crate) enum InputSource {
+    /// Keyboard, mouse and gamepad.
+    #[default]
+    Live,
+    /// A recorded run, `tick` and `choice` are the next entries of `log` to feed.
+    Replay {
+        log: InputLog,
+        tick: usize,
+        choice: usize,
+    },
+}
+
+impl InputSource {
+    pub fn replay(log: InputLog) -> Self {
+        InputSource::Replay {
+            log,
+            tick: 0,
+            choice: 0,
+        }
+    }
+}
+
+/// Run condition for systems that stand in for the player and have to stay out of replays.
+pub(crate) fn replaying(source: Res<InputSource>) -> bool {
+    matches!(*source, InputSource::Replay { .. })
+}
+
+/// Start a run replaying the last saved live run, with its character and quest.
+pub(crate) fn replay_last_run(
+    mut commands: Commands,
+    mut source: ResMut<InputSource>,
+    mut selected_quest: ResMut<SelectedQuest>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    let Some(path) = persistence::config_path(REPLAY_FILE) else {
+        return;
+    };
+    let log = match persistence::load_ron::<InputLog>(&path) {
+        Ok(Some(log)) => log,
+        Ok(None) => {
+            warn!("There is no replay at {} yet", path.display());
+            return;
+        }
+        Err(err) => {
+            warn!("Failed to load the replay from {}: {err}", path.display());
+            return;
+        }
+    };
+    let Some(character) = log.character else {
+        warn!("The replay at {} has no character", path.display());
+        return;
+    };
+
+    commands.spawn((Player, character));
+    selected_quest.0 = Some(log.quest);
+    *source = InputSource::replay(log);
+    next_screen.set(Screen::Loading);
+}
+
+fn start_recording(
+    seed: Res<RunSeed>,
+    character: Query<&Characters, With<Player>>,
+    selected_quest: Res<SelectedQuest>,
+    mut log: ResMut<InputLog>,
+) {
+    *log = InputLog {
+        seed: seed.current,
+        character: character.single().ok().copied(),
+        quest: selected_quest.quest(),
+        ticks: Vec::new(),
+        choices: Vec::new(),
+    };
+}
+
+/// Overwrite the live input with the recorded one, switching back to live input at the end.
+fn feed_replay_input(mut source: ResMut<InputSource>, mut input: Single<&mut AccumulatedInput>) {
+    let InputSource::Replay { log, tick, .. } = &mut *source else {
+        return;
+    };
+
+    match log.ticks.get(*tick) {
+        Some(recorded) => {
+            **input = *recorded;
+            *tick += 1;
+        }
+        None => {
+            info!("Replay finished after {tick} ticks");
+            **input = AccumulatedInput::default();
+            *source = InputSource::Live;
+        }
+    }
+}
+
+/// Make the next recorded choice once the replay reached its tick and its overlay is open.
+fn feed_replay_choices(
+    mut source: ResMut<InputSource>,
+    overlay: Res<State<Overlay>>,
+    mut commands: Commands,
+) {
+    let InputSource::Replay { log, tick, choice } = &mut *source else {
+        return;
+    };
+    let Some(recorded) = log.choices.get(*choice) else {
+        return;
+    };
+
+    if recorded.tick <= *tick && recorded.choice.overlay() == *overlay.get() {
+        commands.trigger(recorded.choice);
+        *choice += 1;
+    }
+}
+
+pub(crate) fn record_input(input: Single<&AccumulatedInput>, mut log: ResMut<InputLog>) {
+    log.ticks.push(**input);
+}
+
+fn record_choice(choice: On<OverlayChoice>, mut log: ResMut<InputLog>) {
+    let tick = log.ticks.len();
+    log.choices.push(RecordedChoice {
+        tick,
+        choice: *choice.event(),
+    });
+}
+
+fn save_replay(source: Res<InputSource>, log: Res<InputLog>) {
+    if !matches!(*source, InputSource::Live) || log.ticks.is_empty() {
+        return;
+    }
+    let Some(path) = persistence::config_path(REPLAY_FILE) else {
+        return;
+    };
+
+    if let Err(err) = persistence::save_ron(&path, &*log) {
+        warn!("Failed to save the replay to {}: {err}", path.display());
+    }
+}
+
+/// Leaving a replay early goes back to live input for the next run.
+fn stop_replay(mut source: ResMut<InputSource>) {
+    *source = InputSource::Live;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        gameplay::{
+            Health,
+            player::{Level, XP},
+            run_stats::RunStats,
+        },
+        sim::{self, MovementPolicy, SimConfig, WavePlanKind},
+    };
+
+    /// Long enough for a few level ups in a rush.
+    const FRAMES: usize = 90 * 64;
+
+    /// Where the player ended up, their health, level and XP, and the kills.
+    fn outcome(app: &mut App) -> (Vec3, f32, f32, f32, u32) {
+        let kills = app.world().resource::<RunStats>().kills;
+        let (transform, health, level, xp) = app
+            .world_mut()
+            .query_filtered::<(&Transform, &Health, &Level, &XP), With<Player>>()
+            .single(app.world())
+            .unwrap();
+        (transform.translation, health.0, level.0, xp.0, kills)
+    }
+
+    // Needs the assets submodule for the weapon specs, like `tests/simulation.rs`.
+    #[test]
+    fn replay_reproduces_a_simulated_run() {
+        if !sim::assets_checked_out() {
+            return;
+        }
+
+        let config = SimConfig {
+            wave_plan: WavePlanKind::Rush,
+            policy: MovementPolicy::Circle(150.0),
+            seed: 42,
+            ..default()
+        };
+
+        let mut live = sim::headless_app(&config);
+        for _ in 0..FRAMES {
+            live.update();
+        }
+        let log = live.world().resource::<InputLog>().clone();
+        assert_eq!(log.seed, 42);
+        assert!(!log.choices.is_empty(), "the run should level up");
+
+        let mut replay = sim::replay_app(&config, log.clone());
+        for _ in 0..FRAMES {
+            replay.update();
+        }
+
+        assert_eq!(*replay.world().resource::<InputLog>(), log);
+        assert_eq!(outcome(&mut replay), outcome(&mut live));
+    }
+}
@@ -0,0 +1,80 @@
+//! Seeded randomness for a run.
+//!
+//! Every run has a [`RunSeed`]. On entering the gameplay it seeds one RNG per stream of rolls,
+//! forked from the run seed, so systems running in an unspecified order can't change each
+//! other's rolls. The same seed and the same inputs reproduce the same run.
+
+use bevy::prelude::*;
+use bevy_rand::{global::GlobalRng, prelude::WyRand};
+use rand::{Rng, SeedableRng};
+
+use crate::{gameplay::replay::InputSource, screens::Screen};
+
+pub(crate) fn plugin(app: &mut App) {
+    app.init_resource::<RunSeed>();
+    app.add_systems(OnEnter(Screen::Gameplay), seed_run);
+}
+
+/// The seed of the current run.
+#[derive(Resource, Default, Debug)]
+pub(crate) struct RunSeed {
+    /// Set on the title screen, a fresh seed is picked for every run if `None`.
+    pub fixed: Option<u64>,
+    /// The seed the current run was started with.
+    pub current: u64,
+}
+
+/// Enemy spawn positions and elite rolls.
+#[derive(Component, Debug)]
+pub(crate) struct SpawnRng;
+
+/// Enemy behaviour, e.g. where a jumper lands.
+#[derive(Component, Debug)]
+pub(crate) struct EnemyRng;
+
+/// Critical hits.
+#[derive(Component, Debug)]
+pub(crate) struct CritRng;
+
+/// Weapon attacks, e.g. spread angles and initial targets.
+#[derive(Component, Debug)]
+pub(crate) struct WeaponRng;
+
+/// Loot and crafting material drops.
+#[derive(Component, Debug)]
+pub(crate) struct LootRng;
+
+/// Level up offers and chest rewards.
+#[derive(Component, Debug)]
+pub(crate) struct OfferRng;
+
+pub(crate) fn seed_run(
+    mut seed: ResMut<RunSeed>,
+    source: Res<InputSource>,
+    mut global: Single<&mut WyRand, With<GlobalRng>>,
+    mut commands: Commands,
+) {
+    seed.current = match &*source {
+        InputSource::Replay { log, .. } => log.seed,
+        InputSource::Live => seed.fixed.unwrap_or_else(|| global.random()),
+    };
+    info!("Run seed: {}", seed.current);
+
+    let mut run_rng = WyRand::seed_from_u64(seed.current);
+    // The order of the forks is part of the seed, append new streams at the end.
+    fork(&mut commands, &mut run_rng, SpawnRng);
+    fork(&mut commands, &mut run_rng, EnemyRng);
+    fork(&mut commands, &mut run_rng, CritRng);
+    fork(&mut commands, &mut run_rng, WeaponRng);
+    fork(&mut commands, &mut run_rng, LootRng);
+    fork(&mut commands, &mut run_rng, OfferRng);
+}
+
+fn fork(commands: &mut Commands, run_rng: &mut WyRand, stream: impl Component + std::fmt::Debug) {
+    commands.spawn((
+        Name::new(format!("{stream:?}")),
+        WyRand::from_rng(run_rng),
+        stream,
+        DespawnOnExit(Screen::Gameplay),
+    ));
+}
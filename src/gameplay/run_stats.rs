@@ -0,0 +1,275 @@
+//! Statistics of the current run, for seeing which weapons carry it.
+//!
+//! [`RunStats`] is fed by observers on the hit and damage events, shown in a panel toggled with
+//! [`Control::Stats`] and written to the config directory as RON and CSV when the run ends.
+
+use std::fmt::Write;
+
+use bevy::{platform::collections::HashMap, prelude::*};
+use serde::Serialize;
+
+use crate::{
+    PausableSystems,
+    controls::{Control, control_just_pressed},
+    gameplay::{
+        Health,
+        enemy::{EnemyDamageEvent, EnemyDeathEvent, EnemyType},
+        player::{Player, PlayerHitEvent},
+        weapons::kind::WeaponKind,
+    },
+    persistence,
+    screens::Screen,
+};
+
+/// Where the stats of the last run are written, next to the settings.
+const STATS_RON_FILE: &str = "last_run.stats.ron";
+const STATS_CSV_FILE: &str = "last_run.stats.csv";
+
+pub(crate) fn plugin(app: &mut App) {
+    app.init_resource::<RunStats>();
+
+    app.add_systems(
+        OnEnter(Screen::Gameplay),
+        (reset_run_stats, spawn_stats_panel),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), export_run_stats);
+    app.add_systems(
+        Update,
+        (
+            count_time_alive.in_set(PausableSystems),
+            toggle_stats_panel.run_if(control_just_pressed(Control::Stats)),
+            update_stats_panel.run_if(resource_changed::<RunStats>),
+        )
+            .run_if(in_state(Screen::Gameplay)),
+    );
+
+    app.add_observer(count_damage_dealt);
+    app.add_observer(count_kill);
+    app.add_observer(count_damage_taken);
+}
+
+/// Damage dealt by one [`WeaponKind`].
+#[derive(Serialize, Default, Debug, Clone, Copy, PartialEq)]
+pub(crate) struct WeaponStats {
+    pub damage: f32,
+    /// Enemies damaged, an area hit counts every enemy inside it.
+    pub hits: u32,
+    pub kills: u32,
+}
+
+/// What happened during the current run.
+#[derive(Resource, Serialize, Default, Debug, Clone)]
+pub(crate) struct RunStats {
+    pub weapons: HashMap<WeaponKind, WeaponStats>,
+    /// All kills, including those not made by a weapon.
+    pub kills: u32,
+    pub damage_taken: HashMap<EnemyType, f32>,
+    pub gems_collected: u32,
+    /// Seconds the player was alive, not counting pauses.
+    pub time_alive: f32,
+}
+
+impl RunStats {
+    /// Damage per second of alive time.
+    pub fn dps(&self, kind: WeaponKind) -> f32 {
+        self.weapons.get(&kind).map_or(0.0, |stats| {
+            stats.damage / self.time_alive.max(f32::EPSILON)
+        })
+    }
+
+    /// Weapons that dealt damage, the most damaging first.
+    pub fn weapons_by_damage(&self) -> Vec<(WeaponKind, WeaponStats)> {
+        let mut weapons: Vec<_> = self.weapons.iter().map(|(k, s)| (*k, *s)).collect();
+        weapons.sort_by(|(_, a), (_, b)| b.damage.total_cmp(&a.damage));
+        weapons
+    }
+
+    /// Enemy types that dealt damage, the most damaging first.
+    pub fn damage_taken_by_source(&self) -> Vec<(EnemyType, f32)> {
+        let mut sources: Vec<_> = self.damage_taken.iter().map(|(t, d)| (*t, *d)).collect();
+        sources.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+        sources
+    }
+
+    /// One `stat,subject,value` row per number, e.g. `damage,Fireball,1234.5`.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("stat,subject,value\n");
+        let _ = writeln!(csv, "time_alive,,{:.2}", self.time_alive);
+        let _ = writeln!(csv, "kills,,{}", self.kills);
+        let _ = writeln!(csv, "gems_collected,,{}", self.gems_collected);
+        for (kind, stats) in self.weapons_by_damage() {
+            let _ = writeln!(csv, "damage,{kind:?},{:.2}", stats.damage);
+            let _ = writeln!(csv, "hits,{kind:?},{}", stats.hits);
+            let _ = writeln!(csv, "kills,{kind:?},{}", stats.kills);
+            let _ = writeln!(csv, "dps,{kind:?},{:.2}", self.dps(kind));
+        }
+        for (source, damage) in self.damage_taken_by_source() {
+            let _ = writeln!(csv, "damage_taken,{source:?},{damage:.2}");
+        }
+        csv
+    }
+}
+
+fn reset_run_stats(mut stats: ResMut<RunStats>) {
+    *stats = RunStats::default();
+}
+
+fn count_time_alive(
+    player: Option<Single<&Health, With<Player>>>,
+    time: Res<Time>,
+    mut stats: ResMut<RunStats>,
+) {
+    if player.is_some_and(|health| health.0 > 0.0) {
+        stats.time_alive += time.delta_secs();
+    }
+}
+
+fn count_damage_dealt(trigger: On<EnemyDamageEvent>, mut stats: ResMut<RunStats>) {
+    let Some(kind) = trigger.source else {
+        return;
+    };
+
+    let weapon = stats.weapons.entry(kind).or_default();
+    weapon.damage += trigger.dmg;
+    weapon.hits += 1;
+}
+
+fn count_kill(trigger: On<EnemyDeathEvent>, mut stats: ResMut<RunStats>) {
+    stats.kills += 1;
+    if let Some(kind) = trigger.killed_by {
+        stats.weapons.entry(kind).or_default().kills += 1;
+    }
+}
+
+fn count_damage_taken(trigger: On<PlayerHitEvent>, mut stats: ResMut<RunStats>) {
+    *stats.damage_taken.entry(trigger.source).or_default() += trigger.dmg;
+}
+
+fn export_run_stats(stats: Res<RunStats>) {
+    if stats.time_alive <= 0.0 {
+        return;
+    }
+
+    if let Some(path) = persistence::config_path(STATS_RON_FILE)
+        && let Err(err) = persistence::save_ron(&path, &*stats)
+    {
+        warn!("Failed to save the run stats to {}: {err}", path.display());
+    }
+    if let Some(path) = persistence::config_path(STATS_CSV_FILE)
+        && let Err(err) = persistence::save_string(&path, &stats.to_csv())
+    {
+        warn!("Failed to save the run stats to {}: {err}", path.display());
+    }
+}
+
+#[derive(Component)]
+struct StatsPanel;
+
+#[derive(Component)]
+struct StatsText;
+
+fn spawn_stats_panel(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Stats Panel"),
+        StatsPanel,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(60.0),
+            right: Val::Px(20.0),
+            padding: UiRect::all(Val::Px(12.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+        Visibility::Hidden,
+        Pickable::IGNORE,
+        DespawnOnExit(Screen::Gameplay),
+        children![(
+            StatsText,
+            Text::default(),
+            TextFont::from_font_size(16.0),
+            TextColor(Color::WHITE),
+        )],
+    ));
+}
+
+fn toggle_stats_panel(mut panel: Single<&mut Visibility, With<StatsPanel>>) {
+    **panel = match **panel {
+        Visibility::Hidden => Visibility::Visible,
+        _ => Visibility::Hidden,
+    };
+}
+
+fn update_stats_panel(stats: Res<RunStats>, mut text: Single<&mut Text, With<StatsText>>) {
+    let seconds = stats.time_alive as u32;
+    let mut body = format!(
+        "Time alive  {}:{:02}\nKills  {}\nGems  {}\n",
+        seconds / 60,
+        seconds % 60,
+        stats.kills,
+        stats.gems_collected,
+    );
+
+    body.push_str("\nWeapon  dmg / hits / kills / dps\n");
+    for (kind, weapon) in stats.weapons_by_damage() {
+        let _ = writeln!(
+            body,
+            "{kind:?}  {:.0} / {} / {} / {:.1}",
+            weapon.damage,
+            weapon.hits,
+            weapon.kills,
+            stats.dps(kind),
+        );
+    }
+
+    body.push_str("\nDamage taken\n");
+    for (source, damage) in stats.damage_taken_by_source() {
+        let _ = writeln!(body, "{source:?}  {damage:.0}");
+    }
+
+    text.0 = body;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_lists_weapons_by_damage() {
+        let mut stats = RunStats {
+            kills: 3,
+            gems_collected: 2,
+            time_alive: 10.0,
+            ..default()
+        };
+        stats.weapons.insert(
+            WeaponKind::Orb,
+            WeaponStats {
+                damage: 50.0,
+                hits: 5,
+                kills: 1,
+            },
+        );
+        stats.weapons.insert(
+            WeaponKind::Fireball,
+            WeaponStats {
+                damage: 200.0,
+                hits: 4,
+                kills: 2,
+            },
+        );
+        stats.damage_taken.insert(EnemyType::Walker, 7.5);
+
+        let csv = stats.to_csv();
+        let rows: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(rows[0], "stat,subject,value");
+        assert!(rows.contains(&"time_alive,,10.00"));
+        assert!(rows.contains(&"dps,Fireball,20.00"));
+        assert!(rows.contains(&"damage_taken,Walker,7.50"));
+        let fireball = rows
+            .iter()
+            .position(|row| row.starts_with("damage,Fireball"));
+        let orb = rows.iter().position(|row| row.starts_with("damage,Orb"));
+        assert!(fireball < orb);
+    }
+}
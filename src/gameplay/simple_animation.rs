@@ -2,7 +2,7 @@ use std::time::Duration;
 
 use bevy::prelude::*;
 
-use crate::{gameplay::enemy::Root, screens::Screen};
+use crate::{gameplay::enemy::Root, screens::Screen, settings::Settings};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
@@ -56,6 +56,7 @@ impl Default for HurtAnimationTimer {
 
 fn hurt_flash(
     time: Res<Time>,
+    settings: Res<Settings>,
     mut commands: Commands,
     mut query: Query<(Entity, &mut HurtAnimationTimer, &mut Sprite)>,
 ) {
@@ -66,13 +67,18 @@ fn hurt_flash(
             sprite.color = Color::WHITE;
             commands.entity(entity).remove::<HurtAnimationTimer>();
         } else {
-            sprite.color = Color::srgba(1.0, 0.0, 0.0, 1.0);
+            sprite.color = if settings.accessibility.reduced_flashes {
+                Color::srgba(1.0, 0.6, 0.6, 1.0)
+            } else {
+                Color::srgba(1.0, 0.0, 0.0, 1.0)
+            };
         }
     }
 }
 
 fn root_flash(
     time: Res<Time>,
+    settings: Res<Settings>,
     mut commands: Commands,
     mut query: Query<(Entity, &mut Root, &mut Sprite)>,
 ) {
@@ -83,7 +89,11 @@ fn root_flash(
             sprite.color = Color::WHITE;
             commands.entity(entity).remove::<Root>();
         } else {
-            sprite.color = Color::srgba(0.5, 1.0, 1.0, 1.0);
+            sprite.color = if settings.accessibility.reduced_flashes {
+                Color::srgba(0.8, 1.0, 1.0, 1.0)
+            } else {
+                Color::srgba(0.5, 1.0, 1.0, 1.0)
+            };
         }
     }
 }
@@ -1,6 +1,7 @@
 use std::collections::VecDeque;
 
 use bevy::{platform::collections::HashMap, prelude::*};
+use serde::Deserialize;
 
 use crate::{
     PausableSystems, SPAWN_RADIUS, SPAWN_RADIUS_BUFFER,
@@ -12,15 +13,20 @@ use crate::{
             sprinter::{SprinterPatchEvent, SprinterSpawnEvent},
             walker::{WalkerPatchEvent, WalkerSpawnEvent},
         },
-        player::Player,
-        waves::waveplan::make_wave_plan,
+        hub::SelectedQuest,
+        level::CurrentLevel,
+        player::{Player, movement::CameraBounds},
+        waves::waveplan::{make_rush_wave_plan, make_wave_plan},
     },
     screens::Screen,
 };
 
 mod waveplan;
+pub(crate) mod zones;
 
 pub(crate) fn plugin(app: &mut App) {
+    app.add_plugins(zones::plugin);
+
     app.add_systems(OnEnter(Screen::Gameplay), wave_spawner);
 
     app.add_systems(
@@ -80,9 +86,44 @@ pub(crate) struct WavePlan {
     pub waves: VecDeque<WaveStats>,
 }
 
-fn wave_spawner(mut commands: Commands) {
+/// The wave plans a level or quest can choose from.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect, Deserialize)]
+pub enum WavePlanKind {
+    #[default]
+    Standard,
+    Rush,
+}
+
+impl WavePlanKind {
+    pub(crate) fn make(self) -> WavePlan {
+        match self {
+            WavePlanKind::Standard => make_wave_plan(),
+            WavePlanKind::Rush => make_rush_wave_plan(),
+        }
+    }
+}
+
+fn wave_spawner(
+    mut commands: Commands,
+    selected_quest: Res<SelectedQuest>,
+    level: Res<CurrentLevel>,
+) {
     info!("Wave spawned");
-    commands.insert_resource(make_wave_plan());
+    let mut plan = selected_quest
+        .quest()
+        .wave_plan()
+        .unwrap_or(level.definition.wave_plan)
+        .make();
+
+    for wave in &mut plan.waves {
+        for (enemy_type, sprite) in &level.definition.enemy_sprites {
+            if let Some(wave_sprite) = wave.sprite_pool.get_mut(enemy_type) {
+                wave_sprite.clone_from(sprite);
+            }
+        }
+    }
+
+    commands.insert_resource(plan);
     commands.spawn((Name::new("Wave"), Wave, DespawnOnExit(Screen::Gameplay)));
     // commands.spawn((Name::new("Wave"), Wave));
     commands.trigger(WavePatchEvent);
@@ -125,6 +166,7 @@ fn account_enemies(
     mut wave_q: Query<(&EnemyPool, &EnemyScreenCount), With<Wave>>,
     player_q: Query<&Transform, With<Player>>,
     enemy_q: Query<(&Transform, &EnemyType), With<Enemy>>,
+    camera_bounds: Res<CameraBounds>,
     mut commands: Commands,
 ) -> Result {
     let Ok(player_pos) = player_q.single() else {
@@ -148,22 +190,21 @@ fn account_enemies(
             }
         }
 
-        match signature_enemy {
-            EnemyType::Walker => commands.trigger(WalkerSpawnEvent),
-            EnemyType::Shooter => commands.trigger(ShooterSpawnEvent),
-            EnemyType::Sprinter => commands.trigger(SprinterSpawnEvent),
-            EnemyType::Jumper => commands.trigger(JumperSpawnEvent),
-            EnemyType::None => (),
-        }
+        spawn_enemy(&mut commands, signature_enemy, None);
         return Ok(());
     }
 
-    // Count enemies
+    // Count enemies around the screen, including those just spawned off-screen
+    let counted_area = camera_bounds.spawn_area().inflate(SPAWN_RADIUS_BUFFER);
     for (transform, enemy_type) in &enemy_q {
         let enemy_pos = transform.translation.truncate();
-        if enemy_pos.distance(player_pos.translation.truncate())
-            <= (SPAWN_RADIUS + SPAWN_RADIUS_BUFFER)
-        {
+        let nearby = if camera_bounds.viewport.is_empty() {
+            enemy_pos.distance(player_pos.translation.truncate())
+                <= (SPAWN_RADIUS + SPAWN_RADIUS_BUFFER)
+        } else {
+            counted_area.contains(enemy_pos)
+        };
+        if nearby {
             absolut_enemy_count += 1.0;
             if let Some(count) = live_enemies.get_mut(enemy_type) {
                 *count += 1.0
@@ -190,18 +231,23 @@ fn account_enemies(
             }
         }
 
-        match demanded_type {
-            EnemyType::Walker => commands.trigger(WalkerSpawnEvent),
-            EnemyType::Shooter => commands.trigger(ShooterSpawnEvent),
-            EnemyType::Sprinter => commands.trigger(SprinterSpawnEvent),
-            EnemyType::Jumper => commands.trigger(JumperSpawnEvent),
-            EnemyType::None => (),
-        }
+        spawn_enemy(&mut commands, *demanded_type, None);
     }
 
     Ok(())
 }
 
+/// Spawn an enemy at `position`, or at a spawn position picked near the player.
+pub(crate) fn spawn_enemy(commands: &mut Commands, enemy_type: EnemyType, position: Option<Vec2>) {
+    match enemy_type {
+        EnemyType::Walker => commands.trigger(WalkerSpawnEvent(position)),
+        EnemyType::Shooter => commands.trigger(ShooterSpawnEvent(position)),
+        EnemyType::Sprinter => commands.trigger(SprinterSpawnEvent(position)),
+        EnemyType::Jumper => commands.trigger(JumperSpawnEvent(position)),
+        EnemyType::None => (),
+    }
+}
+
 fn wave_timer_handle(
     mut commands: Commands,
     mut wave_q: Query<(&mut SpawnTimer, &mut WaveDuration), With<Wave>>,
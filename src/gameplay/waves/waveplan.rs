@@ -88,3 +88,14 @@ pub(crate) fn make_wave_plan() -> WavePlan {
         ]),
     }
 }
+
+/// The standard plan with shorter, denser waves.
+pub(crate) fn make_rush_wave_plan() -> WavePlan {
+    let mut plan = make_wave_plan();
+    for wave in &mut plan.waves {
+        wave.duration *= 0.5;
+        wave.spawn_frequency *= 2.0;
+        wave.enemy_screen_count *= 1.5;
+    }
+    plan
+}
@@ -0,0 +1,231 @@
+//! Enemy spawn zones and scripted spawners placed as objects in Tiled.
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_ecs_tiled::prelude::TiledObject;
+use bevy_rand::prelude::WyRand;
+use rand::Rng;
+
+use crate::{
+    PausableSystems, SPAWN_ATTEMPTS,
+    gameplay::{
+        enemy::EnemyType, overlays::offers::pick_weighted, player::Player, waves::spawn_enemy,
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.register_type::<EnemySpawnZone>();
+    app.register_type::<EnemySpawner>();
+
+    app.add_systems(
+        FixedUpdate,
+        trigger_spawners
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// A rectangle or polygon in Tiled where enemies may spawn.
+///
+/// Each weight is the chance of this zone being picked for that enemy type,
+/// relative to the other zones. A weight of `0` forbids the type here.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component, Default)]
+pub(crate) struct EnemySpawnZone {
+    pub walker: f32,
+    pub shooter: f32,
+    pub sprinter: f32,
+    pub jumper: f32,
+}
+
+impl EnemySpawnZone {
+    pub fn weight(&self, enemy_type: EnemyType) -> f32 {
+        match enemy_type {
+            EnemyType::Walker => self.walker,
+            EnemyType::Shooter => self.shooter,
+            EnemyType::Sprinter => self.sprinter,
+            EnemyType::Jumper => self.jumper,
+            EnemyType::None => 0.0,
+        }
+    }
+}
+
+/// A scripted group of enemies, spawned once when the player comes within `trigger_radius`.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Reflect)]
+#[reflect(Component, Default)]
+pub(crate) struct EnemySpawner {
+    pub enemy_type: EnemyType,
+    pub count: u32,
+    pub trigger_radius: f32,
+}
+
+/// Spacing between the enemies of a spawner group.
+const SPAWNER_SPREAD: f32 = 12.0;
+
+fn trigger_spawners(
+    mut commands: Commands,
+    player_q: Query<&Transform, With<Player>>,
+    spawner_q: Query<(Entity, &EnemySpawner, &GlobalTransform)>,
+) {
+    let Ok(player_transform) = player_q.single() else {
+        return;
+    };
+    let player_pos = player_transform.translation.truncate();
+
+    for (entity, spawner, transform) in &spawner_q {
+        let spawner_pos = transform.translation().truncate();
+        if spawner_pos.distance(player_pos) > spawner.trigger_radius {
+            continue;
+        }
+
+        for i in 0..spawner.count {
+            let offset = Vec2::from_angle(i as f32 * 2.4) * SPAWNER_SPREAD * (i as f32).sqrt();
+            spawn_enemy(
+                &mut commands,
+                spawner.enemy_type,
+                Some(spawner_pos + offset),
+            );
+        }
+        commands.entity(entity).remove::<EnemySpawner>();
+    }
+}
+
+/// The [`EnemySpawnZone`]s of the current map.
+#[derive(SystemParam)]
+pub(crate) struct SpawnZones<'w, 's> {
+    zone_q: Query<
+        'w,
+        's,
+        (
+            &'static EnemySpawnZone,
+            &'static TiledObject,
+            &'static GlobalTransform,
+        ),
+    >,
+}
+
+impl SpawnZones<'_, '_> {
+    /// Pick a point inside a zone that allows `enemy_type` and passes `is_valid`.
+    ///
+    /// Returns `None` if no such zone exists, or no valid point was found.
+    pub fn pick_position(
+        &self,
+        enemy_type: EnemyType,
+        rng: &mut WyRand,
+        is_valid: impl Fn(Vec2) -> bool,
+    ) -> Option<Vec2> {
+        let zones: Vec<(Vec<Vec2>, f32)> = self
+            .zone_q
+            .iter()
+            .filter_map(|(zone, object, transform)| {
+                let weight = zone.weight(enemy_type);
+                if weight <= 0.0 {
+                    return None;
+                }
+                let polygon = zone_polygon(object, transform)?;
+                Some((polygon, weight))
+            })
+            .collect();
+
+        if zones.is_empty() {
+            return None;
+        }
+
+        for _ in 0..SPAWN_ATTEMPTS {
+            let polygon = pick_weighted(zones.clone(), 1, rng).pop()?;
+            let Some(point) = random_point_in_polygon(&polygon, rng) else {
+                continue;
+            };
+            if is_valid(point) {
+                return Some(point);
+            }
+        }
+
+        None
+    }
+}
+
+/// The world space outline of a zone object, if it has an area.
+fn zone_polygon(object: &TiledObject, transform: &GlobalTransform) -> Option<Vec<Vec2>> {
+    let local = match object {
+        TiledObject::Rectangle { width, height } => vec![
+            Vec2::ZERO,
+            Vec2::new(*width, 0.0),
+            Vec2::new(*width, -*height),
+            Vec2::new(0.0, -*height),
+        ],
+        TiledObject::Polygon { vertices } => vertices.clone(),
+        _ => return None,
+    };
+
+    Some(
+        local
+            .into_iter()
+            .map(|vertex| transform.transform_point(vertex.extend(0.0)).truncate())
+            .collect(),
+    )
+}
+
+/// Rejection sample a point inside the polygon's bounding box.
+fn random_point_in_polygon(polygon: &[Vec2], rng: &mut WyRand) -> Option<Vec2> {
+    let min = polygon.iter().copied().reduce(Vec2::min)?;
+    let max = polygon.iter().copied().reduce(Vec2::max)?;
+    if min.x >= max.x || min.y >= max.y {
+        return None;
+    }
+
+    (0..SPAWN_ATTEMPTS)
+        .map(|_| {
+            Vec2::new(
+                rng.random_range(min.x..max.x),
+                rng.random_range(min.y..max.y),
+            )
+        })
+        .find(|point| polygon_contains(polygon, *point))
+}
+
+/// Even-odd test of whether `point` lies inside `polygon`.
+fn polygon_contains(polygon: &[Vec2], point: Vec2) -> bool {
+    let mut inside = false;
+    let mut previous = match polygon.last() {
+        Some(vertex) => *vertex,
+        None => return false,
+    };
+
+    for vertex in polygon {
+        if (vertex.y > point.y) != (previous.y > point.y)
+            && point.x
+                < (previous.x - vertex.x) * (point.y - vertex.y) / (previous.y - vertex.y)
+                    + vertex.x
+        {
+            inside = !inside;
+        }
+        previous = *vertex;
+    }
+
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn polygon_contains_concave_shape() {
+        // An L shape, open towards the top right.
+        let polygon = [
+            Vec2::new(0.0, 0.0),
+            Vec2::new(20.0, 0.0),
+            Vec2::new(20.0, 10.0),
+            Vec2::new(10.0, 10.0),
+            Vec2::new(10.0, 20.0),
+            Vec2::new(0.0, 20.0),
+        ];
+
+        assert!(polygon_contains(&polygon, Vec2::new(5.0, 5.0)));
+        assert!(polygon_contains(&polygon, Vec2::new(15.0, 5.0)));
+        assert!(polygon_contains(&polygon, Vec2::new(5.0, 15.0)));
+        assert!(!polygon_contains(&polygon, Vec2::new(15.0, 15.0)));
+        assert!(!polygon_contains(&polygon, Vec2::new(-1.0, 5.0)));
+    }
+}
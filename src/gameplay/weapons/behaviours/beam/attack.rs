@@ -0,0 +1,63 @@
+use bevy::{prelude::*, sprite::Anchor};
+use bevy_seedling::sample::SamplePlayer;
+
+use crate::{
+    audio::SfxPool,
+    gameplay::weapons::{
+        behaviours::{
+            Attack, WeaponAttackSfx, WeaponProjectileVisuals,
+            beam::{Beam, BeamAttack, BeamTargets},
+        },
+        components::{CastWeapon, WeaponLifetime},
+        systems::cooldown::WeaponDuration,
+        targeting::{TargetingSpec, Targets},
+    },
+};
+
+pub fn on_beam_attack(
+    attack: On<Attack<BeamAttack>>,
+    weapon_q: Query<
+        (
+            Entity,
+            &TargetingSpec,
+            &WeaponLifetime,
+            &WeaponProjectileVisuals,
+            Option<&WeaponAttackSfx>,
+        ),
+        With<BeamAttack>,
+    >,
+    mut targets: Targets,
+    mut commands: Commands,
+) {
+    let Ok((entity, targeting, lifetime, visuals, sfx)) = weapon_q.get(attack.source.weapon) else {
+        return;
+    };
+    targets.attack_from(attack.source.origin);
+
+    let Some(direction) = targets.direction(*targeting) else {
+        return;
+    };
+    let origin = targets.origin();
+
+    if let Some(weapon_sfx) = sfx {
+        commands.spawn((SamplePlayer::new(weapon_sfx.0.clone()), SfxPool));
+    }
+
+    // Length and targets are set by `update_beams` before the first damage tick.
+    let mut beam = commands.spawn((
+        Name::new("Beam"),
+        Beam {
+            angle: direction.to_angle(),
+            origin: attack.source.origin,
+        },
+        BeamTargets::default(),
+        CastWeapon(entity),
+        Transform::from_translation(origin.extend(10.0))
+            .with_rotation(Quat::from_rotation_z(direction.to_angle()))
+            .with_scale(Vec3::new(0.0, 1.0, 1.0)),
+        WeaponDuration(Timer::from_seconds(lifetime.0, TimerMode::Once)),
+    ));
+
+    visuals.0.apply_ec(&mut beam);
+    beam.insert(Anchor::CENTER_LEFT);
+}
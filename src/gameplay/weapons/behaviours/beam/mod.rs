@@ -0,0 +1,241 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::gameplay::weapons::{
+    behaviours::{Attack, AttackSource, TriggerAttackBehavior},
+    components::{TickDuration, WeaponLifetime},
+};
+
+mod attack;
+mod movement;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(attack::on_beam_attack);
+    app.add_plugins(movement::plugin);
+}
+
+/// Seconds between damage ticks if the weapon has no `dot`.
+const DEFAULT_BEAM_TICK: f32 = 0.2;
+
+#[derive(Component)]
+pub struct BeamAttack;
+
+/// The shape of the beams a weapon fires, from its [`BeamSpec`].
+#[derive(Component, Debug, Clone, Copy)]
+pub struct BeamConfig {
+    pub range: f32,
+    pub width: f32,
+    pub pierce: u32,
+    /// Radians per second.
+    pub sweep: f32,
+}
+
+/// A fired beam, pointing at `angle` from where it was fired.
+#[derive(Component, Debug)]
+pub struct Beam {
+    pub angle: f32,
+    /// Where a beam fired by a deployable starts, beams from the player follow the player.
+    pub origin: Option<Vec2>,
+}
+
+/// The enemies a beam currently touches, damaged on every tick.
+#[derive(Component, Debug, Default)]
+pub struct BeamTargets(pub Vec<Entity>);
+
+/// A beam from the player, or the deployable firing it, that damages every enemy it touches
+/// every `dot` seconds of the weapon.
+///
+/// The beam stops at walls, and at the enemy after the first `pierce` enemies.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BeamSpec {
+    pub range: f32,
+    pub width: f32,
+    /// Seconds the beam lasts.
+    pub duration: f32,
+    /// Enemies passed through, unlimited if not set.
+    #[serde(default)]
+    pub pierce: Option<u32>,
+    /// Degrees per second the beam rotates while it lasts.
+    #[serde(default)]
+    pub sweep: f32,
+}
+
+impl EntityCommand for BeamSpec {
+    fn apply(self, mut entity: EntityWorldMut) {
+        entity.insert((
+            BeamAttack,
+            BeamConfig {
+                range: self.range,
+                width: self.width,
+                pierce: self.pierce.unwrap_or(u32::MAX),
+                sweep: self.sweep.to_radians(),
+            },
+            WeaponLifetime(self.duration),
+            // Replaced by the weapon's `dot`, if it has one.
+            TickDuration(DEFAULT_BEAM_TICK),
+        ));
+    }
+}
+
+impl TriggerAttackBehavior for BeamSpec {
+    fn trigger(&self, source: AttackSource, mut commands: Commands) {
+        commands.trigger(Attack::<BeamAttack>::new(source));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use avian2d::prelude::{Collider, RigidBody};
+
+    use super::*;
+    use crate::{
+        gameplay::weapons::{kind::WeaponKind, spec::loader::test_spec, targeting::TargetingSpec},
+        sim,
+    };
+
+    /// Enemies hit by the one damage tick of `beam`, fired to the right at walkers at
+    /// `walkers` with a wall at `wall`.
+    fn beam_hits(beam: &str, walkers: &[Vec2], wall: Option<Vec2>) -> u32 {
+        let mut spec = test_spec(WeaponKind::Energy, beam);
+        spec.targeting = TargetingSpec::Facing;
+        let mut app = sim::weapon_test_app([spec]);
+        for walker in walkers {
+            sim::spawn_walker(&mut app, *walker);
+        }
+        if let Some(wall) = wall {
+            app.world_mut().spawn((
+                RigidBody::Static,
+                Collider::rectangle(16.0, 200.0),
+                Transform::from_translation(wall.extend(0.0)),
+            ));
+        }
+
+        sim::attack(&mut app);
+        sim::advance(&mut app, 0.5);
+        sim::hits(&app, WeaponKind::Energy)
+    }
+
+    #[test]
+    fn beams_are_as_wide_as_their_width() {
+        let beside = [Vec2::new(100.0, 30.0)];
+
+        assert_eq!(
+            beam_hits(
+                "Beam((range: 200.0, width: 2.0, duration: 0.3))",
+                &beside,
+                None
+            ),
+            0
+        );
+        assert_eq!(
+            beam_hits(
+                "Beam((range: 200.0, width: 60.0, duration: 0.3))",
+                &beside,
+                None
+            ),
+            1
+        );
+    }
+
+    #[test]
+    fn beams_stop_after_piercing() {
+        let row = [
+            Vec2::new(100.0, 0.0),
+            Vec2::new(150.0, 0.0),
+            Vec2::new(200.0, 0.0),
+        ];
+
+        assert_eq!(
+            beam_hits(
+                "Beam((range: 300.0, width: 8.0, duration: 0.3))",
+                &row,
+                None
+            ),
+            3
+        );
+        assert_eq!(
+            beam_hits(
+                "Beam((range: 300.0, width: 8.0, duration: 0.3, pierce: 1))",
+                &row,
+                None
+            ),
+            2
+        );
+    }
+
+    #[test]
+    fn beams_stop_at_walls() {
+        let behind_wall = [Vec2::new(150.0, 0.0)];
+
+        assert_eq!(
+            beam_hits(
+                "Beam((range: 300.0, width: 8.0, duration: 0.3))",
+                &behind_wall,
+                None
+            ),
+            1
+        );
+        assert_eq!(
+            beam_hits(
+                "Beam((range: 300.0, width: 8.0, duration: 0.3))",
+                &behind_wall,
+                Some(Vec2::new(75.0, 0.0))
+            ),
+            0
+        );
+    }
+
+    #[test]
+    fn beams_tick_every_dot() {
+        let ticks = |dot: Option<f32>| {
+            let mut spec = test_spec(
+                WeaponKind::Energy,
+                "Beam((range: 200.0, width: 8.0, duration: 0.5))",
+            );
+            spec.targeting = TargetingSpec::Facing;
+            spec.dot = dot;
+            let mut app = sim::weapon_test_app([spec]);
+            sim::spawn_walker(&mut app, Vec2::new(100.0, 0.0));
+
+            sim::attack(&mut app);
+            sim::advance(&mut app, 1.0);
+            sim::hits(&app, WeaponKind::Energy)
+        };
+
+        assert_eq!(ticks(None), 2);
+        assert!((4..=5).contains(&ticks(Some(0.1))));
+    }
+
+    #[test]
+    fn deployed_beams_fire_from_the_deployable() {
+        let mut spec = test_spec(
+            WeaponKind::Energy,
+            "Deploy((trigger: Cooldown(seconds: 0.1), max_active: 1, lifetime: 5.0, \
+             attack: Beam((range: 100.0, width: 8.0, duration: 5.0))))",
+        );
+        spec.targeting = TargetingSpec::Facing;
+        let mut app = sim::weapon_test_app([spec]);
+        sim::spawn_walker(&mut app, Vec2::new(60.0, 0.0));
+        sim::root_enemies(&mut app);
+
+        // The turret stays where the player was.
+        sim::attack(&mut app);
+        sim::advance(&mut app, 0.05);
+        sim::move_player(&mut app, Vec2::new(0.0, 300.0));
+        sim::advance(&mut app, 0.5);
+
+        let beams: Vec<Vec2> = app
+            .world_mut()
+            .query_filtered::<&Transform, With<Beam>>()
+            .iter(app.world())
+            .map(|transform| transform.translation.truncate())
+            .collect();
+        assert!(!beams.is_empty());
+        assert!(
+            beams.iter().all(|beam| beam.length() < 1.0),
+            "beams at {beams:?}"
+        );
+        assert!(sim::hits(&app, WeaponKind::Energy) > 0);
+    }
+}
@@ -0,0 +1,85 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    GameLayer, PausableSystems,
+    gameplay::{
+        enemy::Enemy,
+        player::Player,
+        weapons::{
+            behaviours::{
+                WeaponProjectileVisuals,
+                beam::{Beam, BeamConfig, BeamTargets},
+            },
+            components::CastWeapon,
+        },
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        (update_beams)
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Sweep beams, cast a circle as wide as the beam and stretch their sprite up to where it stops.
+pub fn update_beams(
+    player_q: Single<&Transform, (With<Player>, Without<Beam>)>,
+    mut beam_q: Query<(&CastWeapon, &mut Beam, &mut BeamTargets, &mut Transform)>,
+    weapon_q: Query<(&BeamConfig, &WeaponProjectileVisuals)>,
+    enemy_q: Query<(), With<Enemy>>,
+    body_q: Query<&RigidBody>,
+    spatial_query: SpatialQuery,
+    time: Res<Time>,
+) {
+    let player_position = player_q.translation.truncate();
+
+    for (cast_weapon, mut beam, mut targets, mut tf) in &mut beam_q {
+        let origin = beam.origin.unwrap_or(player_position);
+        let Ok((config, visuals)) = weapon_q.get(cast_weapon.0) else {
+            continue;
+        };
+
+        beam.angle += config.sweep * time.delta_secs();
+        let Ok(direction) = Dir2::new(Vec2::from_angle(beam.angle)) else {
+            continue;
+        };
+
+        targets.0.clear();
+        let mut length = config.range;
+        let mut filter = SpatialQueryFilter::from_mask([GameLayer::Enemy, GameLayer::Default]);
+        let radius = config.width / 2.0;
+        let shape = Collider::circle(radius);
+        let cast = ShapeCastConfig::from_max_distance(config.range);
+
+        while let Some(hit) =
+            spatial_query.cast_shape(&shape, origin, 0.0, direction, &cast, &filter)
+        {
+            // The beam reaches past the circle's center to its front.
+            let reach = (hit.distance + radius).min(config.range);
+            if enemy_q.contains(hit.entity) {
+                targets.0.push(hit.entity);
+                if targets.0.len() as u32 > config.pierce {
+                    length = reach;
+                    break;
+                }
+            } else if body_q.get(hit.entity).is_ok_and(RigidBody::is_static) {
+                length = reach;
+                break;
+            }
+            filter.excluded_entities.insert(hit.entity);
+        }
+
+        tf.translation = origin.extend(tf.translation.z);
+        tf.rotation = Quat::from_rotation_z(beam.angle);
+        tf.scale = Vec3::new(
+            length / visuals.0.size.x,
+            config.width / visuals.0.size.y,
+            1.0,
+        );
+    }
+}
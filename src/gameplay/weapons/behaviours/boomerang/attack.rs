@@ -0,0 +1,46 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::gameplay::weapons::{
+    behaviours::{
+        Attack, WeaponProjectileVisuals,
+        boomerang::{BoomerangAttack, BoomerangFlight, BoomerangLeg},
+    },
+    components::{CastWeapon, PlayerProjectile, ProjectileDirection, ProjectileSpeedScale},
+    targeting::{TargetingSpec, Targets},
+};
+
+pub fn on_boomerang_attack(
+    attack: On<Attack<BoomerangAttack>>,
+    weapon_q: Query<(Entity, &TargetingSpec, &WeaponProjectileVisuals), With<BoomerangAttack>>,
+    mut targets: Targets,
+    mut commands: Commands,
+) {
+    let Ok((entity, targeting, projectile_visuals)) = weapon_q.get(attack.source.weapon) else {
+        return;
+    };
+    targets.attack_from(attack.source.origin);
+
+    let Some(direction) = targets.direction(*targeting) else {
+        return;
+    };
+    let origin = targets.origin();
+
+    let mut proj = commands.spawn((
+        Name::new("Boomerang Projectile"),
+        CastWeapon(entity),
+        Transform::from_xyz(origin.x, origin.y, 10.0),
+        ProjectileDirection(direction.extend(0.0)),
+        ProjectileSpeedScale(1.0),
+        BoomerangFlight {
+            leg: BoomerangLeg::Out,
+            heading: direction,
+            travelled: 0.0,
+            last_position: origin,
+        },
+        Mass(0.1),
+        PlayerProjectile,
+    ));
+
+    projectile_visuals.0.apply_ec(&mut proj);
+}
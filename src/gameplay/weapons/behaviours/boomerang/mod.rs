@@ -0,0 +1,227 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::gameplay::weapons::{
+    behaviours::{Attack, AttackSource, TriggerAttackBehavior},
+    components::ProjectileSpeed,
+    spec::components::{ProjectilePolicy, WallBehaviour},
+};
+
+mod attack;
+mod movement;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(attack::on_boomerang_attack);
+    app.add_plugins(movement::plugin);
+}
+
+#[derive(Component)]
+pub struct BoomerangAttack;
+
+/// How far and along which curve a weapon throws its boomerangs.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct BoomerangConfig {
+    pub distance: f32,
+    pub curve: BoomerangCurve,
+}
+
+/// The path a boomerang takes.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub enum BoomerangCurve {
+    #[default]
+    Straight,
+    /// Turn `degrees` to the left over the way out, and bend back in on the way home.
+    Arc { degrees: f32 },
+}
+
+/// Which way a boomerang is flying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BoomerangLeg {
+    Out,
+    Return,
+}
+
+/// A thrown boomerang and how far it flew on its current leg.
+#[derive(Component, Debug)]
+pub struct BoomerangFlight {
+    pub leg: BoomerangLeg,
+    /// The direction it was thrown in.
+    pub heading: Vec2,
+    pub travelled: f32,
+    pub last_position: Vec2,
+}
+
+/// A projectile flying out up to `distance`, slowing down, and returning to the player.
+/// It hits every enemy once on the way out and once on the way back.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BoomerangSpec {
+    pub speed: f32,
+    pub distance: f32,
+    #[serde(default)]
+    pub curve: BoomerangCurve,
+    /// Seconds until a boomerang that didn't make it back expires.
+    #[serde(default = "default_boomerang_lifetime")]
+    pub lifetime: f32,
+}
+
+fn default_boomerang_lifetime() -> f32 {
+    10.0
+}
+
+impl EntityCommand for BoomerangSpec {
+    fn apply(self, mut entity: EntityWorldMut) {
+        entity.insert((
+            BoomerangAttack,
+            BoomerangConfig {
+                distance: self.distance,
+                curve: self.curve,
+            },
+            ProjectileSpeed(self.speed),
+            // Never stops at enemies, the hits are reset when it turns around
+            ProjectilePolicy {
+                walls: WallBehaviour::Pass,
+                lifetime: self.lifetime,
+                ..default()
+            },
+        ));
+    }
+}
+
+impl TriggerAttackBehavior for BoomerangSpec {
+    fn trigger(&self, source: AttackSource, mut commands: Commands) {
+        commands.trigger(Attack::<BoomerangAttack>::new(source));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        gameplay::{
+            Health,
+            enemy::Enemy,
+            weapons::{
+                components::ProjectileHits, kind::WeaponKind, spec::loader::test_spec,
+                targeting::TargetingSpec,
+            },
+        },
+        sim,
+    };
+
+    /// Where a boomerang was, on which leg and how many enemies it hit on that leg.
+    type Frame = (Vec2, BoomerangLeg, usize);
+
+    /// Throw `boomerang` to the right at walkers rooted at `walkers`, and follow it every
+    /// frame until it's caught.
+    fn throw(boomerang: &str, walkers: &[Vec2]) -> (App, Vec<Frame>) {
+        let mut spec = test_spec(WeaponKind::Slash, boomerang);
+        spec.targeting = TargetingSpec::Facing;
+        let mut app = sim::weapon_test_app([spec]);
+        for walker in walkers {
+            sim::spawn_walker(&mut app, *walker);
+        }
+        sim::root_enemies(&mut app);
+        // Enough health to survive every hit.
+        let world = app.world_mut();
+        for mut health in world
+            .query_filtered::<&mut Health, With<Enemy>>()
+            .iter_mut(world)
+        {
+            health.0 = 1000.0;
+        }
+
+        sim::attack(&mut app);
+        let mut path = Vec::new();
+        for _ in 0..5 * 64 {
+            app.update();
+            let flight = app
+                .world_mut()
+                .query::<(&Transform, &BoomerangFlight, Option<&ProjectileHits>)>()
+                .single(app.world())
+                .ok()
+                .map(|(transform, flight, hits)| {
+                    let hit = hits.map_or(0, |hits| hits.hit.len());
+                    (transform.translation.truncate(), flight.leg, hit)
+                });
+            match flight {
+                Some(flight) => path.push(flight),
+                None => break,
+            }
+        }
+        (app, path)
+    }
+
+    #[test]
+    fn boomerangs_turn_around_at_their_distance_and_are_caught() {
+        let (mut app, path) = throw("Boomerang((speed: 200.0, distance: 100.0))", &[]);
+
+        let turn = path
+            .iter()
+            .position(|(_, leg, _)| *leg == BoomerangLeg::Return)
+            .expect("the boomerang turned around");
+        assert!(
+            path[..turn]
+                .iter()
+                .all(|(_, leg, _)| *leg == BoomerangLeg::Out)
+        );
+        assert!(
+            path[turn..]
+                .iter()
+                .all(|(_, leg, _)| *leg == BoomerangLeg::Return)
+        );
+
+        let furthest = path
+            .iter()
+            .map(|(position, _, _)| position.x)
+            .fold(0.0, f32::max);
+        assert!((100.0..115.0).contains(&furthest), "flew {furthest} out");
+
+        // Caught at the player, well before its lifetime ran out.
+        let (last, _, _) = path.last().unwrap();
+        assert!(last.length() < 24.0, "last seen at {last}");
+        let boomerangs = app
+            .world_mut()
+            .query::<&BoomerangFlight>()
+            .iter(app.world())
+            .count();
+        assert_eq!(boomerangs, 0);
+    }
+
+    #[test]
+    fn boomerangs_hit_every_enemy_once_per_leg() {
+        // Passed on the way out and back, the second one is out of reach.
+        let (app, path) = throw(
+            "Boomerang((speed: 200.0, distance: 100.0))",
+            &[Vec2::new(50.0, 0.0), Vec2::new(200.0, 0.0)],
+        );
+
+        assert_eq!(sim::hits(&app, WeaponKind::Slash), 2);
+
+        // The walker hit on the way out is forgotten at the turn, so it can be hit again.
+        let turn = path
+            .iter()
+            .position(|(_, leg, _)| *leg == BoomerangLeg::Return)
+            .expect("the boomerang turned around");
+        assert_eq!(path[turn - 1].2, 1);
+        assert_eq!(path[turn].2, 0);
+        assert_eq!(path.last().unwrap().2, 1);
+    }
+
+    #[test]
+    fn arced_boomerangs_curve_and_straight_ones_dont() {
+        let widest = |boomerang: &str| {
+            let (_, path) = throw(boomerang, &[]);
+            path.iter()
+                .map(|(position, _, _)| position.y.abs())
+                .fold(0.0, f32::max)
+        };
+
+        let straight = widest("Boomerang((speed: 200.0, distance: 100.0))");
+        assert!(straight < 1.0, "straight one strayed {straight}");
+
+        let arc = widest("Boomerang((speed: 200.0, distance: 100.0, curve: Arc(degrees: 90.0)))");
+        assert!(arc > 10.0, "arced one strayed {arc}");
+    }
+}
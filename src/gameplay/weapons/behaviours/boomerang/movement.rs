@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    gameplay::{
+        player::Player,
+        weapons::{
+            behaviours::boomerang::{
+                BoomerangConfig, BoomerangCurve, BoomerangFlight, BoomerangLeg,
+            },
+            components::{CastWeapon, ProjectileDirection, ProjectileHits, ProjectileSpeedScale},
+            systems::projectile_movement::move_projectiles,
+        },
+    },
+    screens::Screen,
+};
+
+/// Slowest a boomerang gets when it turns around, relative to its speed.
+const MIN_SPEED_SCALE: f32 = 0.2;
+/// Distance from the player at which a returning boomerang is caught.
+const CATCH_DISTANCE: f32 = 16.0;
+/// Radians per second a boomerang spins.
+const SPIN_SPEED: f32 = 12.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        steer_boomerangs
+            .before(move_projectiles)
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Slow boomerangs down on the way out, turn them around at their distance and speed them
+/// up again on their way back to the player.
+pub fn steer_boomerangs(
+    player_q: Single<&Transform, (With<Player>, Without<BoomerangFlight>)>,
+    weapon_q: Query<&BoomerangConfig>,
+    mut boomerang_q: Query<(
+        Entity,
+        &CastWeapon,
+        &mut Transform,
+        &mut BoomerangFlight,
+        &mut ProjectileDirection,
+        &mut ProjectileSpeedScale,
+        Option<&mut ProjectileHits>,
+    )>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    let player_pos = player_q.translation.truncate();
+
+    for (entity, cast_weapon, mut tf, mut flight, mut direction, mut speed_scale, hits) in
+        &mut boomerang_q
+    {
+        let Ok(config) = weapon_q.get(cast_weapon.0) else {
+            continue;
+        };
+
+        let position = tf.translation.truncate();
+        flight.travelled += position.distance(flight.last_position);
+        flight.last_position = position;
+        tf.rotate_z(SPIN_SPEED * time.delta_secs());
+
+        let progress = (flight.travelled / config.distance).min(1.0);
+        let heading = match flight.leg {
+            BoomerangLeg::Out => {
+                if progress >= 1.0 {
+                    flight.leg = BoomerangLeg::Return;
+                    flight.travelled = 0.0;
+                    // Enemies can be hit again on the way back
+                    if let Some(mut hits) = hits {
+                        hits.hit.clear();
+                    }
+                }
+
+                speed_scale.0 = (1.0 - progress).sqrt().max(MIN_SPEED_SCALE);
+                match config.curve {
+                    BoomerangCurve::Straight => flight.heading,
+                    BoomerangCurve::Arc { degrees } => {
+                        Rot2::radians(degrees.to_radians() * (progress - 0.5)) * flight.heading
+                    }
+                }
+            }
+            BoomerangLeg::Return => {
+                let to_player = player_pos - position;
+                if to_player.length() < CATCH_DISTANCE {
+                    commands.entity(entity).despawn();
+                    continue;
+                }
+
+                speed_scale.0 = progress.sqrt().max(MIN_SPEED_SCALE);
+                let to_player = to_player.normalize();
+                match config.curve {
+                    BoomerangCurve::Straight => to_player,
+                    BoomerangCurve::Arc { degrees } => {
+                        Rot2::radians(degrees.to_radians() * 0.5 * (1.0 - progress)) * to_player
+                    }
+                }
+            }
+        };
+
+        direction.0 = heading.extend(0.0);
+    }
+}
@@ -5,73 +5,61 @@ use crate::{
     audio::SfxPool,
     gameplay::{
         enemy::Enemy,
-        player::Player,
         weapons::{
             behaviours::{
-                WeaponAttackSfx, WeaponProjectileVisuals,
+                Attack, WeaponAttackSfx, WeaponProjectileVisuals,
                 chain::{ChainAttack, ChainLifetime},
             },
             components::{BaseDamage, ProjectileCount, WeaponRange},
             spec::components::HitSpec,
             systems::{cooldown::WeaponDuration, hit::WeaponHitEvent},
+            targeting::{TargetingSpec, Targets},
         },
     },
 };
 
 pub fn on_chain_attack(
-    _chain_attack: On<ChainAttack>,
-    weapon_q: Single<
+    attack: On<Attack<ChainAttack>>,
+    weapon_q: Query<
         (
             Entity,
             &ProjectileCount,
             &WeaponRange,
             &ChainLifetime,
+            &TargetingSpec,
             &WeaponProjectileVisuals,
             Option<&WeaponAttackSfx>,
         ),
         With<ChainAttack>,
     >,
-    player_pos: Single<&Transform, (With<Player>, Without<Enemy>)>,
-    enemy_q: Query<(&Transform, Entity), (With<Enemy>, Without<Player>)>,
+    mut targets: Targets,
+    enemy_q: Query<(&Transform, Entity), With<Enemy>>,
     weapon_stats_q: Query<(&HitSpec, &BaseDamage)>,
     mut commands: Commands,
 ) -> Result {
-    let (entity, chain_count, chain_range, bolt_lifetime, projectile_visuals, sfx) =
-        weapon_q.into_inner();
+    let (entity, chain_count, chain_range, bolt_lifetime, targeting, projectile_visuals, sfx) =
+        weapon_q.get(attack.source.weapon)?;
+    targets.attack_from(attack.source.origin);
 
     if let Some(weapon_sfx) = sfx {
         commands.spawn((SamplePlayer::new(weapon_sfx.0.clone()), SfxPool));
     }
 
-    let mut current_source_pos = player_pos.into_inner();
+    // The first hop goes to the best target in range, later hops to the closest enemy
+    let origin = targets.origin();
+    let mut next = targets
+        .ranked_enemies(*targeting)
+        .into_iter()
+        .find(|(_, position)| position.distance(origin) < chain_range.0)
+        .and_then(|(enemy, _)| enemy_q.get(enemy).ok())
+        .map(|(enemy_pos, enemy)| (enemy, enemy_pos));
+
+    let mut current_source_pos = Transform::from_translation(origin.extend(0.0));
     let mut current_source_entity: Option<Entity> = None;
     let mut visited: HashSet<Entity> = HashSet::new();
 
     for _ in 0..chain_count.0 {
-        let mut max_distance = chain_range.0;
-        let mut closest: Option<(Entity, &Transform)> = None;
-
-        for (enemy_pos, enemy) in &enemy_q {
-            if Some(enemy) == current_source_entity {
-                continue;
-            }
-
-            if visited.contains(&enemy) {
-                continue;
-            }
-
-            let distance = current_source_pos
-                .translation
-                .truncate()
-                .distance(enemy_pos.translation.truncate());
-
-            if distance < max_distance {
-                max_distance = distance;
-                closest = Some((enemy, enemy_pos));
-            }
-        }
-
-        let Some((enemy, enemy_pos)) = closest else {
+        let Some((enemy, enemy_pos)) = next.take() else {
             break;
         };
 
@@ -104,8 +92,29 @@ pub fn on_chain_attack(
         });
 
         visited.insert(enemy);
-        current_source_pos = enemy_pos;
+        current_source_pos = *enemy_pos;
         current_source_entity = Some(enemy);
+
+        let mut max_distance = chain_range.0;
+        for (enemy_pos, enemy) in &enemy_q {
+            if Some(enemy) == current_source_entity {
+                continue;
+            }
+
+            if visited.contains(&enemy) {
+                continue;
+            }
+
+            let distance = current_source_pos
+                .translation
+                .truncate()
+                .distance(enemy_pos.translation.truncate());
+
+            if distance < max_distance {
+                max_distance = distance;
+                next = Some((enemy, enemy_pos));
+            }
+        }
     }
 
     Ok(())
@@ -2,7 +2,7 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::gameplay::weapons::{
-    behaviours::TriggerAttackBehavior,
+    behaviours::{Attack, AttackSource, TriggerAttackBehavior},
     components::{ProjectileCount, WeaponRange},
 };
 
@@ -12,7 +12,7 @@ pub(super) fn plugin(app: &mut App) {
     app.add_observer(attack::on_chain_attack);
 }
 
-#[derive(Component, Event)]
+#[derive(Component)]
 pub struct ChainAttack;
 
 #[derive(Component)]
@@ -38,7 +38,7 @@ impl EntityCommand for ChainSpec {
 }
 
 impl TriggerAttackBehavior for ChainSpec {
-    fn trigger(&self, mut commands: Commands) {
-        commands.trigger(ChainAttack);
+    fn trigger(&self, source: AttackSource, mut commands: Commands) {
+        commands.trigger(Attack::<ChainAttack>::new(source));
     }
 }
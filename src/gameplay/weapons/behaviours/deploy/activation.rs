@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    gameplay::{
+        enemy::Enemy,
+        weapons::{
+            behaviours::{
+                AttackSource, TriggerAttackBehavior,
+                deploy::{
+                    DeployConfig, DeployCooldown, DeployExplosion, DeployTrigger, DeployedBy,
+                },
+            },
+            components::BaseDamage,
+            spec::components::HitSpec,
+            systems::hit::WeaponHitEvent,
+        },
+    },
+    screens::Screen,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        (fire_deployables, detonate_deployables)
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+/// Run the attack of deployables on a cooldown from where they stand.
+fn fire_deployables(
+    mut deployed_q: Query<(&Transform, &DeployedBy, &mut DeployCooldown)>,
+    weapon_q: Query<&DeployConfig>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (transform, deployed_by, mut cooldown) in &mut deployed_q {
+        if !cooldown.0.tick(time.delta()).just_finished() {
+            continue;
+        }
+        let Ok(config) = weapon_q.get(deployed_by.0) else {
+            continue;
+        };
+
+        if let Some(attack) = &config.attack {
+            attack.trigger(
+                AttackSource {
+                    weapon: deployed_by.0,
+                    origin: Some(transform.translation.truncate()),
+                },
+                commands.reborrow(),
+            );
+        }
+    }
+}
+
+/// Set off deployables with a proximity trigger once an enemy comes close, then despawn them.
+fn detonate_deployables(
+    deployed_q: Query<(Entity, &Transform, &DeployedBy)>,
+    enemy_q: Query<(Entity, &Transform), With<Enemy>>,
+    weapon_q: Query<(
+        &DeployConfig,
+        &HitSpec,
+        &BaseDamage,
+        Option<&DeployExplosion>,
+    )>,
+    mut commands: Commands,
+) {
+    for (entity, transform, deployed_by) in &deployed_q {
+        let weapon = deployed_by.0;
+        let Ok((config, hit_spec, base_damage, explosion_radius)) = weapon_q.get(weapon) else {
+            continue;
+        };
+        let DeployTrigger::Proximity { radius } = config.trigger else {
+            continue;
+        };
+
+        let position = transform.translation.truncate();
+        let nearest = enemy_q
+            .iter()
+            .map(|(enemy, enemy_tf)| (enemy, enemy_tf.translation.truncate().distance(position)))
+            .filter(|&(_, distance)| distance <= radius)
+            .min_by(|(_, a), (_, b)| a.total_cmp(b));
+        let Some((enemy, _)) = nearest else {
+            continue;
+        };
+
+        if let Some(attack) = &config.attack {
+            attack.trigger(
+                AttackSource {
+                    weapon,
+                    origin: Some(position),
+                },
+                commands.reborrow(),
+            );
+        }
+
+        if let Some(explosion_radius) = explosion_radius {
+            commands.trigger(WeaponHitEvent {
+                entity: weapon,
+                target: enemy,
+                hit_pos: transform.translation,
+                dmg: base_damage.0,
+                damage_type: hit_spec.damage_type,
+                aoe: Some(explosion_radius.0),
+                effects: hit_spec.effects.clone(),
+            });
+        }
+
+        commands.entity(entity).despawn();
+    }
+}
@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+
+use crate::gameplay::weapons::{
+    behaviours::{
+        Attack, WeaponProjectileVisuals,
+        deploy::{
+            DeployAttack, DeployConfig, DeployCooldown, DeployPlacement, DeployTrigger, DeployedBy,
+            WeaponDeployables,
+        },
+    },
+    systems::cooldown::WeaponDuration,
+    targeting::{TargetingSpec, Targets},
+};
+
+pub fn on_deploy_attack(
+    attack: On<Attack<DeployAttack>>,
+    weapon_q: Query<
+        (
+            &DeployConfig,
+            &TargetingSpec,
+            &WeaponProjectileVisuals,
+            Option<&WeaponDeployables>,
+        ),
+        With<DeployAttack>,
+    >,
+    deployed_q: Query<&WeaponDuration, With<DeployedBy>>,
+    mut targets: Targets,
+    mut commands: Commands,
+) {
+    let weapon = attack.source.weapon;
+    let Ok((config, targeting, visuals, deployables)) = weapon_q.get(weapon) else {
+        return;
+    };
+    targets.attack_from(attack.source.origin);
+
+    let position = match config.placement {
+        DeployPlacement::Player => targets.origin(),
+        DeployPlacement::Target => targets.pick(*targeting).unwrap_or_else(|| targets.origin()),
+    };
+
+    // Make room for the new one by despawning the oldest
+    if let Some(deployables) = deployables {
+        let mut active: Vec<(Entity, f32)> = deployables
+            .iter()
+            .filter_map(|e| {
+                deployed_q
+                    .get(e)
+                    .ok()
+                    .map(|duration| (e, duration.0.elapsed_secs()))
+            })
+            .collect();
+        active.sort_by(|(_, a), (_, b)| b.total_cmp(a));
+
+        let excess = (active.len() + 1).saturating_sub(config.max_active as usize);
+        for &(oldest, _) in active.iter().take(excess) {
+            commands.entity(oldest).despawn();
+        }
+    }
+
+    let mut deployed = commands.spawn((
+        Name::new("Deployable"),
+        DeployedBy(weapon),
+        Transform::from_translation(position.extend(5.0)),
+        WeaponDuration(Timer::from_seconds(config.lifetime, TimerMode::Once)),
+    ));
+    if let DeployTrigger::Cooldown { seconds } = config.trigger {
+        deployed.insert(DeployCooldown(Timer::from_seconds(
+            seconds,
+            TimerMode::Repeating,
+        )));
+    }
+
+    visuals.0.apply_ec(&mut deployed);
+}
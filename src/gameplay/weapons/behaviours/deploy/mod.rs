@@ -0,0 +1,235 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::gameplay::weapons::{
+    behaviours::{Attack, AttackSource, TriggerAttackBehavior},
+    spec::components::{AttackSpec, ProjectilePolicy},
+};
+
+mod activation;
+mod attack;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(attack::on_deploy_attack);
+    app.add_plugins(activation::plugin);
+}
+
+#[derive(Component)]
+pub struct DeployAttack;
+
+/// Where a weapon deploys, how many it keeps and what its deployables do.
+#[derive(Component, Debug, Clone)]
+pub struct DeployConfig {
+    pub placement: DeployPlacement,
+    pub trigger: DeployTrigger,
+    pub attack: Option<AttackSpec>,
+    pub max_active: u32,
+    pub lifetime: f32,
+}
+
+/// Where a deployable is placed.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum DeployPlacement {
+    #[default]
+    Player,
+    /// The point picked by the weapon's targeting, the player if there is none.
+    Target,
+}
+
+/// When a deployable uses its attack.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub enum DeployTrigger {
+    /// Every `seconds`, like a turret.
+    Cooldown { seconds: f32 },
+    /// Once an enemy comes within `radius`, then it despawns, like a mine.
+    Proximity { radius: f32 },
+}
+
+/// The weapon that deployed this, which its hits count towards.
+#[derive(Component)]
+#[relationship(relationship_target = WeaponDeployables)]
+pub struct DeployedBy(pub Entity);
+
+#[derive(Component)]
+#[relationship_target(relationship = DeployedBy, linked_spawn)]
+pub struct WeaponDeployables(Vec<Entity>);
+
+/// The radius a weapon's deployables explode with when triggered. Separate from the
+/// [`ExplosionRadius`](crate::gameplay::weapons::components::ExplosionRadius) of their attack.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct DeployExplosion(pub f32);
+
+/// Time until a deployable with [`DeployTrigger::Cooldown`] attacks again.
+#[derive(Component)]
+pub struct DeployCooldown(pub Timer);
+
+/// A stationary turret, mine or totem running its own `attack`.
+///
+/// The attack is applied to the weapon itself, so its projectiles and hits belong to the weapon
+/// and use its damage, visuals and targeting. They start at the deployable, beams fire from it.
+/// Attacks that stay with the player, like melee cones and orbiters, don't move with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeploySpec {
+    #[serde(default)]
+    pub placement: DeployPlacement,
+    pub trigger: DeployTrigger,
+    #[serde(default)]
+    pub attack: Option<Box<AttackSpec>>,
+    /// Damage every enemy within this radius when triggered, mostly for mines.
+    #[serde(default)]
+    pub explosion_radius: Option<f32>,
+    /// Deploying more despawns the oldest.
+    #[serde(default = "default_max_active")]
+    pub max_active: u32,
+    /// Seconds until a deployable despawns.
+    pub lifetime: f32,
+}
+
+fn default_max_active() -> u32 {
+    3
+}
+
+impl DeploySpec {
+    /// The nested attack, unless it deploys again.
+    fn nested_attack(&self) -> Option<&AttackSpec> {
+        self.attack
+            .as_deref()
+            .filter(|attack| !matches!(attack, AttackSpec::Deploy(_)))
+    }
+
+    /// The [`ProjectilePolicy`] of the nested attack, if it fires projectiles.
+    pub fn projectile_policy_mut(&mut self) -> Option<&mut ProjectilePolicy> {
+        self.attack
+            .as_deref_mut()
+            .and_then(AttackSpec::projectile_policy_mut)
+    }
+}
+
+impl EntityCommand for DeploySpec {
+    fn apply(self, mut entity: EntityWorldMut) {
+        if matches!(self.attack.as_deref(), Some(AttackSpec::Deploy(_))) {
+            warn!("Deployables can't deploy, ignoring the nested `Deploy` attack");
+        }
+        let attack = self.nested_attack().cloned();
+
+        entity.insert((
+            DeployAttack,
+            DeployConfig {
+                placement: self.placement,
+                trigger: self.trigger,
+                attack: attack.clone(),
+                max_active: self.max_active.max(1),
+                lifetime: self.lifetime,
+            },
+        ));
+
+        if let Some(radius) = self.explosion_radius {
+            entity.insert(DeployExplosion(radius));
+        }
+
+        if let Some(attack) = attack {
+            attack.apply(entity);
+        }
+    }
+}
+
+impl TriggerAttackBehavior for DeploySpec {
+    fn trigger(&self, source: AttackSource, mut commands: Commands) {
+        commands.trigger(Attack::<DeployAttack>::new(source));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        gameplay::weapons::{kind::WeaponKind, spec::loader::test_spec},
+        sim,
+    };
+
+    fn deployables(app: &mut App) -> Vec<Entity> {
+        app.world_mut()
+            .query_filtered::<Entity, With<DeployedBy>>()
+            .iter(app.world())
+            .collect()
+    }
+
+    #[test]
+    fn deploying_more_than_max_active_despawns_the_oldest() {
+        let mut app = sim::weapon_test_app([test_spec(
+            WeaponKind::Hammer,
+            "Deploy((trigger: Cooldown(seconds: 100.0), max_active: 2, lifetime: 100.0))",
+        )]);
+
+        sim::attack(&mut app);
+        sim::advance(&mut app, 0.1);
+        let oldest = deployables(&mut app);
+        assert_eq!(oldest.len(), 1);
+
+        sim::attack(&mut app);
+        sim::advance(&mut app, 0.1);
+        assert_eq!(deployables(&mut app).len(), 2);
+
+        sim::attack(&mut app);
+        sim::advance(&mut app, 0.1);
+        let active = deployables(&mut app);
+        assert_eq!(active.len(), 2);
+        assert!(!active.contains(&oldest[0]));
+    }
+
+    #[test]
+    fn deploy_aims_and_flies_like_its_attack() {
+        // The turret fires once, at the walkers above rather than where the player faces.
+        let turret_hits = |projectile: &str| {
+            let turret = format!(
+                "Deploy((trigger: Cooldown(seconds: 0.2), lifetime: 0.3, \
+                 attack: Shot((speed: 300.0, range: 400.0, explosion_radius: None{projectile}))))"
+            );
+            let mut app = sim::weapon_test_app([test_spec(WeaponKind::Hammer, &turret)]);
+            sim::spawn_walker(&mut app, Vec2::new(0.0, 100.0));
+            sim::spawn_walker(&mut app, Vec2::new(0.0, 150.0));
+
+            sim::attack(&mut app);
+            sim::advance(&mut app, 1.0);
+            sim::hits(&app, WeaponKind::Hammer)
+        };
+
+        assert_eq!(turret_hits(""), 2);
+        assert_eq!(turret_hits(", projectile: (pierce: 0)"), 1);
+    }
+
+    #[test]
+    fn mines_explode_at_their_target() {
+        let mut app = sim::weapon_test_app([test_spec(
+            WeaponKind::Hammer,
+            "Deploy((placement: Target, trigger: Proximity(radius: 24.0), \
+             explosion_radius: 64.0, lifetime: 20.0))",
+        )]);
+        sim::spawn_walker(&mut app, Vec2::new(0.0, 100.0));
+
+        sim::attack(&mut app);
+        sim::advance(&mut app, 0.1);
+        assert_eq!(sim::hits(&app, WeaponKind::Hammer), 1);
+        assert!(deployables(&mut app).is_empty());
+    }
+
+    #[test]
+    fn mines_explode_apart_from_their_attack() {
+        // The mine goes off at the walker above and shoots it. Only the mine explodes, and the
+        // walker beside is out of its reach.
+        let mut app = sim::weapon_test_app([test_spec(
+            WeaponKind::Hammer,
+            "Deploy((trigger: Proximity(radius: 120.0), explosion_radius: 80.0, lifetime: 20.0, \
+             attack: Shot((speed: 300.0, range: 400.0, explosion_radius: None))))",
+        )]);
+        sim::spawn_walker(&mut app, Vec2::new(0.0, 100.0));
+        sim::spawn_walker(&mut app, Vec2::new(50.0, 100.0));
+        sim::root_enemies(&mut app);
+
+        sim::attack(&mut app);
+        sim::advance(&mut app, 1.0);
+        assert_eq!(sim::hits(&app, WeaponKind::Hammer), 2);
+    }
+}
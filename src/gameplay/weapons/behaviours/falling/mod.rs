@@ -2,8 +2,9 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::gameplay::weapons::{
-    behaviours::TriggerAttackBehavior,
+    behaviours::{Attack, AttackSource, TriggerAttackBehavior},
     components::{ExplosionRadius, ProjectileSpeed},
+    spec::components::ProjectilePolicy,
 };
 
 mod attack;
@@ -12,7 +13,7 @@ pub(super) fn plugin(app: &mut App) {
     app.add_observer(attack::on_falling_attack);
 }
 
-#[derive(Component, Event)]
+#[derive(Component)]
 pub struct FallingAttack;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +22,8 @@ pub struct FallingSpec {
     pub spawn_height: f32,
     pub fall_speed: f32,
     pub explosion_radius: Option<f32>,
+    #[serde(default)]
+    pub projectile: ProjectilePolicy,
 }
 
 impl EntityCommand for FallingSpec {
@@ -29,6 +32,7 @@ impl EntityCommand for FallingSpec {
             FallingAttack,
             SpawnHeight(self.spawn_height),
             ProjectileSpeed(self.fall_speed),
+            self.projectile,
         ));
 
         if let Some(radius) = self.explosion_radius {
@@ -38,8 +42,8 @@ impl EntityCommand for FallingSpec {
 }
 
 impl TriggerAttackBehavior for FallingSpec {
-    fn trigger(&self, mut commands: Commands) {
-        commands.trigger(FallingAttack);
+    fn trigger(&self, source: AttackSource, mut commands: Commands) {
+        commands.trigger(Attack::<FallingAttack>::new(source));
     }
 }
 
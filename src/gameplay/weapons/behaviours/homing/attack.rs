@@ -1,65 +1,48 @@
 use crate::{
     GameLayer,
-    gameplay::{
-        enemy::Enemy,
-        player::Player,
-        weapons::{
-            behaviours::{
-                WeaponProjectileVisuals,
-                homing::{
-                    CurrentTarget, HitCounter, HomingAttack, HomingProjectile, MaxHits,
-                    MovementConfig,
-                },
-            },
-            components::{CastWeapon, ProjectileCount, ProjectileDirection, WeaponLifetime},
-            systems::cooldown::WeaponDuration,
+    gameplay::weapons::{
+        behaviours::{
+            Attack, WeaponProjectileVisuals,
+            homing::{CurrentTarget, HomingAttack, HomingProjectile, MovementConfig},
         },
+        components::{CastWeapon, ProjectileCount, ProjectileDirection},
+        targeting::{TargetingSpec, Targets},
     },
 };
 
 use avian2d::prelude::*;
 use bevy::prelude::*;
-use rand::Rng;
 
 pub fn on_homing_attack(
-    _homing_attack: On<HomingAttack>,
-    weapon: Single<
+    attack: On<Attack<HomingAttack>>,
+    weapon_q: Query<
         (
             Entity,
             &ProjectileCount,
-            &WeaponLifetime,
-            &MaxHits,
             &MovementConfig,
+            &TargetingSpec,
             &WeaponProjectileVisuals,
         ),
         With<HomingAttack>,
     >,
-    player_pos: Single<&Transform, With<Player>>,
-    enemy_q: Query<Entity, With<Enemy>>,
+    mut targets: Targets,
     mut commands: Commands,
 ) -> Result {
-    let (entity, count, lifetime, max_hits, movement_config, projectile_visuals) =
-        weapon.into_inner();
+    let (entity, count, movement_config, targeting, projectile_visuals) =
+        weapon_q.get(attack.source.weapon)?;
+    targets.attack_from(attack.source.origin);
 
-    let enemy_count = enemy_q.iter().len();
-    let mut rng = rand::rng();
+    let origin = targets.origin();
+    // Spread the projectiles over the best targets
+    let ranked = targets.ranked_enemies(*targeting);
 
     for i in 0..count.0 {
-        let initial_target = if enemy_count > 0 {
-            let target_idx = if enemy_count == 1 {
-                0
-            } else {
-                (i as usize + rng.random_range(0..enemy_count)) % enemy_count
-            };
-            enemy_q.iter().nth(target_idx)
-        } else {
-            None
-        };
+        let initial_target = (!ranked.is_empty()).then(|| ranked[i as usize % ranked.len()].0);
 
         let mut proj = commands.spawn((
             Name::new("Homing Projectile"),
             CastWeapon(entity),
-            Transform::from_xyz(player_pos.translation.x, player_pos.translation.y, 10.0),
+            Transform::from_xyz(origin.x, origin.y, 10.0),
             ProjectileDirection(Vec3::ZERO), // Will be updated by movement system
             RigidBody::Kinematic,
             Collider::rectangle(32.0, 32.0),
@@ -69,36 +52,10 @@ pub fn on_homing_attack(
             LinearVelocity(Vec2::ZERO),
             HomingProjectile,
             CurrentTarget(initial_target),
-            HitCounter {
-                hits: 0,
-                max_hits: max_hits.0 as usize,
-            },
             movement_config.clone(),
-            WeaponDuration(Timer::from_seconds(lifetime.0, TimerMode::Once)),
         ));
 
         projectile_visuals.0.apply_ec(&mut proj);
-
-        proj.observe(on_homing_hit_counter);
-    }
-
-    Ok(())
-}
-
-// Separate observer to track hit count for homing projectiles
-fn on_homing_hit_counter(
-    event: On<avian2d::prelude::CollisionStart>,
-    mut hit_counter_q: Query<&mut super::HitCounter>,
-    mut commands: Commands,
-) -> Result {
-    let projectile = event.collider1;
-
-    if let Ok(mut counter) = hit_counter_q.get_mut(projectile) {
-        counter.hits += 1;
-
-        if counter.hits >= counter.max_hits {
-            commands.entity(projectile).despawn();
-        }
     }
 
     Ok(())
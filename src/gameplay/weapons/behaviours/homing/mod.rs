@@ -2,8 +2,9 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::gameplay::weapons::{
-    behaviours::TriggerAttackBehavior,
-    components::{ProjectileCount, ProjectileSpeed, WeaponLifetime},
+    behaviours::{Attack, AttackSource, TriggerAttackBehavior},
+    components::{ProjectileCount, ProjectileSpeed},
+    spec::components::ProjectilePolicy,
 };
 
 mod attack;
@@ -14,7 +15,7 @@ pub(super) fn plugin(app: &mut App) {
     app.add_plugins(movement::plugin);
 }
 
-#[derive(Component, Event)]
+#[derive(Component)]
 pub struct HomingAttack;
 
 #[derive(Component, Reflect)]
@@ -23,15 +24,6 @@ pub struct HomingProjectile;
 #[derive(Component, Reflect)]
 pub struct CurrentTarget(pub Option<Entity>);
 
-#[derive(Component, Reflect)]
-pub struct HitCounter {
-    pub hits: usize,
-    pub max_hits: usize,
-}
-
-#[derive(Component, Reflect)]
-pub struct MaxHits(pub u32);
-
 #[derive(Component, Clone, Reflect)]
 pub struct MovementConfig {
     pub pattern: MovementPatternKind,
@@ -41,29 +33,42 @@ pub struct MovementConfig {
 #[serde(deny_unknown_fields)]
 pub struct HomingSpec {
     pub count: u32,
-    pub lifetime: f32,
-    pub max_hits: u32,
+    /// Older specs set how long a missile flies with this instead of `projectile.lifetime`.
+    #[serde(default)]
+    pub lifetime: Option<f32>,
+    /// Older specs limit the enemies hit with this instead of `projectile.pierce`.
+    #[serde(default)]
+    pub max_hits: Option<u32>,
     pub movement: MovementPattern,
+    #[serde(default)]
+    pub projectile: ProjectilePolicy,
 }
 
 impl EntityCommand for HomingSpec {
     fn apply(self, mut entity: EntityWorldMut) {
+        let mut projectile = self.projectile;
+        if let Some(lifetime) = self.lifetime {
+            projectile.lifetime = lifetime;
+        }
+        if let Some(max_hits) = self.max_hits {
+            projectile.pierce = max_hits.saturating_sub(1);
+        }
+
         entity.insert((
             HomingAttack,
             ProjectileCount(self.count),
             ProjectileSpeed(self.movement.speed),
-            WeaponLifetime(self.lifetime),
-            MaxHits(self.max_hits),
             MovementConfig {
                 pattern: self.movement.kind,
             },
+            projectile,
         ));
     }
 }
 
 impl TriggerAttackBehavior for HomingSpec {
-    fn trigger(&self, mut commands: Commands) {
-        commands.trigger(HomingAttack);
+    fn trigger(&self, source: AttackSource, mut commands: Commands) {
+        commands.trigger(Attack::<HomingAttack>::new(source));
     }
 }
 
@@ -7,7 +7,7 @@ use crate::{
         enemy::Enemy,
         weapons::{
             behaviours::homing::{CurrentTarget, MovementConfig},
-            components::{ProjectileDirection, ProjectileSpeed, WeaponProjectiles},
+            components::{ProjectileDirection, ProjectileHits, ProjectileSpeed, WeaponProjectiles},
         },
     },
     screens::Screen,
@@ -35,17 +35,34 @@ struct SpiralState {
 }
 
 fn retarget_homing(
-    mut projectiles: Query<(&Transform, &mut super::CurrentTarget), With<super::HomingProjectile>>,
+    mut projectiles: Query<
+        (
+            &Transform,
+            &mut super::CurrentTarget,
+            Option<&ProjectileHits>,
+        ),
+        With<super::HomingProjectile>,
+    >,
     enemies: Query<(Entity, &Transform), With<Enemy>>,
 ) {
-    for (proj_transform, mut current_target) in &mut projectiles {
-        let target_valid = current_target.0.and_then(|t| enemies.get(t).ok()).is_some();
+    for (proj_transform, mut current_target, hits) in &mut projectiles {
+        // Enemies already hit are never hit again, so chasing them is pointless.
+        let unhit = |enemy: &Entity| hits.is_none_or(|hits| !hits.hit.contains(enemy));
+        let target_valid = current_target
+            .0
+            .filter(unhit)
+            .and_then(|t| enemies.get(t).ok())
+            .is_some();
 
         if !target_valid {
             let mut min_distance = f32::MAX;
             let mut closest: Option<Entity> = None;
 
             for (enemy, enemy_transform) in &enemies {
+                if !unhit(&enemy) {
+                    continue;
+                }
+
                 let distance = proj_transform
                     .translation
                     .distance(enemy_transform.translation);
@@ -1,15 +1,15 @@
 use crate::{
     GameLayer,
     gameplay::{
-        player::{Player, PlayerFacing},
         simple_animation::{AnimationIndices, AnimationPlayback, AnimationTimer},
         weapons::{
             behaviours::{
-                WeaponProjectileVisuals,
+                Attack, WeaponProjectileVisuals,
                 melee::{AttackCone, MeleeAttack, MeleeAttackZone},
             },
             components::CastWeapon,
             systems::cooldown::WeaponDuration,
+            targeting::{TargetingSpec, Targets},
         },
     },
 };
@@ -19,27 +19,33 @@ use bevy::prelude::*;
 const DEFAUL_DURATION: f32 = 0.5;
 
 pub fn on_melee_attack(
-    _melee_attack: On<MeleeAttack>,
-    weapon: Single<(Entity, &AttackCone, &WeaponProjectileVisuals), With<MeleeAttack>>,
-    player: Single<(&Transform, &PlayerFacing), With<Player>>,
+    attack: On<Attack<MeleeAttack>>,
+    weapon_q: Query<
+        (
+            Entity,
+            &AttackCone,
+            &TargetingSpec,
+            &WeaponProjectileVisuals,
+        ),
+        With<MeleeAttack>,
+    >,
+    mut targets: Targets,
     mut commands: Commands,
 ) {
-    let (entity, cone, visuals) = weapon.into_inner();
-
-    let (player_pos, facing) = player.into_inner();
-
-    let facing_right = facing.is_right();
-    let dir_x = if facing_right { 1.0 } else { -1.0 };
+    let Ok((entity, cone, targeting, visuals)) = weapon_q.get(attack.source.weapon) else {
+        return;
+    };
+    targets.attack_from(attack.source.origin);
 
-    let angle = if facing_right {
-        0.0
-    } else {
-        std::f32::consts::PI
+    let Some(direction) = targets.aimed_direction(*targeting) else {
+        return;
     };
+    let origin = targets.origin();
 
+    // The cone points along +x and the zone is rotated towards the target
     let half_angle = cone.angle.to_radians() / 2.0;
-    let left_direction = Vec2::from_angle(angle + half_angle);
-    let right_direction = Vec2::from_angle(angle - half_angle);
+    let left_direction = Vec2::from_angle(half_angle);
+    let right_direction = Vec2::from_angle(-half_angle);
 
     let apex = Vec2::ZERO;
     let left_point = left_direction * cone.range;
@@ -59,7 +65,8 @@ pub fn on_melee_attack(
             CollisionEventsEnabled,
             CollisionLayers::new(GameLayer::Player, [GameLayer::Enemy, GameLayer::Default]),
             DebugRender::default().with_collider_color(Color::srgb(0.0, 1.0, 0.0)),
-            Transform::from_xyz(player_pos.translation.x, player_pos.translation.y, 10.0),
+            Transform::from_xyz(origin.x, origin.y, 10.0)
+                .with_rotation(Quat::from_rotation_z(direction.to_angle())),
             WeaponDuration(Timer::from_seconds(duration_secs, TimerMode::Once)),
             Visibility::default(),
         ))
@@ -70,12 +77,13 @@ pub fn on_melee_attack(
 
     commands.entity(proj).with_children(|c| {
         let mut sprite = visuals.0.get_sprite();
-        sprite.flip_x = !facing_right;
+        // Keep the sprite upright when swinging to the left
+        sprite.flip_y = direction.x < 0.0;
 
         let mut child = c.spawn((
             Name::new("MeleeAttackConeVisual"),
             sprite,
-            Transform::from_xyz(dir_x * sprite_offset_x, 0.0, 0.0),
+            Transform::from_xyz(sprite_offset_x, 0.0, 0.0),
         ));
 
         if let Some(atlas) = visuals.0.atlas.as_ref() {
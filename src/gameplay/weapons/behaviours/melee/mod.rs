@@ -1,7 +1,7 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::gameplay::weapons::behaviours::TriggerAttackBehavior;
+use crate::gameplay::weapons::behaviours::{Attack, AttackSource, TriggerAttackBehavior};
 
 mod attack;
 mod movement;
@@ -11,7 +11,7 @@ pub(super) fn plugin(app: &mut App) {
     app.add_plugins(movement::plugin);
 }
 
-#[derive(Component, Event)]
+#[derive(Component)]
 pub struct MeleeAttack;
 
 #[derive(Component)]
@@ -38,7 +38,7 @@ impl EntityCommand for MeleeSpec {
 }
 
 impl TriggerAttackBehavior for MeleeSpec {
-    fn trigger(&self, mut commands: Commands) {
-        commands.trigger(MeleeAttack);
+    fn trigger(&self, source: AttackSource, mut commands: Commands) {
+        commands.trigger(Attack::<MeleeAttack>::new(source));
     }
 }
@@ -1,15 +1,21 @@
+use std::marker::PhantomData;
+
 use bevy::prelude::*;
 use bevy_seedling::sample::AudioSample;
 
 use crate::gameplay::weapons::spec::components::VisualSpec;
 
+pub mod beam;
+pub mod boomerang;
 pub mod chain;
+pub mod deploy;
 pub mod falling;
 pub mod homing;
 pub mod melee;
 pub mod nova;
 pub mod orbiters;
 pub mod shot;
+pub mod trail;
 pub mod zone;
 
 pub(crate) fn plugin(app: &mut App) {
@@ -22,6 +28,10 @@ pub(crate) fn plugin(app: &mut App) {
         falling::plugin,
         melee::plugin,
         zone::plugin,
+        beam::plugin,
+        boomerang::plugin,
+        deploy::plugin,
+        trail::plugin,
     ));
 }
 
@@ -37,6 +47,40 @@ pub struct WeaponAttackSfx(pub Handle<AudioSample>);
 #[derive(Component, Clone)]
 pub struct WeaponImpactSfx(pub Handle<AudioSample>);
 
+/// The weapon an attack is for and where it starts.
+#[derive(Debug, Clone, Copy)]
+pub struct AttackSource {
+    pub weapon: Entity,
+    /// Where the attack starts instead of the player, e.g. a deployed turret.
+    pub origin: Option<Vec2>,
+}
+
+impl AttackSource {
+    /// An attack by `weapon` from the player.
+    pub fn player(weapon: Entity) -> Self {
+        Self {
+            weapon,
+            origin: None,
+        }
+    }
+}
+
+/// Fires the attack of a weapon with the behaviour marked by `M`, e.g. [`shot::ShotAttack`].
+#[derive(Event)]
+pub struct Attack<M: Component> {
+    pub source: AttackSource,
+    marker: PhantomData<M>,
+}
+
+impl<M: Component> Attack<M> {
+    pub fn new(source: AttackSource) -> Self {
+        Self {
+            source,
+            marker: PhantomData,
+        }
+    }
+}
+
 pub trait TriggerAttackBehavior {
-    fn trigger(&self, commands: Commands);
+    fn trigger(&self, source: AttackSource, commands: Commands);
 }
@@ -1,46 +1,53 @@
 use bevy::prelude::*;
-use bevy_rand::{global::GlobalRng, prelude::WyRand};
 use rand::Rng;
 
-use crate::gameplay::{
-    player::Player,
-    weapons::{
-        behaviours::{
-            WeaponAttackSfx, WeaponProjectileVisuals,
-            nova::{NovaAttack, SpreadPattern},
-        },
-        components::{
-            CastWeapon, PlayerProjectile, ProjectileCount, ProjectileDirection, ProjectileSpeed,
-        },
+use crate::gameplay::weapons::{
+    behaviours::{
+        Attack, WeaponAttackSfx, WeaponProjectileVisuals,
+        nova::{NovaAttack, SpreadPattern},
     },
+    components::{
+        CastWeapon, PlayerProjectile, ProjectileCount, ProjectileDirection, ProjectileSpeed,
+    },
+    targeting::{TargetingSpec, Targets},
 };
 
 pub fn on_nova_attack(
-    _nova_attack: On<NovaAttack>,
-    weapon: Single<
+    attack: On<Attack<NovaAttack>>,
+    weapon_q: Query<
         (
             Entity,
             &ProjectileCount,
             &ProjectileSpeed,
             &SpreadPattern,
+            &TargetingSpec,
             &WeaponProjectileVisuals,
             Option<&WeaponAttackSfx>,
         ),
         With<NovaAttack>,
     >,
-    player_pos: Single<&Transform, With<Player>>,
+    mut targets: Targets,
     mut commands: Commands,
-    mut rng: Single<&mut WyRand, With<GlobalRng>>,
 ) -> Result {
-    let (entity, count, _speed, spread_pattern, projectile_visuals, _sfx) = weapon.into_inner();
+    let (entity, count, _speed, spread_pattern, targeting, projectile_visuals, _sfx) =
+        weapon_q.get(attack.source.weapon)?;
+    targets.attack_from(attack.source.origin);
+
+    let origin = targets.origin();
+    // The first projectile of an even spread flies at the target
+    let target_angle = targets
+        .aimed_direction(*targeting)
+        .map_or(0.0, |direction| direction.to_angle());
 
     let num_projectiles = count.0.max(1);
     let angle_step = std::f32::consts::TAU / num_projectiles as f32;
 
     for i in 0..num_projectiles {
         let angle = match spread_pattern.0 {
-            super::SpreadPatternKind::Even => angle_step * i as f32,
-            super::SpreadPatternKind::Random => rng.random_range(0.0..std::f32::consts::TAU),
+            super::SpreadPatternKind::Even => target_angle + angle_step * i as f32,
+            super::SpreadPatternKind::Random => {
+                targets.rng().random_range(0.0..std::f32::consts::TAU)
+            }
         };
 
         let direction = Vec2::new(angle.cos(), angle.sin());
@@ -49,8 +56,7 @@ pub fn on_nova_attack(
         let mut proj = commands.spawn((
             Name::new("Nova Projectile"),
             CastWeapon(entity),
-            Transform::from_xyz(player_pos.translation.x, player_pos.translation.y, 10.0)
-                .with_rotation(rotation),
+            Transform::from_xyz(origin.x, origin.y, 10.0).with_rotation(rotation),
             ProjectileDirection(direction.extend(0.0)),
             PlayerProjectile,
         ));
@@ -2,8 +2,9 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::gameplay::weapons::{
-    behaviours::TriggerAttackBehavior,
+    behaviours::{Attack, AttackSource, TriggerAttackBehavior},
     components::{ProjectileCount, ProjectileSpeed},
+    spec::components::ProjectilePolicy,
 };
 
 mod attack;
@@ -12,7 +13,7 @@ pub(super) fn plugin(app: &mut App) {
     app.add_observer(attack::on_nova_attack);
 }
 
-#[derive(Component, Event)]
+#[derive(Component)]
 pub struct NovaAttack;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +22,8 @@ pub struct NovaSpec {
     pub speed: f32,
     pub projectile_count: u32,
     pub spread_pattern: SpreadPatternKind,
+    #[serde(default)]
+    pub projectile: ProjectilePolicy,
 }
 
 impl EntityCommand for NovaSpec {
@@ -30,13 +33,14 @@ impl EntityCommand for NovaSpec {
             ProjectileCount(self.projectile_count),
             ProjectileSpeed(self.speed),
             SpreadPattern(self.spread_pattern),
+            self.projectile,
         ));
     }
 }
 
 impl TriggerAttackBehavior for NovaSpec {
-    fn trigger(&self, mut commands: Commands) {
-        commands.trigger(NovaAttack);
+    fn trigger(&self, source: AttackSource, mut commands: Commands) {
+        commands.trigger(Attack::<NovaAttack>::new(source));
     }
 }
 
@@ -1,48 +1,53 @@
 use crate::{
     GameLayer,
-    gameplay::{
-        player::Player,
-        weapons::{
-            behaviours::{
-                WeaponProjectileVisuals,
-                orbiters::{
-                    OrbitAngularSpeed, OrbitPhase, OrbitRadius, OrbiterProjectile, OrbitersAttack,
-                },
+    gameplay::weapons::{
+        behaviours::{
+            Attack, WeaponProjectileVisuals,
+            orbiters::{
+                OrbitAngularSpeed, OrbitPhase, OrbitRadius, OrbiterProjectile, OrbitersAttack,
             },
-            components::{CastWeapon, PlayerProjectile, ProjectileCount, WeaponLifetime},
-            systems::cooldown::WeaponDuration,
         },
+        components::{CastWeapon, PlayerProjectile, ProjectileCount, WeaponLifetime},
+        systems::cooldown::WeaponDuration,
+        targeting::{TargetingSpec, Targets},
     },
 };
 use avian2d::prelude::*;
 use bevy::prelude::*;
 
 pub fn on_orbiters_attack(
-    _orbiters_attack: On<OrbitersAttack>,
-    weapon: Single<
+    attack: On<Attack<OrbitersAttack>>,
+    weapon_q: Query<
         (
             Entity,
             &ProjectileCount,
             &OrbitRadius,
             &OrbitAngularSpeed,
             &WeaponLifetime,
+            &TargetingSpec,
             &WeaponProjectileVisuals,
         ),
         With<OrbitersAttack>,
     >,
-    player_q: Query<&Transform, With<Player>>,
+    mut targets: Targets,
     mut commands: Commands,
 ) -> Result {
-    let (entity, count, radius, ang_speed, lifetime, projectile_visuals) = weapon.into_inner();
+    let (entity, count, radius, ang_speed, lifetime, targeting, projectile_visuals) =
+        weapon_q.get(attack.source.weapon)?;
+    targets.attack_from(attack.source.origin);
 
-    let player_tf = player_q.single()?;
+    let origin = targets.origin();
+    // The first orbiter starts at the target
+    let start_phase = targets
+        .direction(*targeting)
+        .map_or(0.0, |direction| direction.to_angle());
 
     let count_f = count.0.max(1) as f32;
 
     for i in 0..(count.0.max(1) as usize) {
-        let phase = std::f32::consts::TAU * (i as f32 / count_f);
+        let phase = start_phase + std::f32::consts::TAU * (i as f32 / count_f);
         let offset = Vec2::from_angle(phase) * radius.0;
-        let world_pos = player_tf.translation + offset.extend(10.0);
+        let world_pos = origin + offset;
 
         let mut e = commands.spawn((
             Name::new("Orbiter"),
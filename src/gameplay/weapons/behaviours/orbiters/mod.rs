@@ -2,7 +2,7 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::gameplay::weapons::{
-    behaviours::TriggerAttackBehavior,
+    behaviours::{Attack, AttackSource, TriggerAttackBehavior},
     components::{ProjectileCount, WeaponLifetime},
 };
 
@@ -14,7 +14,7 @@ pub(super) fn plugin(app: &mut App) {
     app.add_plugins(movement::plugin);
 }
 
-#[derive(Component, Event)]
+#[derive(Component)]
 pub struct OrbitersAttack;
 #[derive(Component)]
 pub struct OrbitRadius(pub f32);
@@ -50,7 +50,7 @@ impl EntityCommand for OrbitersSpec {
 }
 
 impl TriggerAttackBehavior for OrbitersSpec {
-    fn trigger(&self, mut commands: Commands) {
-        commands.trigger(OrbitersAttack);
+    fn trigger(&self, source: AttackSource, mut commands: Commands) {
+        commands.trigger(Attack::<OrbitersAttack>::new(source));
     }
 }
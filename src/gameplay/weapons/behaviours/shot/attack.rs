@@ -1,50 +1,30 @@
-use crate::gameplay::{
-    enemy::Enemy,
-    player::Player,
-    weapons::{
-        behaviours::{WeaponProjectileVisuals, shot::ShotAttack},
-        components::{CastWeapon, PlayerProjectile, ProjectileDirection},
-    },
+use crate::gameplay::weapons::{
+    behaviours::{Attack, WeaponProjectileVisuals, shot::ShotAttack},
+    components::{CastWeapon, PlayerProjectile, ProjectileDirection},
+    targeting::{TargetingSpec, Targets},
 };
 use avian2d::prelude::*;
 use bevy::prelude::*;
 
 pub fn on_projectile_attack(
-    _shot_attack: On<ShotAttack>,
+    attack: On<Attack<ShotAttack>>,
     mut commands: Commands,
-    weapon: Single<(Entity, &WeaponProjectileVisuals), With<ShotAttack>>,
-    player_pos: Single<&Transform, With<Player>>,
-    enemy_positions: Query<&Transform, With<Enemy>>,
+    weapon_q: Query<(Entity, &TargetingSpec, &WeaponProjectileVisuals), With<ShotAttack>>,
+    mut targets: Targets,
 ) {
-    let (weapon, projectile_visuals) = weapon.into_inner();
-
-    let mut min_distance = f32::MAX;
-    let mut closest_enemy: Option<&Transform> = None;
-
-    for enemy_pos in &enemy_positions {
-        let distance = player_pos
-            .translation
-            .truncate()
-            .distance(enemy_pos.translation.truncate());
-
-        if distance < min_distance {
-            min_distance = distance;
-            closest_enemy = Some(enemy_pos);
-        }
-    }
-
-    if let Some(enemy_pos) = closest_enemy {
-        let direction = (enemy_pos.translation - player_pos.translation)
-            .truncate()
-            .normalize();
+    let Ok((weapon, targeting, projectile_visuals)) = weapon_q.get(attack.source.weapon) else {
+        return;
+    };
+    targets.attack_from(attack.source.origin);
 
+    if let Some(direction) = targets.aimed_direction(*targeting) {
+        let origin = targets.origin();
         let towards_quaternion = Quat::from_rotation_arc(Vec3::Y, direction.extend(0.).normalize());
 
         let mut proj = commands.spawn((
             Name::new("Shot Projectile"),
             CastWeapon(weapon),
-            Transform::from_xyz(player_pos.translation.x, player_pos.translation.y, 10.0)
-                .with_rotation(towards_quaternion),
+            Transform::from_xyz(origin.x, origin.y, 10.0).with_rotation(towards_quaternion),
             ProjectileDirection(direction.extend(0.)),
             Mass(0.1),
             PlayerProjectile,
@@ -2,8 +2,9 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
 use crate::gameplay::weapons::{
-    behaviours::TriggerAttackBehavior,
+    behaviours::{Attack, AttackSource, TriggerAttackBehavior},
     components::{ExplosionRadius, ProjectileSpeed},
+    spec::components::ProjectilePolicy,
 };
 
 mod attack;
@@ -12,20 +13,28 @@ pub(super) fn plugin(app: &mut App) {
     app.add_observer(attack::on_projectile_attack);
 }
 
-#[derive(Component, Event)]
+#[derive(Component)]
 pub struct ShotAttack;
 
 #[derive(Component, Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ShotSpec {
     pub speed: f32,
-    pub range: f32,
+    /// Older specs set how far a shot flies with this instead of `projectile.range`.
+    #[serde(default)]
+    pub range: Option<f32>,
     pub explosion_radius: Option<f32>,
+    #[serde(default)]
+    pub projectile: ProjectilePolicy,
 }
 
 impl EntityCommand for ShotSpec {
     fn apply(self, mut entity: EntityWorldMut) {
-        entity.insert((ShotAttack, ProjectileSpeed(self.speed)));
+        let mut projectile = self.projectile;
+        if let Some(range) = self.range {
+            projectile.range = Some(range);
+        }
+        entity.insert((ShotAttack, ProjectileSpeed(self.speed), projectile));
 
         if let Some(radius) = self.explosion_radius {
             entity.insert(ExplosionRadius(radius));
@@ -34,7 +43,7 @@ impl EntityCommand for ShotSpec {
 }
 
 impl TriggerAttackBehavior for ShotSpec {
-    fn trigger(&self, mut commands: Commands) {
-        commands.trigger(ShotAttack);
+    fn trigger(&self, source: AttackSource, mut commands: Commands) {
+        commands.trigger(Attack::<ShotAttack>::new(source));
     }
 }
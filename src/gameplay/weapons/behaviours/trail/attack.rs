@@ -0,0 +1,64 @@
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    GameLayer,
+    gameplay::weapons::{
+        behaviours::{
+            Attack, WeaponProjectileVisuals,
+            trail::{TrailAttack, TrailConfig, TrailLastPatch, TrailPatch},
+        },
+        components::{CastWeapon, WeaponLifetime},
+        systems::cooldown::WeaponDuration,
+        targeting::Targets,
+    },
+};
+
+pub fn on_trail_attack(
+    attack: On<Attack<TrailAttack>>,
+    mut weapon_q: Query<
+        (
+            Entity,
+            &TrailConfig,
+            &mut TrailLastPatch,
+            &WeaponLifetime,
+            &WeaponProjectileVisuals,
+        ),
+        With<TrailAttack>,
+    >,
+    mut targets: Targets,
+    mut commands: Commands,
+) {
+    let Ok((entity, config, mut last_patch, lifetime, visuals)) =
+        weapon_q.get_mut(attack.source.weapon)
+    else {
+        return;
+    };
+    targets.attack_from(attack.source.origin);
+
+    let position = targets.origin();
+    if last_patch
+        .0
+        .is_some_and(|last| last.distance(position) < config.spacing)
+    {
+        return;
+    }
+    last_patch.0 = Some(position);
+
+    // The transform scales the sprite to the patch, so the collider is built unscaled.
+    let scale = 2.0 * config.radius / visuals.0.size.max_element();
+
+    let mut patch = commands.spawn((
+        Name::new("TrailPatch"),
+        TrailPatch,
+        CastWeapon(entity),
+        Collider::circle(config.radius / scale),
+        Sensor,
+        CollisionEventsEnabled,
+        CollisionLayers::new(GameLayer::Player, [GameLayer::Enemy]),
+        Transform::from_translation(position.extend(-1.0)).with_scale(Vec3::new(scale, scale, 1.0)),
+        WeaponDuration(Timer::from_seconds(lifetime.0, TimerMode::Once)),
+    ));
+
+    visuals.0.apply_ec(&mut patch);
+}
@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::gameplay::weapons::{
+    behaviours::{Attack, AttackSource, TriggerAttackBehavior},
+    components::{TickDuration, WeaponLifetime},
+};
+
+mod attack;
+
+const DEFAULT_TRAIL_TICK: f32 = 0.5;
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_observer(attack::on_trail_attack);
+}
+
+#[derive(Component)]
+pub struct TrailAttack;
+
+/// A patch left behind by a trail weapon.
+#[derive(Component)]
+pub struct TrailPatch;
+
+/// The patches a weapon leaves, from its [`TrailSpec`].
+#[derive(Component, Debug, Clone, Copy)]
+pub struct TrailConfig {
+    pub radius: f32,
+    pub spacing: f32,
+}
+
+/// Where a trail weapon left its last patch.
+#[derive(Component, Debug, Default)]
+pub struct TrailLastPatch(pub Option<Vec2>);
+
+/// Damaging ground patches left where the player walks, one per attack.
+///
+/// Every enemy standing in a patch is hit each tick, with the weapon's `on_hit` effects, e.g.
+/// a `Root`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TrailSpec {
+    pub radius: f32,
+    /// Seconds a patch lasts.
+    pub lifetime: f32,
+    /// Distance from the last patch below which no new patch is left, so standing still
+    /// doesn't stack them.
+    #[serde(default)]
+    pub spacing: f32,
+    /// Seconds between damage ticks, overridden by the weapon's `dot`.
+    #[serde(default = "default_trail_tick")]
+    pub tick: f32,
+}
+
+fn default_trail_tick() -> f32 {
+    DEFAULT_TRAIL_TICK
+}
+
+impl EntityCommand for TrailSpec {
+    fn apply(self, mut entity: EntityWorldMut) {
+        entity.insert((
+            TrailAttack,
+            TrailConfig {
+                radius: self.radius,
+                spacing: self.spacing,
+            },
+            TrailLastPatch::default(),
+            WeaponLifetime(self.lifetime),
+            TickDuration(self.tick),
+        ));
+    }
+}
+
+impl TriggerAttackBehavior for TrailSpec {
+    fn trigger(&self, source: AttackSource, mut commands: Commands) {
+        commands.trigger(Attack::<TrailAttack>::new(source));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        gameplay::{
+            player::Player,
+            weapons::{kind::WeaponKind, spec::loader::test_spec},
+        },
+        sim,
+    };
+
+    fn patches(app: &mut App) -> Vec<Vec2> {
+        app.world_mut()
+            .query_filtered::<&Transform, With<TrailPatch>>()
+            .iter(app.world())
+            .map(|transform| transform.translation.truncate())
+            .collect()
+    }
+
+    #[test]
+    fn trail_drops_patches_at_the_player() {
+        let mut app = sim::weapon_test_app([test_spec(
+            WeaponKind::Thorn,
+            "Trail((radius: 20.0, lifetime: 3.0, spacing: 24.0))",
+        )]);
+
+        sim::attack(&mut app);
+        app.update();
+        let dropped = patches(&mut app);
+        assert!(
+            dropped.len() == 1 && dropped[0].length() < 1.0,
+            "{dropped:?}"
+        );
+
+        let mut player = app
+            .world_mut()
+            .query_filtered::<&mut Transform, With<Player>>()
+            .single_mut(app.world_mut())
+            .expect("the player spawned");
+        player.translation = Vec3::new(100.0, 0.0, player.translation.z);
+
+        sim::attack(&mut app);
+        app.update();
+        let dropped = patches(&mut app);
+        assert_eq!(dropped.len(), 2);
+        assert!(
+            dropped
+                .iter()
+                .any(|patch| patch.distance(Vec2::new(100.0, 0.0)) < 1.0),
+            "{dropped:?}"
+        );
+    }
+
+    #[test]
+    fn trails_dont_stack_patches_within_spacing() {
+        let mut app = sim::weapon_test_app([test_spec(
+            WeaponKind::Thorn,
+            "Trail((radius: 20.0, lifetime: 3.0, spacing: 24.0))",
+        )]);
+
+        sim::attack(&mut app);
+        app.update();
+        sim::attack(&mut app);
+        app.update();
+
+        assert_eq!(patches(&mut app).len(), 1);
+    }
+}
@@ -1,59 +1,73 @@
+use std::f32::consts::TAU;
+
 use crate::{
     GameLayer,
-    gameplay::{
-        enemy::Enemy,
-        player::Player,
-        weapons::{
-            behaviours::{
-                WeaponProjectileVisuals,
-                zone::{ZoneAttack, ZoneShape, ZoneTarget},
+    gameplay::weapons::{
+        behaviours::{
+            Attack, WeaponProjectileVisuals,
+            zone::{
+                ZoneAttack, ZoneAttackInstance, ZoneBehaviour, ZoneMotion, ZoneMovement,
+                ZoneScaling, ZoneShape,
             },
-            components::{CastWeapon, WeaponLifetime},
-            systems::cooldown::WeaponDuration,
         },
+        components::{CastWeapon, WeaponLifetime},
+        systems::cooldown::WeaponDuration,
+        targeting::{TargetingSpec, Targets},
     },
 };
 use avian2d::prelude::*;
-use bevy::prelude::*;
+use bevy::{prelude::*, sprite::Anchor};
+
+/// Segments approximating a ring collider.
+const RING_SEGMENTS: u32 = 24;
 
 pub fn on_zone_attack(
-    _zone_attack: On<ZoneAttack>,
-    weapon: Single<
+    attack: On<Attack<ZoneAttack>>,
+    weapon_q: Query<
         (
             Entity,
             &ZoneShape,
-            &ZoneTarget,
+            &ZoneBehaviour,
+            &TargetingSpec,
             &WeaponLifetime,
             &WeaponProjectileVisuals,
         ),
         With<ZoneAttack>,
     >,
-    player_pos: Single<&Transform, With<Player>>,
-    enemy_q: Query<&Transform, With<Enemy>>,
+    mut targets: Targets,
     mut commands: Commands,
 ) {
-    let (entity, shape, zone_target, lifetime, visuals) = weapon.into_inner();
-
-    let Some(target) = get_target_position(zone_target, &player_pos, &enemy_q) else {
+    let Ok((entity, shape, behaviour, targeting, lifetime, visuals)) =
+        weapon_q.get(attack.source.weapon)
+    else {
         return;
     };
+    targets.attack_from(attack.source.origin);
 
-    let sprite_size = visuals.0.size;
-
-    let (scale, collider) = match shape {
-        super::ZoneShape::Circle { radius } => {
-            let diameter = radius * 2.0;
-            let visual_scale = diameter / sprite_size.x;
-            let scale = Vec2::splat(visual_scale);
-            let collider = Collider::circle(radius / visual_scale);
+    let Some(target) = targets.pick(*targeting) else {
+        return;
+    };
+    let origin = targets.origin();
+    let direction = (target - origin).try_normalize().unwrap_or(Vec2::X);
 
-            (scale, collider)
-        }
+    let position = match behaviour.motion {
+        ZoneMotion::Orbit { radius, .. } => origin + direction * radius,
+        _ if shape.is_directed() => origin,
+        _ => target,
     };
+    let rotation = if shape.is_directed() {
+        Quat::from_rotation_z(direction.to_angle())
+    } else {
+        Quat::IDENTITY
+    };
+
+    // The transform scales the sprite to the shape, so the collider is built unscaled.
+    let scale = shape.sprite_scale(visuals.0.size);
+    let collider = zone_collider(shape, scale);
 
     let mut proj = commands.spawn((
         Name::new("ZoneAttackInstance"),
-        super::ZoneAttackInstance,
+        ZoneAttackInstance,
         CastWeapon(entity),
         collider,
         Sensor,
@@ -61,40 +75,70 @@ pub fn on_zone_attack(
         CollisionLayers::new(GameLayer::Player, [GameLayer::Enemy, GameLayer::Default]),
         DebugRender::default().with_collider_color(Color::srgb(0.0, 1.0, 0.0)),
         Transform {
-            translation: Vec3::new(target.translation.x, target.translation.y, -1.0),
-            scale: scale.extend(1.0),
-            ..default()
+            translation: position.extend(-1.0),
+            rotation,
+            scale: (scale * behaviour.growth.from).extend(1.0),
+        },
+        ZoneMovement {
+            motion: behaviour.motion,
+            offset: position - origin,
+        },
+        ZoneScaling {
+            base: scale,
+            growth: behaviour.growth,
         },
         WeaponDuration(Timer::from_seconds(lifetime.0, TimerMode::Once)),
     ));
 
     visuals.0.apply_ec(&mut proj);
+    if shape.is_directed() {
+        proj.insert(Anchor::CENTER_LEFT);
+    }
 }
 
-fn get_target_position(
-    target: &super::ZoneTarget,
-    player_pos: &Transform,
-    enemy_q: &Query<&Transform, With<Enemy>>,
-) -> Option<Transform> {
-    match target {
-        super::ZoneTarget::Player => Some(*player_pos),
-        super::ZoneTarget::Enemy => {
-            let mut min_distance = f32::MAX;
-            let mut closest_enemy: Option<Transform> = None;
-
-            for enemy_pos in enemy_q.iter() {
-                let distance = player_pos
-                    .translation
-                    .truncate()
-                    .distance(enemy_pos.translation.truncate());
-
-                if distance < min_distance {
-                    min_distance = distance;
-                    closest_enemy = Some(*enemy_pos);
-                }
-            }
-
-            closest_enemy
+/// The collider of `shape` for a zone scaled by `scale`.
+fn zone_collider(shape: &ZoneShape, scale: Vec2) -> Collider {
+    match *shape {
+        ZoneShape::Circle { radius } => Collider::circle(radius / scale.x),
+        ZoneShape::Rectangle { width, height } => {
+            Collider::rectangle(width / scale.x, height / scale.y)
+        }
+        ZoneShape::Ring {
+            inner_radius,
+            outer_radius,
+        } => {
+            let thickness = (outer_radius - inner_radius) / scale.x;
+            let middle = (outer_radius + inner_radius) / 2.0 / scale.x;
+            let segment = TAU * (outer_radius / scale.x) / RING_SEGMENTS as f32;
+            let segments = (0..RING_SEGMENTS)
+                .map(|i| {
+                    let angle = TAU * i as f32 / RING_SEGMENTS as f32;
+                    (
+                        Vec2::from_angle(angle) * middle,
+                        Rotation::radians(angle),
+                        Collider::rectangle(thickness, segment),
+                    )
+                })
+                .collect();
+            Collider::compound(segments)
+        }
+        ZoneShape::Cone { angle, range } => {
+            // The cone points along +x from its apex at the origin.
+            let half_angle = angle.to_radians() / 2.0;
+            let steps = 8;
+            let arc = (0..=steps).map(|i| {
+                let t = -half_angle + angle.to_radians() * i as f32 / steps as f32;
+                Vec2::from_angle(t) * range / scale
+            });
+            let points: Vec<Vec2> = std::iter::once(Vec2::ZERO).chain(arc).collect();
+            // Too thin cones have no hull, the loader rejects them but a line still works.
+            Collider::convex_hull(points)
+                .unwrap_or_else(|| Collider::segment(Vec2::ZERO, Vec2::new(range / scale.x, 0.0)))
         }
+        ZoneShape::Line { length, width } => Collider::compound(vec![(
+            Vec2::new(length / 2.0 / scale.x, 0.0),
+            Rotation::IDENTITY,
+            Collider::rectangle(length / scale.x, width / scale.y),
+        )]),
     }
 }
@@ -1,7 +1,11 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::gameplay::weapons::{behaviours::TriggerAttackBehavior, components::WeaponLifetime};
+use crate::gameplay::weapons::{
+    behaviours::{Attack, AttackSource, TriggerAttackBehavior},
+    components::WeaponLifetime,
+    targeting::TargetingSpec,
+};
 
 mod attack;
 mod movement;
@@ -11,49 +15,335 @@ pub(super) fn plugin(app: &mut App) {
     app.add_plugins(movement::plugin);
 }
 
-#[derive(Component, Event)]
+#[derive(Component)]
 pub struct ZoneAttack;
 
 #[derive(Component)]
 pub struct ZoneAttackInstance;
 
-#[derive(Component)]
-pub struct ZoneFollowPlayer;
+/// How a spawned zone moves, see [`ZoneMotion`].
+#[derive(Component, Debug, Clone)]
+pub struct ZoneMovement {
+    pub motion: ZoneMotion,
+    /// The zone's offset from the player, for motions relative to the player.
+    pub offset: Vec2,
+}
 
-#[derive(Component, Debug, Clone, Serialize, Deserialize)]
+/// Scales a spawned zone from `base * from` to `base * to` over its lifetime.
+#[derive(Component, Debug, Clone)]
+pub struct ZoneScaling {
+    pub base: Vec2,
+    pub growth: ZoneGrowth,
+}
+
+/// Where older specs place zones, superseded by the weapon's [`TargetingSpec`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub enum ZoneTarget {
     Player,
     Enemy,
 }
 
+/// The area a zone covers. Angles are in degrees, cones are at most 180° wide.
+///
+/// Cones and lines start at the player and point at the target, the other shapes are
+/// centered on the target.
 #[derive(Component, Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub enum ZoneShape {
-    Circle { radius: f32 },
+    Circle {
+        radius: f32,
+    },
+    Rectangle {
+        width: f32,
+        height: f32,
+    },
+    /// A donut, enemies inside `inner_radius` are safe.
+    Ring {
+        inner_radius: f32,
+        outer_radius: f32,
+    },
+    Cone {
+        angle: f32,
+        range: f32,
+    },
+    /// A beam.
+    Line {
+        length: f32,
+        width: f32,
+    },
+}
+
+impl ZoneShape {
+    /// Whether the zone starts at the player instead of being centered on the target.
+    pub fn is_directed(&self) -> bool {
+        matches!(self, ZoneShape::Cone { .. } | ZoneShape::Line { .. })
+    }
+
+    /// The scale stretching a sprite of `sprite_size` over the zone. Round zones scale
+    /// uniformly to their width.
+    pub fn sprite_scale(&self, sprite_size: Vec2) -> Vec2 {
+        match self {
+            ZoneShape::Circle { .. } | ZoneShape::Ring { .. } => {
+                Vec2::splat(self.size().x / sprite_size.x)
+            }
+            _ => self.size() / sprite_size,
+        }
+    }
+
+    /// The size of the box around the zone.
+    fn size(&self) -> Vec2 {
+        match *self {
+            ZoneShape::Circle { radius } => Vec2::splat(radius * 2.0),
+            ZoneShape::Rectangle { width, height } => Vec2::new(width, height),
+            ZoneShape::Ring { outer_radius, .. } => Vec2::splat(outer_radius * 2.0),
+            ZoneShape::Cone { angle, range } => {
+                Vec2::new(range, 2.0 * range * (angle.to_radians() / 2.0).sin())
+            }
+            ZoneShape::Line { length, width } => Vec2::new(length, width),
+        }
+    }
+}
+
+/// How a zone moves after it spawned.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub enum ZoneMotion {
+    /// Stay where it spawned.
+    #[default]
+    Static,
+    /// Keep the offset to the player it spawned with.
+    FollowPlayer,
+    /// Move toward the nearest enemy at `speed` units per second.
+    Drift { speed: f32 },
+    /// Circle the player at `radius`, `angular_speed` in radians per second.
+    Orbit { radius: f32, angular_speed: f32 },
+}
+
+/// Scale factors at the start and at the end of a zone's lifetime. Zones stay larger than 0.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ZoneGrowth {
+    pub from: f32,
+    pub to: f32,
+}
+
+impl Default for ZoneGrowth {
+    fn default() -> Self {
+        Self { from: 1.0, to: 1.0 }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(deny_unknown_fields)]
 pub struct ZoneSpec {
     pub shape: ZoneShape,
-    pub target: ZoneTarget,
+    #[serde(default)]
+    pub target: Option<ZoneTarget>,
     pub lifetime: f32,
+    #[serde(default)]
+    pub motion: ZoneMotion,
+    #[serde(default)]
+    pub growth: ZoneGrowth,
+}
+
+impl ZoneSpec {
+    pub fn default_targeting(&self) -> TargetingSpec {
+        match self.target {
+            Some(ZoneTarget::Player) => TargetingSpec::Player,
+            Some(ZoneTarget::Enemy) | None => TargetingSpec::Nearest,
+        }
+    }
+}
+
+/// The [`ZoneMotion`] and [`ZoneGrowth`] of a zone weapon, copied onto every zone it spawns.
+#[derive(Component, Debug, Clone, Copy)]
+pub struct ZoneBehaviour {
+    pub motion: ZoneMotion,
+    pub growth: ZoneGrowth,
 }
 
 impl EntityCommand for ZoneSpec {
     fn apply(self, mut entity: EntityWorldMut) {
         entity.insert((
             ZoneAttack,
-            self.target,
             self.shape,
+            ZoneBehaviour {
+                motion: self.motion,
+                growth: self.growth,
+            },
             WeaponLifetime(self.lifetime),
         ));
     }
 }
 
 impl TriggerAttackBehavior for ZoneSpec {
-    fn trigger(&self, mut commands: Commands) {
-        commands.trigger(ZoneAttack);
+    fn trigger(&self, source: AttackSource, mut commands: Commands) {
+        commands.trigger(Attack::<ZoneAttack>::new(source));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32::consts::PI;
+
+    use super::*;
+    use crate::{
+        gameplay::weapons::{kind::WeaponKind, spec::loader::test_spec, targeting::TargetingSpec},
+        sim,
+    };
+
+    /// An app that just spawned `zone` aimed with `targeting`, with a walker rooted at
+    /// `walker`.
+    fn spawn_zone(zone: &str, targeting: TargetingSpec, walker: Vec2) -> App {
+        let mut spec = test_spec(WeaponKind::Aoe, zone);
+        spec.targeting = targeting;
+        let mut app = sim::weapon_test_app([spec]);
+        sim::spawn_walker(&mut app, walker);
+        sim::root_enemies(&mut app);
+
+        sim::attack(&mut app);
+        app.update();
+        app
+    }
+
+    fn zone(app: &mut App) -> Transform {
+        *app.world_mut()
+            .query_filtered::<&Transform, With<ZoneAttackInstance>>()
+            .single(app.world())
+            .expect("the zone spawned")
+    }
+
+    fn zone_position(app: &mut App) -> Vec2 {
+        zone(app).translation.truncate()
+    }
+
+    #[test]
+    fn sprites_stretch_over_the_zone() {
+        let sprite = Vec2::new(32.0, 16.0);
+
+        let circle = ZoneShape::Circle { radius: 32.0 };
+        assert_eq!(circle.size(), Vec2::splat(64.0));
+        assert_eq!(circle.sprite_scale(sprite), Vec2::splat(2.0));
+
+        let ring = ZoneShape::Ring {
+            inner_radius: 8.0,
+            outer_radius: 16.0,
+        };
+        assert_eq!(ring.sprite_scale(sprite), Vec2::splat(1.0));
+
+        let line = ZoneShape::Line {
+            length: 64.0,
+            width: 8.0,
+        };
+        assert_eq!(line.sprite_scale(sprite), Vec2::new(2.0, 0.5));
+    }
+
+    #[test]
+    fn cones_are_as_wide_as_their_arc() {
+        let size = |angle: f32| {
+            ZoneShape::Cone {
+                angle,
+                range: 100.0,
+            }
+            .size()
+        };
+
+        assert!(size(60.0).abs_diff_eq(Vec2::new(100.0, 100.0), 1e-3));
+        assert!(size(90.0).abs_diff_eq(Vec2::new(100.0, 141.421), 1e-3));
+        assert!(size(180.0).abs_diff_eq(Vec2::new(100.0, 200.0), 1e-3));
+    }
+
+    #[test]
+    fn static_zones_stay_on_their_target() {
+        let walker = Vec2::new(100.0, 0.0);
+        let mut app = spawn_zone(
+            "Zone((shape: Circle(radius: 10.0), lifetime: 5.0))",
+            TargetingSpec::Nearest,
+            walker,
+        );
+        assert!(zone_position(&mut app).distance(walker) < 1.0);
+
+        sim::advance(&mut app, 1.0);
+        assert!(zone_position(&mut app).distance(walker) < 1.0);
+        assert_eq!(sim::hits(&app, WeaponKind::Aoe), 1);
+    }
+
+    #[test]
+    fn following_zones_keep_their_offset_to_the_player() {
+        let walker = Vec2::new(100.0, 0.0);
+        let mut app = spawn_zone(
+            "Zone((shape: Circle(radius: 10.0), lifetime: 5.0, motion: FollowPlayer))",
+            TargetingSpec::Nearest,
+            walker,
+        );
+
+        let moved_to = Vec2::new(0.0, 80.0);
+        sim::move_player(&mut app, moved_to);
+
+        sim::advance(&mut app, 0.1);
+        let position = zone_position(&mut app);
+        assert!(
+            position.distance(walker + moved_to) < 5.0,
+            "zone at {position}"
+        );
+    }
+
+    #[test]
+    fn drifting_zones_move_to_the_nearest_enemy() {
+        let walker = Vec2::new(150.0, 0.0);
+        let mut app = spawn_zone(
+            "Zone((shape: Circle(radius: 10.0), lifetime: 5.0, motion: Drift(speed: 100.0)))",
+            TargetingSpec::Player,
+            walker,
+        );
+        assert!(zone_position(&mut app).length() < 1.0);
+
+        sim::advance(&mut app, 0.5);
+        let position = zone_position(&mut app);
+        assert!((40.0..60.0).contains(&position.x), "zone at {position}");
+        assert!(position.y.abs() < 1.0, "zone at {position}");
+        assert_eq!(sim::hits(&app, WeaponKind::Aoe), 0);
+
+        sim::advance(&mut app, 1.5);
+        assert!(zone_position(&mut app).distance(walker) < 1.0);
+        assert_eq!(sim::hits(&app, WeaponKind::Aoe), 1);
+    }
+
+    #[test]
+    fn orbiting_zones_circle_the_player() {
+        let mut app = spawn_zone(
+            "Zone((shape: Circle(radius: 10.0), lifetime: 5.0, \
+             motion: Orbit(radius: 60.0, angular_speed: 3.1415927)))",
+            TargetingSpec::Nearest,
+            Vec2::new(200.0, 0.0),
+        );
+        let start = zone_position(&mut app);
+        assert!(
+            start.distance(Vec2::new(60.0, 0.0)) < 5.0,
+            "zone at {start}"
+        );
+
+        sim::advance(&mut app, 0.5);
+        let quarter = zone_position(&mut app);
+        assert!((quarter.length() - 60.0).abs() < 1.0, "zone at {quarter}");
+        let turned = start.angle_to(quarter);
+        assert!((turned - PI / 2.0).abs() < 0.1, "turned {turned} rad");
+    }
+
+    #[test]
+    fn growing_zones_scale_and_reach_further() {
+        let mut app = spawn_zone(
+            "Zone((shape: Circle(radius: 10.0), lifetime: 2.0, growth: (from: 1.0, to: 11.0)))",
+            TargetingSpec::Player,
+            Vec2::new(60.0, 0.0),
+        );
+        let start = zone(&mut app).scale.x;
+        assert_eq!(sim::hits(&app, WeaponKind::Aoe), 0);
+
+        sim::advance(&mut app, 1.0);
+        let halfway = zone(&mut app).scale.x / start;
+        assert!((5.5..6.5).contains(&halfway), "scaled by {halfway}");
+        assert_eq!(sim::hits(&app, WeaponKind::Aoe), 1);
     }
 }
@@ -1,4 +1,11 @@
-use crate::gameplay::player::Player;
+use crate::gameplay::{
+    enemy::Enemy,
+    player::Player,
+    weapons::{
+        behaviours::zone::{ZoneAttackInstance, ZoneMotion, ZoneMovement, ZoneScaling},
+        systems::cooldown::WeaponDuration,
+    },
+};
 use bevy::prelude::*;
 
 use crate::{PausableSystems, screens::Screen};
@@ -6,21 +13,59 @@ use crate::{PausableSystems, screens::Screen};
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         FixedUpdate,
-        (move_zone_attack)
+        (move_zone_attack, scale_zone_attack)
             .run_if(in_state(Screen::Gameplay))
             .in_set(PausableSystems),
     );
 }
 
 pub fn move_zone_attack(
-    player_q: Query<&Transform, (With<Player>, Without<super::ZoneFollowPlayer>)>,
-    mut zone_q: Query<&mut Transform, (With<super::ZoneFollowPlayer>, Without<Player>)>,
+    player_q: Query<&Transform, (With<Player>, Without<ZoneAttackInstance>)>,
+    enemy_q: Query<&Transform, (With<Enemy>, Without<ZoneAttackInstance>)>,
+    mut zone_q: Query<(&mut Transform, &mut ZoneMovement), With<ZoneAttackInstance>>,
+    time: Res<Time>,
 ) {
     let Ok(player_tf) = player_q.single() else {
         return;
     };
+    let player_pos = player_tf.translation.truncate();
 
-    for mut tf in &mut zone_q {
-        tf.translation = player_tf.translation;
+    for (mut tf, mut movement) in &mut zone_q {
+        let position = tf.translation.truncate();
+
+        let new_position = match movement.motion {
+            ZoneMotion::Static => continue,
+            ZoneMotion::FollowPlayer => player_pos + movement.offset,
+            ZoneMotion::Drift { speed } => {
+                let nearest = enemy_q
+                    .iter()
+                    .map(|enemy_tf| enemy_tf.translation.truncate())
+                    .min_by(|a, b| {
+                        a.distance_squared(position)
+                            .total_cmp(&b.distance_squared(position))
+                    });
+                let Some(nearest) = nearest else {
+                    continue;
+                };
+                position.move_towards(nearest, speed * time.delta_secs())
+            }
+            ZoneMotion::Orbit { angular_speed, .. } => {
+                let step = Rot2::radians(angular_speed * time.delta_secs());
+                movement.offset = step * movement.offset;
+                player_pos + movement.offset
+            }
+        };
+
+        tf.translation = new_position.extend(tf.translation.z);
+    }
+}
+
+pub fn scale_zone_attack(mut zone_q: Query<(&mut Transform, &ZoneScaling, &WeaponDuration)>) {
+    for (mut tf, scaling, duration) in &mut zone_q {
+        let factor = scaling
+            .growth
+            .from
+            .lerp(scaling.growth.to, duration.0.fraction());
+        tf.scale = (scaling.base * factor).extend(1.0);
     }
 }
@@ -1,10 +1,7 @@
 use avian2d::prelude::*;
-use bevy::prelude::*;
+use bevy::{ecs::entity::EntityHashSet, prelude::*};
 
-use crate::{
-    GameLayer,
-    gameplay::{damage_numbers::DamageType, weapons::systems::cooldown::WeaponDuration},
-};
+use crate::{GameLayer, gameplay::damage_numbers::DamageType};
 
 #[derive(Component)]
 pub struct Weapon;
@@ -22,8 +19,6 @@ pub struct Weapon;
             GameLayer::Default,
         ],
     ),
-    // TODO: This is a placeholder to remove projectiles after some time.
-    WeaponDuration(Timer::from_seconds(5.0, TimerMode::Once)),
 )]
 pub(crate) struct PlayerProjectile;
 
@@ -43,6 +38,10 @@ pub(crate) struct ProjectileDirection(pub Vec3);
 #[derive(Component, Reflect, Default)]
 pub struct ProjectileSpeed(pub f32);
 
+/// Scales the [`ProjectileSpeed`] of a single projectile, e.g. a slowing boomerang.
+#[derive(Component, Reflect)]
+pub(crate) struct ProjectileSpeedScale(pub f32);
+
 #[derive(Component, Reflect)]
 pub(crate) struct ProjectileCount(pub u32);
 
@@ -52,8 +51,30 @@ pub(crate) struct WeaponLifetime(pub f32);
 #[derive(Component, Reflect)]
 pub(crate) struct WeaponRange(pub f32);
 
-#[derive(Component, Reflect)]
-pub struct DeathOnCollision;
+/// The enemies a projectile with a [`ProjectilePolicy`] has hit, and how often it pierced and
+/// bounced so far.
+///
+/// [`ProjectilePolicy`]: crate::gameplay::weapons::spec::components::ProjectilePolicy
+#[derive(Component, Default)]
+pub(crate) struct ProjectileHits {
+    pub hit: EntityHashSet,
+    pub pierced: u32,
+    pub bounced: u32,
+}
+
+/// How long and how far a projectile with a [`ProjectilePolicy`] has flown.
+///
+/// [`ProjectilePolicy`]: crate::gameplay::weapons::spec::components::ProjectilePolicy
+#[derive(Component)]
+pub(crate) struct ProjectileFlight {
+    pub lifetime: Timer,
+    pub travelled: f32,
+    pub last_position: Vec2,
+}
+
+/// An expired projectile fading out, it no longer hits anything.
+#[derive(Component)]
+pub(crate) struct FadingOut(pub Timer);
 
 #[derive(Component, Reflect)]
 pub struct CollisionDamage;
@@ -67,6 +88,13 @@ pub(crate) struct ExplosionRadius(pub f32);
 #[derive(Component, Reflect)]
 pub(crate) struct BaseDamage(pub f32);
 
+/// The highest level a weapon can be upgraded to.
+pub(crate) const MAX_WEAPON_LEVEL: u32 = 8;
+
+/// How often a weapon was picked or upgraded. Starts at 1.
+#[derive(Component, Reflect)]
+pub(crate) struct WeaponLevel(pub u32);
+
 #[derive(Component, Reflect)]
 pub struct TickDuration(pub f32);
 
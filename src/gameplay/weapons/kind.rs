@@ -1,7 +1,7 @@
 use bevy::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Deserialize)]
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
 pub enum WeaponKind {
     Aoe,
     Orb,
@@ -14,9 +14,12 @@ pub enum WeaponKind {
     Hammer,
     Slash,
     Sword,
+    Thorn,
 }
 
 impl WeaponKind {
+    /// Every kind that has a spec in the assets and can be offered.
+    /// Thorn is left out until its spec and sprites are added.
     pub const ALL: &'static [WeaponKind] = &[
         WeaponKind::Aoe,
         WeaponKind::Orb,
@@ -29,6 +32,5 @@ impl WeaponKind {
         WeaponKind::Hammer,
         WeaponKind::Slash,
         WeaponKind::Sword,
-        // TODO: WeaponKind::Thorn,
     ];
 }
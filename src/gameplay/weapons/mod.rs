@@ -5,19 +5,22 @@ use crate::{
         player::{InInventoryOf, Player},
         weapons::{
             behaviours::{WeaponImpactVisuals, WeaponProjectileVisuals},
-            components::{BaseDamage, CollisionDamage, DeathOnCollision, TickDuration, Weapon},
+            components::{BaseDamage, CollisionDamage, TickDuration, Weapon, WeaponLevel},
             spec::components::WeaponSpec,
             systems::cooldown::WeaponCooldown,
         },
     },
+    save::{SaveData, Upgrade},
     screens::Screen,
 };
 
 mod behaviours;
 pub(crate) mod components;
 pub(crate) mod kind;
+pub(crate) mod rarity;
 pub(crate) mod spec;
 pub(crate) mod systems;
+pub(crate) mod targeting;
 
 pub(crate) fn plugin(app: &mut App) {
     app.add_plugins((spec::plugin, behaviours::plugin, systems::plugin));
@@ -31,6 +34,9 @@ impl Command for AddWeapon {
         let Ok(player) = query.single(world) else {
             return;
         };
+        let damage_multiplier = world
+            .get_resource::<SaveData>()
+            .map_or(1.0, |save| save.upgrade_multiplier(Upgrade::Damage));
 
         let mut commands = world.commands();
 
@@ -40,7 +46,8 @@ impl Command for AddWeapon {
             DespawnOnExit(Screen::Gameplay),
             self.0.kind,
             InInventoryOf(player),
-            BaseDamage(self.0.base_damage),
+            BaseDamage(self.0.base_damage * damage_multiplier),
+            WeaponLevel(1),
             WeaponCooldown(Timer::from_seconds(self.0.cooldown, TimerMode::Repeating)),
             WeaponProjectileVisuals(self.0.visuals),
         ));
@@ -48,6 +55,7 @@ impl Command for AddWeapon {
         entity.queue(self.0.attack);
         entity.queue(self.0.on_hit);
         entity.queue(self.0.sfx);
+        entity.insert(self.0.targeting);
 
         match self.0.dot {
             Some(dot) => {
@@ -61,10 +69,5 @@ impl Command for AddWeapon {
         if let Some(impact) = self.0.impact_visuals {
             entity.insert(WeaponImpactVisuals(impact));
         }
-
-        if self.0.despawn_on_hit {
-            //TODO: Move to attack spec to allow for pass through
-            entity.insert(DeathOnCollision);
-        }
     }
 }
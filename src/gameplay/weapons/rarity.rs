@@ -0,0 +1,88 @@
+use bevy::{color::palettes::basic, prelude::*};
+use rand::Rng;
+
+/// How strong an upgrade offered on level up is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+pub enum Rarity {
+    Common,
+    Uncommon,
+    Rare,
+    Epic,
+}
+
+impl Rarity {
+    pub const ALL: [Rarity; 4] = [Rarity::Common, Rarity::Uncommon, Rarity::Rare, Rarity::Epic];
+
+    /// Relative chance of rolling this rarity.
+    pub fn weight(self) -> f32 {
+        match self {
+            Rarity::Common => 60.,
+            Rarity::Uncommon => 25.,
+            Rarity::Rare => 10.,
+            Rarity::Epic => 5.,
+        }
+    }
+
+    /// Damage multiplier applied per upgrade level of this rarity.
+    pub fn damage_multiplier(self) -> f32 {
+        match self {
+            Rarity::Common => 1.1,
+            Rarity::Uncommon => 1.2,
+            Rarity::Rare => 1.35,
+            Rarity::Epic => 1.5,
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Rarity::Common => basic::WHITE,
+            Rarity::Uncommon => basic::GREEN,
+            Rarity::Rare => basic::BLUE,
+            Rarity::Epic => basic::RED,
+        }
+        .into()
+    }
+
+    pub fn roll(rng: &mut impl Rng) -> Self {
+        let total: f32 = Self::ALL.iter().map(|r| r.weight()).sum();
+        let mut roll = rng.random_range(0.0..total);
+
+        for rarity in Self::ALL {
+            if roll < rarity.weight() {
+                return rarity;
+            }
+            roll -= rarity.weight();
+        }
+
+        Rarity::Common
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::platform::collections::HashMap;
+    use bevy_rand::prelude::WyRand;
+    use rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn rarities_roll_as_often_as_their_weight() {
+        const ROLLS: usize = 100_000;
+        let mut rng = WyRand::seed_from_u64(7);
+
+        let mut rolled = HashMap::<Rarity, usize>::default();
+        for _ in 0..ROLLS {
+            *rolled.entry(Rarity::roll(&mut rng)).or_default() += 1;
+        }
+
+        for rarity in Rarity::ALL {
+            let share = rolled[&rarity] as f32 / ROLLS as f32;
+            let expected = rarity.weight() / 100.;
+            assert!(
+                (share - expected).abs() < 0.01,
+                "{rarity:?} rolled {share}, expected {expected}"
+            );
+        }
+    }
+}
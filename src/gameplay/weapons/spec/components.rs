@@ -7,11 +7,13 @@ use crate::gameplay::{
     simple_animation::{AnimationIndices, AnimationTimer},
     weapons::{
         behaviours::{
-            TriggerAttackBehavior, WeaponAttackSfx, WeaponImpactSfx, chain::ChainSpec,
-            falling::FallingSpec, homing::HomingSpec, melee::MeleeSpec, nova::NovaSpec,
-            orbiters::OrbitersSpec, shot::ShotSpec, zone::ZoneSpec,
+            AttackSource, TriggerAttackBehavior, WeaponAttackSfx, WeaponImpactSfx, beam::BeamSpec,
+            boomerang::BoomerangSpec, chain::ChainSpec, deploy::DeploySpec, falling::FallingSpec,
+            homing::HomingSpec, melee::MeleeSpec, nova::NovaSpec, orbiters::OrbitersSpec,
+            shot::ShotSpec, trail::TrailSpec, zone::ZoneSpec,
         },
         kind::WeaponKind,
+        targeting::TargetingSpec,
     },
 };
 
@@ -21,9 +23,9 @@ pub struct WeaponSpec {
     pub base_damage: f32,
     pub cooldown: f32,
     pub dot: Option<f32>,
-    pub despawn_on_hit: bool,
 
     pub attack: AttackSpec,
+    pub targeting: TargetingSpec,
     pub on_hit: HitSpec,
 
     pub visuals: VisualSpec,
@@ -53,6 +55,70 @@ impl EntityCommand for HitSpec {
     }
 }
 
+/// Seconds a projectile flies if its spec doesn't say otherwise.
+const DEFAULT_PROJECTILE_LIFETIME: f32 = 5.0;
+
+/// What happens to a projectile when it hits enemies and walls, and when it expires.
+///
+/// A projectile never hits the same enemy twice. It passes through `pierce` enemies, then
+/// turns to the nearest enemy it hasn't hit yet `bounce` times before it despawns.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Reflect)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectilePolicy {
+    /// Enemies passed through, unlimited if not set.
+    pub pierce: u32,
+    pub bounce: u32,
+    pub walls: WallBehaviour,
+    /// Seconds until the projectile expires.
+    pub lifetime: f32,
+    /// Distance travelled until the projectile expires, unlimited if not set.
+    pub range: Option<f32>,
+    pub on_expire: ExpireEffect,
+}
+
+impl Default for ProjectilePolicy {
+    fn default() -> Self {
+        Self {
+            pierce: u32::MAX,
+            bounce: 0,
+            walls: WallBehaviour::Pass,
+            lifetime: DEFAULT_PROJECTILE_LIFETIME,
+            range: None,
+            on_expire: ExpireEffect::Despawn,
+        }
+    }
+}
+
+impl EntityCommand for ProjectilePolicy {
+    fn apply(self, mut entity: EntityWorldMut) {
+        entity.insert(self);
+    }
+}
+
+/// What a projectile does when it runs into a wall.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Reflect)]
+pub enum WallBehaviour {
+    /// Despawn.
+    Stop,
+    /// Bounce off the wall.
+    Ricochet,
+    /// Fly through the wall.
+    #[default]
+    Pass,
+}
+
+/// What a projectile does when its lifetime or range runs out.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, Reflect)]
+#[serde(deny_unknown_fields)]
+pub enum ExpireEffect {
+    #[default]
+    Despawn,
+    /// Stop hitting and fade out over `seconds`.
+    FadeOut { seconds: f32 },
+    /// Damage every enemy within the attack's `explosion_radius`.
+    Explode,
+}
+
 #[derive(Debug, Clone)]
 pub struct VisualSpec {
     pub image: Handle<Image>,
@@ -155,6 +221,10 @@ pub enum AttackSpec {
     Falling(FallingSpec),
     Melee(MeleeSpec),
     Zone(ZoneSpec),
+    Beam(BeamSpec),
+    Boomerang(BoomerangSpec),
+    Deploy(DeploySpec),
+    Trail(TrailSpec),
 }
 
 impl EntityCommand for AttackSpec {
@@ -168,21 +238,71 @@ impl EntityCommand for AttackSpec {
             AttackSpec::Falling(s) => s.apply(entity),
             AttackSpec::Zone(s) => s.apply(entity),
             AttackSpec::Melee(s) => s.apply(entity),
+            AttackSpec::Beam(s) => s.apply(entity),
+            AttackSpec::Boomerang(s) => s.apply(entity),
+            AttackSpec::Deploy(s) => s.apply(entity),
+            AttackSpec::Trail(s) => s.apply(entity),
+        }
+    }
+}
+
+impl AttackSpec {
+    /// The [`TargetingSpec`] of specs that don't set one, matching how each attack aimed
+    /// before weapons could choose.
+    pub fn default_targeting(&self) -> TargetingSpec {
+        match self {
+            AttackSpec::Orbiters(_) | AttackSpec::Nova(_) | AttackSpec::Melee(_) => {
+                TargetingSpec::Facing
+            }
+            AttackSpec::Chain(_)
+            | AttackSpec::Shot(_)
+            | AttackSpec::Falling(_)
+            | AttackSpec::Beam(_)
+            | AttackSpec::Boomerang(_) => TargetingSpec::Nearest,
+            AttackSpec::Homing(_) => TargetingSpec::Random,
+            AttackSpec::Trail(_) => TargetingSpec::Player,
+            AttackSpec::Zone(s) => s.default_targeting(),
+            AttackSpec::Deploy(s) => s
+                .attack
+                .as_deref()
+                .map_or(TargetingSpec::Nearest, AttackSpec::default_targeting),
+        }
+    }
+
+    /// The [`ProjectilePolicy`] of attacks firing projectiles.
+    pub fn projectile_policy_mut(&mut self) -> Option<&mut ProjectilePolicy> {
+        match self {
+            AttackSpec::Shot(s) => Some(&mut s.projectile),
+            AttackSpec::Nova(s) => Some(&mut s.projectile),
+            AttackSpec::Homing(s) => Some(&mut s.projectile),
+            AttackSpec::Falling(s) => Some(&mut s.projectile),
+            AttackSpec::Deploy(s) => s.projectile_policy_mut(),
+            AttackSpec::Orbiters(_)
+            | AttackSpec::Chain(_)
+            | AttackSpec::Melee(_)
+            | AttackSpec::Zone(_)
+            | AttackSpec::Beam(_)
+            | AttackSpec::Boomerang(_)
+            | AttackSpec::Trail(_) => None,
         }
     }
 }
 
 impl TriggerAttackBehavior for AttackSpec {
-    fn trigger(&self, commands: Commands) {
+    fn trigger(&self, source: AttackSource, commands: Commands) {
         match self {
-            AttackSpec::Shot(s) => s.trigger(commands),
-            AttackSpec::Orbiters(s) => s.trigger(commands),
-            AttackSpec::Chain(s) => s.trigger(commands),
-            AttackSpec::Nova(s) => s.trigger(commands),
-            AttackSpec::Homing(s) => s.trigger(commands),
-            AttackSpec::Falling(s) => s.trigger(commands),
-            AttackSpec::Melee(s) => s.trigger(commands),
-            AttackSpec::Zone(s) => s.trigger(commands),
+            AttackSpec::Shot(s) => s.trigger(source, commands),
+            AttackSpec::Orbiters(s) => s.trigger(source, commands),
+            AttackSpec::Chain(s) => s.trigger(source, commands),
+            AttackSpec::Nova(s) => s.trigger(source, commands),
+            AttackSpec::Homing(s) => s.trigger(source, commands),
+            AttackSpec::Falling(s) => s.trigger(source, commands),
+            AttackSpec::Melee(s) => s.trigger(source, commands),
+            AttackSpec::Zone(s) => s.trigger(source, commands),
+            AttackSpec::Beam(s) => s.trigger(source, commands),
+            AttackSpec::Boomerang(s) => s.trigger(source, commands),
+            AttackSpec::Deploy(s) => s.trigger(source, commands),
+            AttackSpec::Trail(s) => s.trigger(source, commands),
         }
     }
 }
@@ -1,14 +1,24 @@
+use std::path::{Path, PathBuf};
+
 use bevy::{
     asset::{AssetLoader, LoadContext, io::Reader},
     prelude::*,
 };
 use serde::Deserialize;
-use serde_ron::de::from_bytes;
+use serde_ron::{Options, extensions::Extensions};
 use thiserror::Error;
 
 use crate::gameplay::weapons::{
+    behaviours::{
+        deploy::DeployTrigger,
+        zone::{ZoneGrowth, ZoneShape, ZoneSpec},
+    },
     kind::WeaponKind,
-    spec::components::{AtlasAnimation, AttackSpec, HitSpec, VisualSpec, WeaponSfx, WeaponSpec},
+    spec::components::{
+        AtlasAnimation, AttackSpec, ExpireEffect, HitSpec, ProjectilePolicy, VisualSpec, WeaponSfx,
+        WeaponSpec,
+    },
+    targeting::TargetingSpec,
 };
 
 #[derive(Deserialize)]
@@ -18,9 +28,13 @@ struct WeaponSpecRaw {
     pub base_damage: f32,
     pub cooldown: f32,
     pub dot: Option<f32>,
+    /// Older specs despawn projectiles on their first hit with this instead of `pierce: 0`.
     #[serde(default)]
     pub despawn_on_hit: bool,
     pub attack: AttackSpec,
+    /// Defaults per attack, see [`AttackSpec::default_targeting`].
+    #[serde(default)]
+    pub targeting: Option<TargetingSpec>,
     pub on_hit: HitSpec,
     pub visuals: VisualRaw,
     pub impact_visuals: Option<VisualRaw>,
@@ -113,6 +127,65 @@ pub enum WeaponRonLoaderError {
 
     #[error("Could not parse RON: {0}")]
     RonError(#[from] serde_ron::error::SpannedError),
+
+    #[error("A zone cone is {angle}° by {range}, it has to be up to 180° wide and longer than 0")]
+    InvalidCone { angle: f32, range: f32 },
+
+    #[error("A zone grows from {from} to {to}, it has to stay larger than 0")]
+    VanishingZone { from: f32, to: f32 },
+
+    #[error("Projectiles explode when they expire, but the attack has no explosion_radius")]
+    ExplosionWithoutRadius,
+
+    #[error("A mine needs an attack or an explosion_radius, or it does nothing")]
+    HarmlessMine,
+}
+
+/// Zones have to have an area while they last, or their collider can't be built. Projectiles
+/// exploding on expiry use the attack's `explosion_radius`, so it has to have one. Mines have to
+/// do something when they go off.
+fn validate_attack(attack: &AttackSpec) -> Result<(), WeaponRonLoaderError> {
+    match attack {
+        AttackSpec::Zone(zone) => validate_zone(zone),
+        AttackSpec::Shot(shot) => validate_expiry(&shot.projectile, shot.explosion_radius),
+        AttackSpec::Falling(falling) => {
+            validate_expiry(&falling.projectile, falling.explosion_radius)
+        }
+        AttackSpec::Nova(nova) => validate_expiry(&nova.projectile, None),
+        AttackSpec::Homing(homing) => validate_expiry(&homing.projectile, None),
+        AttackSpec::Deploy(deploy) => {
+            let is_mine = matches!(deploy.trigger, DeployTrigger::Proximity { .. });
+            if is_mine && deploy.attack.is_none() && deploy.explosion_radius.is_none() {
+                return Err(WeaponRonLoaderError::HarmlessMine);
+            }
+            deploy.attack.as_deref().map_or(Ok(()), validate_attack)
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate_zone(zone: &ZoneSpec) -> Result<(), WeaponRonLoaderError> {
+    if let ZoneShape::Cone { angle, range } = zone.shape {
+        let has_area = angle > 0.0 && angle <= 180.0 && range > 0.0;
+        if !has_area {
+            return Err(WeaponRonLoaderError::InvalidCone { angle, range });
+        }
+    }
+    let ZoneGrowth { from, to } = zone.growth;
+    if from.is_nan() || from <= 0.0 || to.is_nan() || to <= 0.0 {
+        return Err(WeaponRonLoaderError::VanishingZone { from, to });
+    }
+    Ok(())
+}
+
+fn validate_expiry(
+    projectile: &ProjectilePolicy,
+    explosion_radius: Option<f32>,
+) -> Result<(), WeaponRonLoaderError> {
+    if projectile.on_expire == ExpireEffect::Explode && explosion_radius.is_none() {
+        return Err(WeaponRonLoaderError::ExplosionWithoutRadius);
+    }
+    Ok(())
 }
 
 impl AssetLoader for WeaponRonLoader {
@@ -128,15 +201,30 @@ impl AssetLoader for WeaponRonLoader {
     ) -> Result<Self::Asset, Self::Error> {
         let mut bytes = Vec::new();
         reader.read_to_end(&mut bytes).await?;
-        let raw = from_bytes::<WeaponSpecRaw>(&bytes)?;
+        let mut raw = ron_options().from_bytes::<WeaponSpecRaw>(&bytes)?;
+        validate_attack(&raw.attack)?;
+
+        if raw.despawn_on_hit {
+            match raw.attack.projectile_policy_mut() {
+                Some(policy) => policy.pierce = 0,
+                None => warn!(
+                    "{}: `despawn_on_hit` only applies to projectile attacks",
+                    load_context.path().path().display()
+                ),
+            }
+        }
+
+        let targeting = raw
+            .targeting
+            .unwrap_or_else(|| raw.attack.default_targeting());
 
         Ok(WeaponSpec {
             kind: raw.kind,
             base_damage: raw.base_damage,
             cooldown: raw.cooldown,
             dot: raw.dot,
-            despawn_on_hit: raw.despawn_on_hit,
             attack: raw.attack,
+            targeting,
             on_hit: raw.on_hit,
             visuals: raw.visuals.load(load_context),
             impact_visuals: raw.impact_visuals.map(|v| v.load(load_context)),
@@ -150,45 +238,89 @@ impl AssetLoader for WeaponRonLoader {
     }
 }
 
+/// How weapon specs are parsed. Optional fields take a plain value, e.g. `pierce: 2`, so fields
+/// that became optional keep reading older specs.
+pub(crate) fn ron_options() -> Options {
+    Options::default().with_default_extension(Extensions::IMPLICIT_SOME)
+}
+
+/// A spec for `kind` with `attack`, written like in a `.weapon.ron`, and no visuals or sounds.
+/// It deals 1 physical damage a hit and only attacks when told to.
 #[cfg(test)]
-mod tests {
-    use super::*;
-    use std::path::{Path, PathBuf};
+pub(crate) fn test_spec(kind: WeaponKind, attack: &str) -> WeaponSpec {
+    use crate::gameplay::damage_numbers::DamageType;
+
+    let attack: AttackSpec = ron_options()
+        .from_str(attack)
+        .unwrap_or_else(|e| panic!("{attack}: {e}"));
+
+    WeaponSpec {
+        kind,
+        base_damage: 1.0,
+        cooldown: 1000.0,
+        dot: None,
+        targeting: attack.default_targeting(),
+        attack,
+        on_hit: HitSpec {
+            damage_type: DamageType::Physical,
+            effects: Vec::new(),
+            knockback_strength: 0.0,
+        },
+        visuals: VisualSpec {
+            image: Handle::default(),
+            size: Vec2::splat(16.0),
+            atlas: None,
+        },
+        impact_visuals: None,
+        sfx: WeaponSfx {
+            attack: None,
+            impact: None,
+        },
+        icon: Handle::default(),
+    }
+}
 
-    fn weapon_ron_files(root: impl AsRef<Path>) -> Vec<PathBuf> {
-        fn is_weapon_ron(path: &Path) -> bool {
-            matches!(
-                path.file_name().and_then(|s| s.to_str()),
-                Some(name) if name.ends_with(".weapon.ron")
-            )
-        }
+/// Every `.weapon.ron` file below `root`, sorted.
+pub(crate) fn weapon_ron_files(root: impl AsRef<Path>) -> Vec<PathBuf> {
+    fn is_weapon_ron(path: &Path) -> bool {
+        matches!(
+            path.file_name().and_then(|s| s.to_str()),
+            Some(name) if name.ends_with(".weapon.ron")
+        )
+    }
 
-        fn walk(dir: &Path, out: &mut Vec<PathBuf>) {
-            let Ok(entries) = std::fs::read_dir(dir) else {
-                return;
-            };
-
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_dir() {
-                    walk(&path, out);
-                } else if is_weapon_ron(&path) {
-                    out.push(path);
-                }
+    fn walk(dir: &Path, out: &mut Vec<PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                walk(&path, out);
+            } else if is_weapon_ron(&path) {
+                out.push(path);
             }
         }
-
-        let mut out = Vec::new();
-        walk(root.as_ref(), &mut out);
-        out.sort();
-        out
     }
 
+    let mut out = Vec::new();
+    walk(root.as_ref(), &mut out);
+    out.sort();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim;
+
     fn parse_weapon_ron(path: &Path) -> Result<WeaponSpecRaw, String> {
         let bytes =
             std::fs::read(path).map_err(|e| format!("{}: read error: {e}", path.display()))?;
 
-        serde_ron::de::from_bytes::<WeaponSpecRaw>(&bytes)
+        ron_options()
+            .from_bytes::<WeaponSpecRaw>(&bytes)
             .map_err(|e| format!("{}: parse error: {e}", path.display()))
     }
 
@@ -213,6 +345,10 @@ mod tests {
 
     #[test]
     fn weapon_ron_files_parse() {
+        if !sim::assets_checked_out() {
+            return;
+        }
+
         let files = weapon_ron_files("assets/weapons/rons");
         assert!(!files.is_empty(), "No .weapon.ron files found");
 
@@ -231,6 +367,10 @@ mod tests {
 
     #[test]
     fn weapon_ron_references_existing_files() {
+        if !sim::assets_checked_out() {
+            return;
+        }
+
         let files = weapon_ron_files("assets/weapons/rons");
         assert!(!files.is_empty(), "No .weapon.ron files found");
 
@@ -267,6 +407,10 @@ mod tests {
 
     #[test]
     fn every_weaponkind_has_a_weapon_ron() {
+        if !sim::assets_checked_out() {
+            return;
+        }
+
         use std::collections::HashSet;
 
         let files = weapon_ron_files("assets/weapons/rons");
@@ -302,4 +446,73 @@ mod tests {
             "No .weapon.ron found for WeaponKind variant(s): {missing:?}",
         );
     }
+
+    fn validate(ron: &str) -> Result<(), WeaponRonLoaderError> {
+        match ron_options().from_str::<AttackSpec>(ron) {
+            Ok(attack) => validate_attack(&attack),
+            Err(e) => panic!("{ron}: {e}"),
+        }
+    }
+
+    #[test]
+    fn zones_need_an_area() {
+        assert!(validate("Zone((shape: Cone(angle: 180.0, range: 50.0), lifetime: 1.0))").is_ok());
+        assert!(validate("Zone((shape: Cone(angle: 270.0, range: 50.0), lifetime: 1.0))").is_err());
+        assert!(validate("Zone((shape: Cone(angle: 0.0, range: 50.0), lifetime: 1.0))").is_err());
+        assert!(
+            validate(
+                "Zone((shape: Circle(radius: 50.0), lifetime: 1.0, growth: (from: 0.0, to: 1.0)))"
+            )
+            .is_err()
+        );
+        assert!(
+            validate(
+                "Zone((shape: Circle(radius: 50.0), lifetime: 1.0, growth: (from: 1.0, to: 0.0)))"
+            )
+            .is_err()
+        );
+        assert!(
+            validate(
+                "Deploy((trigger: Cooldown(seconds: 1.0), lifetime: 8.0, \
+                 attack: Zone((shape: Cone(angle: 360.0, range: 50.0), lifetime: 1.0))))"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn explosions_need_a_radius() {
+        assert!(
+            validate(
+                "Shot((speed: 300.0, explosion_radius: 60.0, projectile: (on_expire: Explode)))"
+            )
+            .is_ok()
+        );
+        assert!(
+            validate(
+                "Shot((speed: 300.0, explosion_radius: None, projectile: (on_expire: Explode)))"
+            )
+            .is_err()
+        );
+        assert!(
+            validate(
+                "Homing((count: 1, movement: (kind: Straight, speed: 100.0), \
+                 projectile: (on_expire: Explode)))"
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn mines_need_an_attack_or_an_explosion() {
+        assert!(
+            validate(
+                "Deploy((trigger: Proximity(radius: 24.0), explosion_radius: 64.0, lifetime: 20.0))"
+            )
+            .is_ok()
+        );
+        assert!(validate("Deploy((trigger: Proximity(radius: 24.0), lifetime: 20.0))").is_err());
+        // Only mines have to, turrets without an attack still deploy.
+        assert!(validate("Deploy((trigger: Cooldown(seconds: 1.0), lifetime: 20.0))").is_ok());
+    }
 }
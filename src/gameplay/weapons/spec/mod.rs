@@ -27,16 +27,22 @@ impl FromWorld for WeaponMap {
             SystemState::<(Res<WeaponAssets>, Res<Assets<WeaponSpec>>)>::new(world);
         let (raw_assets, spec_assets) = system_state.get(world);
 
-        let mut map = HashMap::new();
+        let mut specs = Vec::new();
         for (file_stem, handle) in &raw_assets.specs {
             if let Some(spec) = spec_assets.get(handle) {
-                map.insert(spec.kind, spec.clone());
+                specs.push(spec.clone());
             } else {
                 warn!("Failed to load weapon spec for: {}", file_stem.as_ref());
             }
         }
 
-        WeaponMap(map)
+        specs.into_iter().collect()
+    }
+}
+
+impl FromIterator<WeaponSpec> for WeaponMap {
+    fn from_iter<I: IntoIterator<Item = WeaponSpec>>(specs: I) -> Self {
+        WeaponMap(specs.into_iter().map(|spec| (spec.kind, spec)).collect())
     }
 }
 
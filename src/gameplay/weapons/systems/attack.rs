@@ -1,4 +1,4 @@
-use crate::gameplay::weapons::behaviours::TriggerAttackBehavior;
+use crate::gameplay::weapons::behaviours::{AttackSource, TriggerAttackBehavior};
 use bevy::prelude::*;
 
 use crate::gameplay::weapons::{components::Weapon, kind::WeaponKind, spec::WeaponMap};
@@ -26,6 +26,7 @@ fn dispatch_weapon_attacks(
         .get(attack.entity)
         .map(|kind| weapon_map.get(kind))
     {
-        spec.attack.trigger(commands);
+        spec.attack
+            .trigger(AttackSource::player(attack.entity), commands);
     }
 }
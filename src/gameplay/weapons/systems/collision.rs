@@ -1,4 +1,4 @@
-use avian2d::prelude::{CollisionStart, Collisions};
+use avian2d::prelude::{CollisionStart, Collisions, RigidBody};
 use bevy::prelude::*;
 
 use crate::{
@@ -6,11 +6,12 @@ use crate::{
     gameplay::{
         enemy::Enemy,
         weapons::{
+            behaviours::{beam::BeamTargets, homing::CurrentTarget},
             components::{
-                BaseDamage, CastWeapon, CollisionDamage, DeathOnCollision, ExplosionRadius,
-                TickDamageTimer, TickDuration, WeaponProjectiles,
+                BaseDamage, CastWeapon, CollisionDamage, ExplosionRadius, ProjectileDirection,
+                ProjectileFlight, ProjectileHits, TickDamageTimer, TickDuration, WeaponProjectiles,
             },
-            spec::components::HitSpec,
+            spec::components::{HitSpec, ProjectilePolicy, WallBehaviour},
             systems::hit::WeaponHitEvent,
         },
     },
@@ -31,17 +32,17 @@ pub(crate) fn plugin(app: &mut App) {
 fn on_added_cast_weapon(
     event: On<Add, CastWeapon>,
     weapons: Query<
-        (Entity, Option<&TickDuration>),
+        (Entity, Option<&TickDuration>, Option<&ProjectilePolicy>),
         Or<(With<CollisionDamage>, With<TickDuration>)>,
     >,
-    projectile_q: Query<&CastWeapon>,
+    projectile_q: Query<(&CastWeapon, &Transform)>,
     mut commands: Commands,
 ) -> Result {
     let projectile = event.entity;
-    let cast_weapon = projectile_q.get(projectile)?;
+    let (cast_weapon, transform) = projectile_q.get(projectile)?;
 
     let weapon = cast_weapon.0;
-    for (weapon_entity, tick_duration) in &weapons {
+    for (weapon_entity, tick_duration, policy) in &weapons {
         if weapon_entity == weapon {
             if let Some(tick_duration) = tick_duration {
                 commands
@@ -53,6 +54,17 @@ fn on_added_cast_weapon(
             } else {
                 commands.entity(projectile).observe(on_projectile_collision);
             }
+
+            if let Some(policy) = policy {
+                commands.entity(projectile).insert((
+                    ProjectileHits::default(),
+                    ProjectileFlight {
+                        lifetime: Timer::from_seconds(policy.lifetime, TimerMode::Once),
+                        travelled: 0.0,
+                        last_position: transform.translation.truncate(),
+                    },
+                ));
+            }
         }
     }
 
@@ -61,28 +73,81 @@ fn on_added_cast_weapon(
 
 pub fn on_projectile_collision(
     event: On<CollisionStart>,
-    projectile_q: Query<&CastWeapon>,
+    mut projectile_q: Query<
+        (
+            &CastWeapon,
+            &mut Transform,
+            Option<&mut ProjectileHits>,
+            Option<&mut ProjectileDirection>,
+            Option<&mut CurrentTarget>,
+        ),
+        Without<Enemy>,
+    >,
     weapon_q: Query<(
         &HitSpec,
         &BaseDamage,
         Option<&ExplosionRadius>,
-        Option<&DeathOnCollision>,
+        Option<&ProjectilePolicy>,
     )>,
-    enemy_q: Query<&Transform, With<Enemy>>,
+    enemy_q: Query<(Entity, &Transform), With<Enemy>>,
+    body_q: Query<&RigidBody>,
+    collisions: Collisions,
     mut commands: Commands,
 ) -> Result {
     let projectile = event.collider1;
     let target = event.collider2;
 
     // Get weapon and damage mode
-    let cast_weapon = projectile_q.get(projectile)?;
+    let (cast_weapon, mut projectile_tf, hits, direction, homing_target) =
+        projectile_q.get_mut(projectile)?;
     let weapon = cast_weapon.0;
 
-    let Ok(enemy_tf) = enemy_q.get(target) else {
+    let (hit, dmg, explosion_radius, policy) = weapon_q.get(weapon)?;
+
+    let Ok((_, enemy_tf)) = enemy_q.get(target) else {
+        let is_wall = body_q.get(target).is_ok_and(RigidBody::is_static);
+        if let Some(policy) = policy
+            && is_wall
+        {
+            match policy.walls {
+                WallBehaviour::Pass => {}
+                WallBehaviour::Stop => commands.entity(projectile).despawn(),
+                WallBehaviour::Ricochet => {
+                    if let Some(mut direction) = direction {
+                        let normal = collisions
+                            .get(projectile, target)
+                            .and_then(|pair| pair.manifolds.first())
+                            .map(|manifold| manifold.normal.extend(0.0));
+                        direction.0 = match normal {
+                            Some(normal) => direction.0.reflect(normal.normalize_or_zero()),
+                            None => -direction.0,
+                        };
+                        if homing_target.is_none() {
+                            face(&mut projectile_tf, direction.0);
+                        }
+                    }
+                }
+            }
+        }
         return Ok(());
     };
 
-    let (hit, dmg, explosion_radius, death_on_collision) = weapon_q.get(weapon)?;
+    let (Some(policy), Some(mut hits)) = (policy, hits) else {
+        trigger_hit_event(
+            &mut commands,
+            weapon,
+            target,
+            enemy_tf,
+            hit,
+            dmg,
+            explosion_radius,
+        );
+        return Ok(());
+    };
+
+    if !hits.hit.insert(target) {
+        return Ok(());
+    }
 
     trigger_hit_event(
         &mut commands,
@@ -94,16 +159,54 @@ pub fn on_projectile_collision(
         explosion_radius,
     );
 
-    if death_on_collision.is_some() {
-        commands.entity(projectile).despawn();
+    if hits.pierced < policy.pierce {
+        hits.pierced += 1;
+        return Ok(());
+    }
+
+    if hits.bounced < policy.bounce {
+        let position = projectile_tf.translation.truncate();
+        let next = enemy_q
+            .iter()
+            .filter(|(enemy, _)| !hits.hit.contains(enemy))
+            .min_by(|(_, a), (_, b)| {
+                let a = a.translation.truncate().distance_squared(position);
+                let b = b.translation.truncate().distance_squared(position);
+                a.total_cmp(&b)
+            });
+
+        if let Some((next, next_tf)) = next {
+            hits.bounced += 1;
+            let towards = (next_tf.translation.truncate() - position)
+                .normalize_or_zero()
+                .extend(0.0);
+
+            if let Some(mut direction) = direction {
+                direction.0 = towards;
+            }
+            match homing_target {
+                Some(mut homing_target) => homing_target.0 = Some(next),
+                None => face(&mut projectile_tf, towards),
+            }
+            return Ok(());
+        }
     }
 
+    commands.entity(projectile).despawn();
+
     Ok(())
 }
 
+/// Rotate a projectile sprite pointing up to fly along `direction`.
+fn face(transform: &mut Transform, direction: Vec3) {
+    if direction != Vec3::ZERO {
+        transform.rotation = Quat::from_rotation_arc(Vec3::Y, direction.normalize());
+    }
+}
+
 fn tick_damage(
     projectiles: Query<&WeaponProjectiles>,
-    mut projectile_q: Query<&mut TickDamageTimer>,
+    mut projectile_q: Query<(&mut TickDamageTimer, Option<&BeamTargets>)>,
     mut weapons: Query<(Entity, &HitSpec, &BaseDamage, Option<&ExplosionRadius>)>,
     enemy_q: Query<&Transform, With<Enemy>>,
     mut commands: Commands,
@@ -112,12 +215,17 @@ fn tick_damage(
 ) {
     for (weapon, hit, dmg, explosion_radius) in &mut weapons {
         for projectile in projectiles.iter_descendants(weapon) {
-            let Ok(mut tick_timer) = projectile_q.get_mut(projectile) else {
+            let Ok((mut tick_timer, beam_targets)) = projectile_q.get_mut(projectile) else {
                 continue;
             };
             tick_timer.0.tick(time.delta());
             if tick_timer.0.just_finished() {
-                for contact in collisions.entities_colliding_with(projectile) {
+                // Beams have no collider and find their targets with a shape cast
+                let contacts: Vec<Entity> = match beam_targets {
+                    Some(targets) => targets.0.clone(),
+                    None => collisions.entities_colliding_with(projectile).collect(),
+                };
+                for contact in contacts {
                     if let Ok(enemy_tf) = enemy_q.get(contact) {
                         trigger_hit_event(
                             &mut commands,
@@ -154,3 +262,88 @@ fn trigger_hit_event(
         effects: hit_spec.effects.clone(),
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use avian2d::prelude::Collider;
+
+    use super::*;
+    use crate::{
+        gameplay::weapons::{kind::WeaponKind, spec::loader::test_spec},
+        sim,
+    };
+
+    /// Fire `shot` at a row of three walkers, returning the enemies hit and whether the shot
+    /// is still flying.
+    fn shoot_row(shot: &str) -> (u32, bool) {
+        let mut app = sim::weapon_test_app([test_spec(WeaponKind::Icelance, shot)]);
+        for x in [100.0, 150.0, 200.0] {
+            sim::spawn_walker(&mut app, Vec2::new(x, 0.0));
+        }
+
+        sim::attack(&mut app);
+        sim::advance(&mut app, 1.0);
+
+        let flying = app
+            .world_mut()
+            .query::<&CastWeapon>()
+            .iter(app.world())
+            .next()
+            .is_some();
+        (sim::hits(&app, WeaponKind::Icelance), flying)
+    }
+
+    #[test]
+    fn projectiles_pierce_every_enemy_without_a_policy() {
+        assert_eq!(
+            shoot_row("Shot((speed: 300.0, range: 400.0, explosion_radius: None))"),
+            (3, true)
+        );
+    }
+
+    #[test]
+    fn projectiles_despawn_after_piercing() {
+        assert_eq!(
+            shoot_row(
+                "Shot((speed: 300.0, range: 400.0, explosion_radius: None, \
+                 projectile: (pierce: 1)))"
+            ),
+            (2, false)
+        );
+    }
+
+    #[test]
+    fn projectiles_bounce_to_enemies_not_hit_yet() {
+        assert_eq!(
+            shoot_row(
+                "Shot((speed: 300.0, range: 400.0, explosion_radius: None, \
+                 projectile: (pierce: 0, bounce: 2)))"
+            ),
+            (3, false)
+        );
+    }
+
+    #[test]
+    fn projectiles_stop_at_walls() {
+        let hits_behind_wall = |walls: &str| {
+            let shot = format!(
+                "Shot((speed: 300.0, range: 400.0, explosion_radius: None, \
+                 projectile: (walls: {walls})))"
+            );
+            let mut app = sim::weapon_test_app([test_spec(WeaponKind::Icelance, &shot)]);
+            app.world_mut().spawn((
+                RigidBody::Static,
+                Collider::rectangle(16.0, 200.0),
+                Transform::from_xyz(100.0, 0.0, 0.0),
+            ));
+            sim::spawn_walker(&mut app, Vec2::new(200.0, 0.0));
+
+            sim::attack(&mut app);
+            sim::advance(&mut app, 1.0);
+            sim::hits(&app, WeaponKind::Icelance)
+        };
+
+        assert_eq!(hits_behind_wall("Pass"), 1);
+        assert_eq!(hits_behind_wall("Stop"), 0);
+    }
+}
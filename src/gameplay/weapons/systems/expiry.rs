@@ -0,0 +1,250 @@
+//! Expiring projectiles once their lifetime or range runs out.
+
+use avian2d::prelude::*;
+use bevy::prelude::*;
+
+use crate::{
+    PausableSystems,
+    gameplay::{
+        enemy::Enemy,
+        weapons::{
+            behaviours::{WeaponImpactSfx, WeaponImpactVisuals},
+            components::{BaseDamage, CastWeapon, ExplosionRadius, FadingOut, ProjectileFlight},
+            spec::components::{ExpireEffect, HitSpec, ProjectilePolicy},
+            systems::hit::{WeaponHitEvent, spawn_impact_fx},
+        },
+    },
+    screens::Screen,
+};
+
+pub(crate) fn plugin(app: &mut App) {
+    app.add_systems(
+        FixedUpdate,
+        (expire_projectiles, fade_out_projectiles)
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+}
+
+fn expire_projectiles(
+    mut projectile_q: Query<(Entity, &CastWeapon, &Transform, &mut ProjectileFlight)>,
+    weapon_q: Query<(
+        &ProjectilePolicy,
+        &BaseDamage,
+        &HitSpec,
+        Option<&ExplosionRadius>,
+        Option<&WeaponImpactVisuals>,
+        Option<&WeaponImpactSfx>,
+    )>,
+    enemy_q: Query<(Entity, &Transform), With<Enemy>>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (projectile, cast_weapon, transform, mut flight) in &mut projectile_q {
+        let Ok((policy, damage, hit, explosion_radius, impact_vfx, impact_sfx)) =
+            weapon_q.get(cast_weapon.0)
+        else {
+            continue;
+        };
+
+        let position = transform.translation.truncate();
+        flight.travelled += position.distance(flight.last_position);
+        flight.last_position = position;
+        flight.lifetime.tick(time.delta());
+
+        let out_of_range = policy.range.is_some_and(|range| flight.travelled >= range);
+        if !out_of_range && !flight.lifetime.is_finished() {
+            continue;
+        }
+
+        match policy.on_expire {
+            ExpireEffect::Despawn => {
+                commands.entity(projectile).despawn();
+            }
+            ExpireEffect::FadeOut { seconds } => {
+                commands
+                    .entity(projectile)
+                    .remove::<(ProjectileFlight, Collider)>()
+                    .insert(FadingOut(Timer::from_seconds(seconds, TimerMode::Once)));
+            }
+            ExpireEffect::Explode => {
+                let radius = explosion_radius.map_or(0.0, |radius| radius.0);
+                let nearest = enemy_q
+                    .iter()
+                    .map(|(enemy, enemy_tf)| {
+                        (enemy, enemy_tf.translation.truncate().distance(position))
+                    })
+                    .filter(|&(_, distance)| distance < radius)
+                    .min_by(|(_, a), (_, b)| a.total_cmp(b));
+
+                // Like a hit on the closest enemy, so on-hit effects and stats apply.
+                if let Some((enemy, _)) = nearest {
+                    commands.trigger(WeaponHitEvent {
+                        entity: cast_weapon.0,
+                        target: enemy,
+                        hit_pos: transform.translation,
+                        dmg: damage.0,
+                        damage_type: hit.damage_type,
+                        aoe: Some(radius),
+                        effects: hit.effects.clone(),
+                    });
+                } else {
+                    spawn_impact_fx(&mut commands, impact_vfx, impact_sfx, transform.translation);
+                }
+                commands.entity(projectile).despawn();
+            }
+        }
+    }
+}
+
+fn fade_out_projectiles(
+    mut projectile_q: Query<(Entity, &mut FadingOut, Option<&mut Sprite>)>,
+    time: Res<Time>,
+    mut commands: Commands,
+) {
+    for (projectile, mut fade, sprite) in &mut projectile_q {
+        fade.0.tick(time.delta());
+
+        if let Some(mut sprite) = sprite {
+            sprite.color.set_alpha(fade.0.fraction_remaining());
+        }
+        if fade.0.is_finished() {
+            commands.entity(projectile).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        gameplay::{
+            enemy::Root,
+            weapons::{
+                kind::WeaponKind,
+                spec::{components::OnHitEffect, loader::test_spec},
+                targeting::TargetingSpec,
+            },
+        },
+        sim,
+    };
+
+    /// An app that just fired `shot` to the right, where the player faces.
+    fn fire(shot: &str) -> App {
+        let mut spec = test_spec(WeaponKind::Icelance, shot);
+        spec.targeting = TargetingSpec::Facing;
+        let mut app = sim::weapon_test_app([spec]);
+        sim::attack(&mut app);
+        app
+    }
+
+    fn projectile(app: &mut App) -> Option<Entity> {
+        app.world_mut()
+            .query_filtered::<Entity, With<CastWeapon>>()
+            .iter(app.world())
+            .next()
+    }
+
+    #[test]
+    fn projectiles_expire_after_their_lifetime() {
+        let mut app = fire(
+            "Shot((speed: 100.0, range: 1000.0, explosion_radius: None, \
+             projectile: (lifetime: 0.5)))",
+        );
+
+        sim::advance(&mut app, 0.3);
+        assert!(projectile(&mut app).is_some());
+        sim::advance(&mut app, 0.3);
+        assert!(projectile(&mut app).is_none());
+    }
+
+    #[test]
+    fn projectiles_expire_out_of_range() {
+        let mut app = fire("Shot((speed: 300.0, range: 100.0, explosion_radius: None))");
+
+        sim::advance(&mut app, 0.2);
+        assert!(projectile(&mut app).is_some());
+        sim::advance(&mut app, 0.2);
+        assert!(projectile(&mut app).is_none());
+    }
+
+    #[test]
+    fn shots_without_a_range_use_the_projectile_range() {
+        let mut app =
+            fire("Shot((speed: 300.0, explosion_radius: None, projectile: (range: 100.0)))");
+
+        sim::advance(&mut app, 0.2);
+        assert!(projectile(&mut app).is_some());
+        sim::advance(&mut app, 0.2);
+        assert!(projectile(&mut app).is_none());
+    }
+
+    #[test]
+    fn homing_lifetime_is_the_projectile_lifetime_unless_set() {
+        let lives_until = |homing: &str| {
+            let mut app = fire(homing);
+            let mut seconds = 0.0;
+            while projectile(&mut app).is_some() && seconds < 5.0 {
+                sim::advance(&mut app, 0.1);
+                seconds += 0.1;
+            }
+            seconds
+        };
+
+        let policy = lives_until(
+            "Homing((count: 1, movement: (kind: Straight, speed: 50.0), \
+             projectile: (lifetime: 0.5)))",
+        );
+        assert!((0.5..=0.7).contains(&policy), "{policy}");
+
+        let old = lives_until(
+            "Homing((count: 1, lifetime: 1.0, movement: (kind: Straight, speed: 50.0), \
+             projectile: (lifetime: 0.5)))",
+        );
+        assert!((1.0..=1.2).contains(&old), "{old}");
+    }
+
+    #[test]
+    fn expired_projectiles_fade_out_without_hitting() {
+        let mut app = fire(
+            "Shot((speed: 100.0, range: 1000.0, explosion_radius: None, \
+             projectile: (lifetime: 0.2, on_expire: FadeOut(seconds: 0.5))))",
+        );
+
+        sim::advance(&mut app, 0.4);
+        let fading = projectile(&mut app).expect("the projectile fades out");
+        let entity = app.world().entity(fading);
+        assert!(entity.contains::<FadingOut>());
+        assert!(!entity.contains::<Collider>());
+        let alpha = entity.get::<Sprite>().map(|sprite| sprite.color.alpha());
+        assert!(alpha.is_some_and(|alpha| alpha < 1.0), "{alpha:?}");
+
+        sim::advance(&mut app, 0.5);
+        assert!(projectile(&mut app).is_none());
+    }
+
+    #[test]
+    fn expiring_projectiles_explode_like_a_hit() {
+        let mut spec = test_spec(
+            WeaponKind::Icelance,
+            "Shot((speed: 300.0, range: 100.0, explosion_radius: 60.0, \
+             projectile: (on_expire: Explode)))",
+        );
+        spec.targeting = TargetingSpec::Facing;
+        spec.on_hit.effects = vec![OnHitEffect::Root { duration: 5.0 }];
+        let mut app = sim::weapon_test_app([spec]);
+        // Next to where the shot expires, but not in its way.
+        sim::spawn_walker(&mut app, Vec2::new(100.0, 45.0));
+        sim::attack(&mut app);
+
+        sim::advance(&mut app, 0.5);
+        assert!(projectile(&mut app).is_none());
+        assert_eq!(sim::hits(&app, WeaponKind::Icelance), 1);
+        let rooted = app
+            .world_mut()
+            .query_filtered::<(), (With<Enemy>, With<Root>)>()
+            .iter(app.world())
+            .count();
+        assert_eq!(rooted, 1);
+    }
+}
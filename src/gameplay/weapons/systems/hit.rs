@@ -6,6 +6,7 @@ use crate::gameplay::{
     weapons::{
         behaviours::{WeaponImpactSfx, WeaponImpactVisuals},
         components::DoT,
+        kind::WeaponKind,
         spec::components::OnHitEffect,
     },
 };
@@ -49,10 +50,22 @@ pub fn on_projectile_hit_fx_sfx(
         .get(ev.entity)
         .expect("ProjectileHitEvent fired for non-weapon entity");
 
+    spawn_impact_fx(&mut commands, impact_vfx, impact_sfx, ev.hit_pos);
+
+    Ok(())
+}
+
+/// Play a weapon's impact visuals and sound at `position`.
+pub(super) fn spawn_impact_fx(
+    commands: &mut Commands,
+    impact_vfx: Option<&WeaponImpactVisuals>,
+    impact_sfx: Option<&WeaponImpactSfx>,
+    position: Vec3,
+) {
     if let Some(vfx) = impact_vfx {
         let mut e = commands.spawn((
             Name::new("Impact VFX"),
-            Transform::from_translation(ev.hit_pos),
+            Transform::from_translation(position),
             GlobalTransform::default(),
         ));
 
@@ -63,17 +76,20 @@ pub fn on_projectile_hit_fx_sfx(
     if let Some(sfx) = impact_sfx {
         commands.spawn((SamplePlayer::new(sfx.0.clone()), SfxPool));
     }
-
-    Ok(())
 }
 
-pub fn on_resolved_hit_damage(trigger: On<WeaponHitEvent>, mut commands: Commands) -> Result {
+pub fn on_resolved_hit_damage(
+    trigger: On<WeaponHitEvent>,
+    weapon_q: Query<&WeaponKind>,
+    mut commands: Commands,
+) -> Result {
     let ev = trigger.event();
 
     commands.trigger(EnemyDamageEvent {
         entity_hit: ev.target,
         dmg: ev.dmg,
         damage_type: ev.damage_type,
+        source: weapon_q.get(ev.entity).ok().copied(),
     });
 
     Ok(())
@@ -82,12 +98,14 @@ pub fn on_resolved_hit_damage(trigger: On<WeaponHitEvent>, mut commands: Command
 pub fn on_resolved_hit_aoe(
     trigger: On<WeaponHitEvent>,
     enemy_q: Query<(Entity, &Transform), With<Enemy>>,
+    weapon_q: Query<&WeaponKind>,
     mut commands: Commands,
 ) {
     let ev = trigger.event();
     let Some(r) = ev.aoe else {
         return;
     };
+    let source = weapon_q.get(ev.entity).ok().copied();
 
     for (other_e, other_tf) in &enemy_q {
         if other_e == ev.target {
@@ -104,6 +122,7 @@ pub fn on_resolved_hit_aoe(
                 entity_hit: other_e,
                 dmg: ev.dmg,
                 damage_type: ev.damage_type,
+                source,
             });
         }
     }
@@ -3,6 +3,7 @@ use bevy::prelude::*;
 pub mod attack;
 pub mod collision;
 pub mod cooldown;
+pub mod expiry;
 pub mod hit;
 pub mod pickup;
 pub mod projectile_movement;
@@ -12,6 +13,7 @@ pub(super) fn plugin(app: &mut App) {
         attack::plugin,
         collision::plugin,
         cooldown::plugin,
+        expiry::plugin,
         pickup::plugin,
         projectile_movement::plugin,
         hit::plugin,
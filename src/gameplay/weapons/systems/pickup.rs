@@ -1,4 +1,10 @@
-use crate::gameplay::weapons::{AddWeapon, components::Weapon, kind::WeaponKind, spec::WeaponMap};
+use crate::gameplay::weapons::{
+    AddWeapon,
+    components::{BaseDamage, MAX_WEAPON_LEVEL, Weapon, WeaponLevel},
+    kind::WeaponKind,
+    rarity::Rarity,
+    spec::WeaponMap,
+};
 use bevy::prelude::*;
 
 use crate::gameplay::player::{InInventoryOf, Player};
@@ -6,6 +12,7 @@ use crate::gameplay::player::{InInventoryOf, Player};
 pub(super) fn plugin(app: &mut App) {
     app.add_observer(handle_pickup_weapon);
     app.add_observer(spawn_weapon_instance);
+    app.add_observer(upgrade_weapon_instance);
 }
 
 #[derive(Event, Reflect, Clone, Copy, Debug)]
@@ -19,10 +26,10 @@ pub struct SpawnWeaponInstanceEvent {
 }
 
 #[derive(Event)]
-#[allow(dead_code)]
 pub struct UpgradeWeaponEvent {
     pub kind: WeaponKind,
     pub amount: u32,
+    pub rarity: Rarity,
 }
 
 pub fn handle_pickup_weapon(
@@ -38,7 +45,11 @@ pub fn handle_pickup_weapon(
         .any(|(k, rel)| *k == kind && rel.0 == *player);
 
     if owned {
-        commands.trigger(UpgradeWeaponEvent { kind, amount: 1 });
+        commands.trigger(UpgradeWeaponEvent {
+            kind,
+            amount: 1,
+            rarity: Rarity::Common,
+        });
     } else {
         commands.trigger(SpawnWeaponInstanceEvent { kind });
     }
@@ -58,3 +69,27 @@ pub fn spawn_weapon_instance(
 
     commands.queue(AddWeapon(spec.clone()));
 }
+
+pub fn upgrade_weapon_instance(
+    trigger: On<UpgradeWeaponEvent>,
+    player: Single<Entity, With<Player>>,
+    mut weapons: Query<
+        (
+            &WeaponKind,
+            &InInventoryOf,
+            &mut WeaponLevel,
+            &mut BaseDamage,
+        ),
+        With<Weapon>,
+    >,
+) {
+    for (kind, owner, mut level, mut damage) in &mut weapons {
+        if *kind != trigger.kind || owner.0 != *player {
+            continue;
+        }
+
+        let levels = trigger.amount.min(MAX_WEAPON_LEVEL.saturating_sub(level.0));
+        level.0 += levels;
+        damage.0 *= trigger.rarity.damage_multiplier().powi(levels as i32);
+    }
+}
@@ -4,7 +4,8 @@ use bevy::prelude::*;
 use crate::{
     PausableSystems,
     gameplay::weapons::components::{
-        PlayerProjectile, ProjectileDirection, ProjectileSpeed, WeaponProjectiles,
+        PlayerProjectile, ProjectileDirection, ProjectileSpeed, ProjectileSpeedScale,
+        WeaponProjectiles,
     },
     screens::Screen,
 };
@@ -21,16 +22,25 @@ pub(crate) fn plugin(app: &mut App) {
 pub(crate) fn move_projectiles(
     weapons: Query<(Entity, &ProjectileSpeed)>,
     projectiles: Query<&WeaponProjectiles>,
-    mut projectile_q: Query<(&mut LinearVelocity, &ProjectileDirection), With<PlayerProjectile>>,
+    mut projectile_q: Query<
+        (
+            &mut LinearVelocity,
+            &ProjectileDirection,
+            Option<&ProjectileSpeedScale>,
+        ),
+        With<PlayerProjectile>,
+    >,
 ) {
     for (weapon, speed) in &weapons {
         for projectile in projectiles.iter_descendants(weapon) {
-            let Ok((mut linear_velocity, bullet_direction)) = projectile_q.get_mut(projectile)
+            let Ok((mut linear_velocity, bullet_direction, speed_scale)) =
+                projectile_q.get_mut(projectile)
             else {
                 continue;
             };
 
-            let movement = bullet_direction.0.normalize_or_zero() * speed.0;
+            let scale = speed_scale.map_or(1.0, |s| s.0);
+            let movement = bullet_direction.0.normalize_or_zero() * speed.0 * scale;
             linear_velocity.0.x = movement.x;
             linear_velocity.0.y = movement.y;
         }
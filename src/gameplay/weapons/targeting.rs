@@ -0,0 +1,365 @@
+//! Picking what a weapon attacks.
+//!
+//! Every weapon has a [`TargetingSpec`], set in its `.weapon.ron` or defaulted by its attack.
+//! Attacks resolve it through the [`Targets`] system param.
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_rand::prelude::WyRand;
+use rand::seq::SliceRandom;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    gameplay::{
+        Health,
+        enemy::Enemy,
+        player::{AimDirection, Player, PlayerFacing},
+        rng::WeaponRng,
+    },
+    settings::{AimMode, Settings},
+};
+
+/// How far from the player a target without an enemy, e.g. [`TargetingSpec::Facing`], lies.
+const AIM_DISTANCE: f32 = 150.0;
+
+/// What a weapon aims at.
+#[derive(Component, Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize, Reflect)]
+#[serde(deny_unknown_fields)]
+pub enum TargetingSpec {
+    /// The enemy closest to the player.
+    #[default]
+    Nearest,
+    /// Any enemy.
+    Random,
+    /// The enemy with the most health.
+    Strongest,
+    /// The enemy with the least health.
+    LowestHealth,
+    /// The enemy with the most other enemies within `radius`.
+    Densest { radius: f32 },
+    /// Where the player is facing.
+    Facing,
+    /// Where the player aims with the mouse or the right stick.
+    Aim,
+    /// The player itself, e.g. for zones around the player.
+    Player,
+}
+
+impl TargetingSpec {
+    /// Whether this picks an enemy rather than a point.
+    pub fn targets_enemies(self) -> bool {
+        match self {
+            TargetingSpec::Nearest
+            | TargetingSpec::Random
+            | TargetingSpec::Strongest
+            | TargetingSpec::LowestHealth
+            | TargetingSpec::Densest { .. } => true,
+            TargetingSpec::Facing | TargetingSpec::Aim | TargetingSpec::Player => false,
+        }
+    }
+}
+
+/// The player and the enemies needed to resolve a [`TargetingSpec`].
+#[derive(SystemParam)]
+pub(crate) struct Targets<'w, 's> {
+    player: Single<
+        'w,
+        's,
+        (
+            &'static Transform,
+            &'static PlayerFacing,
+            &'static AimDirection,
+        ),
+        With<Player>,
+    >,
+    enemies: Query<
+        'w,
+        's,
+        (Entity, &'static Transform, &'static Health),
+        (With<Enemy>, Without<Player>),
+    >,
+    rng: Single<'w, 's, &'static mut WyRand, With<WeaponRng>>,
+    settings: Res<'w, Settings>,
+    /// Where attacks start from instead of the player, see [`Self::attack_from`].
+    from: Local<'s, Option<Vec2>>,
+}
+
+impl Targets<'_, '_> {
+    /// Where attacks start from, the player unless set by [`Self::attack_from`].
+    pub fn origin(&self) -> Vec2 {
+        self.from
+            .unwrap_or_else(|| self.player.0.translation.truncate())
+    }
+
+    /// Resolve targets from `origin`, e.g. a deployed turret, or from the player if `None`.
+    pub fn attack_from(&mut self, origin: Option<Vec2>) {
+        *self.from = origin;
+    }
+
+    /// The weapon RNG, for rolls besides picking targets.
+    pub fn rng(&mut self) -> &mut WyRand {
+        &mut self.rng
+    }
+
+    /// Where the best target is, `None` if the strategy picks enemies and there are none.
+    pub fn pick(&mut self, spec: TargetingSpec) -> Option<Vec2> {
+        if !spec.targets_enemies() {
+            return Some(self.point(spec));
+        }
+
+        self.ranked_enemies(spec)
+            .first()
+            .map(|&(_, position)| position)
+    }
+
+    /// The direction from the origin to the best target.
+    pub fn direction(&mut self, spec: TargetingSpec) -> Option<Vec2> {
+        let origin = self.origin();
+        self.pick(spec).map(|target| {
+            let direction = target - origin;
+            // Targeting the player itself points where the player faces.
+            if direction.length_squared() < f32::EPSILON {
+                self.facing()
+            } else {
+                direction.normalize()
+            }
+        })
+    }
+
+    /// Like [`Self::direction`], but the player's aim if they chose manual aim and the attack
+    /// starts from the player.
+    pub fn aimed_direction(&mut self, spec: TargetingSpec) -> Option<Vec2> {
+        match self.settings.gameplay.aim {
+            AimMode::Manual if self.from.is_none() => Some(self.player.2.0),
+            _ => self.direction(spec),
+        }
+    }
+
+    /// Enemies from best to worst. Strategies picking a point rank enemies by their distance
+    /// to that point.
+    pub fn ranked_enemies(&mut self, spec: TargetingSpec) -> Vec<(Entity, Vec2)> {
+        let origin = self.origin();
+        let mut enemies: Vec<(Entity, Vec2, f32)> = self
+            .enemies
+            .iter()
+            .map(|(entity, transform, health)| (entity, transform.translation.truncate(), health.0))
+            .collect();
+
+        let distance_to = |point: Vec2| {
+            move |(_, a, _): &(Entity, Vec2, f32), (_, b, _): &(Entity, Vec2, f32)| {
+                a.distance_squared(point)
+                    .total_cmp(&b.distance_squared(point))
+            }
+        };
+
+        match spec {
+            TargetingSpec::Nearest => enemies.sort_by(distance_to(origin)),
+            TargetingSpec::Random => enemies.shuffle(&mut **self.rng),
+            TargetingSpec::Strongest => enemies.sort_by(|(_, _, a), (_, _, b)| b.total_cmp(a)),
+            TargetingSpec::LowestHealth => enemies.sort_by(|(_, _, a), (_, _, b)| a.total_cmp(b)),
+            TargetingSpec::Densest { radius } => {
+                let radius_squared = radius * radius;
+                let positions: Vec<Vec2> = enemies.iter().map(|(_, p, _)| *p).collect();
+                let neighbours = |position: Vec2| {
+                    positions
+                        .iter()
+                        .filter(|other| other.distance_squared(position) <= radius_squared)
+                        .count()
+                };
+                enemies.sort_by_cached_key(|(_, position, _)| {
+                    std::cmp::Reverse(neighbours(*position))
+                });
+            }
+            TargetingSpec::Facing | TargetingSpec::Aim | TargetingSpec::Player => {
+                let point = self.point(spec);
+                enemies.sort_by(distance_to(point));
+            }
+        }
+
+        enemies
+            .into_iter()
+            .map(|(entity, position, _)| (entity, position))
+            .collect()
+    }
+
+    /// The point a strategy not picking enemies aims at.
+    fn point(&self, spec: TargetingSpec) -> Vec2 {
+        let origin = self.origin();
+        match spec {
+            TargetingSpec::Player => origin,
+            TargetingSpec::Aim => origin + self.player.2.0 * AIM_DISTANCE,
+            _ => origin + self.facing() * AIM_DISTANCE,
+        }
+    }
+
+    fn facing(&self) -> Vec2 {
+        if self.player.1.is_right() {
+            Vec2::X
+        } else {
+            Vec2::NEG_X
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        gameplay::weapons::{
+            behaviours::zone::ZoneAttackInstance, components::ProjectileDirection,
+            kind::WeaponKind, spec::loader::test_spec,
+        },
+        sim,
+    };
+
+    const ZONE: &str = "Zone((shape: Circle(radius: 10.0), lifetime: 5.0))";
+
+    /// Attack once with a weapon without a `targeting`, with a walker above the player.
+    fn attack_with(attack: &str) -> App {
+        let mut app = sim::weapon_test_app([test_spec(WeaponKind::Aoe, attack)]);
+        sim::spawn_walker(&mut app, Vec2::new(0.0, 100.0));
+        sim::attack(&mut app);
+        app.update();
+        app
+    }
+
+    fn zone_position(attack: &str) -> Vec2 {
+        let mut app = attack_with(attack);
+        app.world_mut()
+            .query_filtered::<&Transform, With<ZoneAttackInstance>>()
+            .single(app.world())
+            .expect("the zone spawned")
+            .translation
+            .truncate()
+    }
+
+    #[test]
+    fn targeting_defaults_to_how_attacks_aimed() {
+        let mut shot = attack_with("Shot((speed: 300.0, range: 400.0, explosion_radius: None))");
+        let direction = shot
+            .world_mut()
+            .query::<&ProjectileDirection>()
+            .single(shot.world())
+            .expect("the shot was fired")
+            .0;
+        assert!(direction.y > 0.9, "shot towards {direction}");
+
+        let at_player =
+            zone_position("Zone((shape: Circle(radius: 50.0), target: Player, lifetime: 2.0))");
+        assert!(at_player.length() < 1.0, "zone at {at_player}");
+
+        let at_enemy = zone_position("Zone((shape: Circle(radius: 50.0), lifetime: 2.0))");
+        assert!(at_enemy.y > 90.0, "zone at {at_enemy}");
+    }
+
+    /// An app with a zone weapon aiming with `targeting` and walkers with the given health.
+    fn walkers_with_health(targeting: TargetingSpec, walkers: &[(Vec2, f32)]) -> App {
+        let mut spec = test_spec(WeaponKind::Aoe, ZONE);
+        spec.targeting = targeting;
+        let mut app = sim::weapon_test_app([spec]);
+        for &(position, _) in walkers {
+            sim::spawn_walker(&mut app, position);
+        }
+
+        let world = app.world_mut();
+        let mut enemy_q = world.query_filtered::<(&Transform, &mut Health), With<Enemy>>();
+        for (transform, mut health) in enemy_q.iter_mut(world) {
+            let position = transform.translation.truncate();
+            if let Some(&(_, walker_health)) = walkers
+                .iter()
+                .find(|(walker, _)| walker.distance(position) < 1.0)
+            {
+                health.0 = walker_health;
+            }
+        }
+        app
+    }
+
+    /// Where the zones of every attack so far spawned.
+    fn zone_positions(app: &mut App) -> Vec<Vec2> {
+        app.world_mut()
+            .query_filtered::<&Transform, With<ZoneAttackInstance>>()
+            .iter(app.world())
+            .map(|transform| transform.translation.truncate())
+            .collect()
+    }
+
+    /// Attack once and return where the zone spawned.
+    fn target_of(mut app: App) -> Vec2 {
+        sim::attack(&mut app);
+        app.update();
+        let positions = zone_positions(&mut app);
+        assert_eq!(positions.len(), 1, "{positions:?}");
+        positions[0]
+    }
+
+    #[test]
+    fn strongest_and_lowest_health_target_by_health() {
+        let walkers = [
+            (Vec2::new(100.0, 0.0), 10.0),
+            (Vec2::new(-100.0, 0.0), 50.0),
+            (Vec2::new(0.0, 100.0), 30.0),
+        ];
+
+        let strongest = target_of(walkers_with_health(TargetingSpec::Strongest, &walkers));
+        assert!(
+            strongest.distance(walkers[1].0) < 5.0,
+            "zone at {strongest}"
+        );
+
+        let weakest = target_of(walkers_with_health(TargetingSpec::LowestHealth, &walkers));
+        assert!(weakest.distance(walkers[0].0) < 5.0, "zone at {weakest}");
+    }
+
+    #[test]
+    fn densest_targets_the_cluster() {
+        // The nearest walker stands alone.
+        let walkers = [
+            (Vec2::new(-60.0, 0.0), 10.0),
+            (Vec2::new(200.0, 0.0), 10.0),
+            (Vec2::new(215.0, 0.0), 10.0),
+            (Vec2::new(200.0, 15.0), 10.0),
+        ];
+
+        let densest = target_of(walkers_with_health(
+            TargetingSpec::Densest { radius: 30.0 },
+            &walkers,
+        ));
+        assert!(densest.x > 190.0, "zone at {densest}");
+
+        let nearest = target_of(walkers_with_health(TargetingSpec::Nearest, &walkers));
+        assert!(nearest.distance(walkers[0].0) < 5.0, "zone at {nearest}");
+    }
+
+    #[test]
+    fn random_targets_any_enemy() {
+        // Enough health to survive every zone.
+        let walkers = [
+            (Vec2::new(100.0, 0.0), 1000.0),
+            (Vec2::new(-100.0, 0.0), 1000.0),
+            (Vec2::new(0.0, 100.0), 1000.0),
+        ];
+        let mut app = walkers_with_health(TargetingSpec::Random, &walkers);
+
+        for _ in 0..20 {
+            sim::attack(&mut app);
+            app.update();
+        }
+
+        let positions = zone_positions(&mut app);
+        assert_eq!(positions.len(), 20);
+        let targeted: Vec<usize> = positions
+            .iter()
+            .map(|position| {
+                walkers
+                    .iter()
+                    .position(|(walker, _)| walker.distance(*position) < 20.0)
+                    .unwrap_or_else(|| panic!("zone at {position} isn't on a walker"))
+            })
+            .collect();
+        assert!(
+            targeted.iter().any(|&walker| walker != targeted[0]),
+            "always targeted walker {}",
+            targeted[0]
+        );
+    }
+}
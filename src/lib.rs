@@ -3,12 +3,17 @@ use bevy::{camera::ScalingMode, prelude::*};
 use bevy_asset_loader::prelude::*;
 
 mod audio;
+mod controls;
 #[cfg(feature = "dev")]
 mod dev_tools;
 mod fixed_update_inspection;
 mod gameplay;
 mod menus;
+mod persistence;
+mod save;
 mod screens;
+mod settings;
+pub mod sim;
 mod theme;
 mod third_party;
 
@@ -40,6 +45,9 @@ pub fn plugin(app: &mut App) {
     app.add_plugins((
         fixed_update_inspection::plugin,
         audio::plugin,
+        controls::plugin,
+        settings::plugin,
+        save::plugin,
         #[cfg(feature = "dev")]
         dev_tools::plugin,
         menus::plugin,
@@ -48,6 +56,13 @@ pub fn plugin(app: &mut App) {
         gameplay::plugin,
     ));
 
+    configure_sets(app);
+
+    app.add_systems(Startup, spawn_camera);
+}
+
+/// Order the system sets and set up the `Pause` state, shared with the headless [`sim`].
+fn configure_sets(app: &mut App) {
     app.configure_sets(
         Update,
         (
@@ -62,15 +77,18 @@ pub fn plugin(app: &mut App) {
 
     app.configure_sets(
         FixedUpdate,
-        (GameplaySystems::MovementModify, GameplaySystems::Movement).chain(),
+        (
+            GameplaySystems::Input,
+            GameplaySystems::MovementModify,
+            GameplaySystems::Movement,
+        )
+            .chain(),
     );
 
     // Set up the `Pause` state.
     app.init_state::<Pause>();
     app.configure_sets(Update, PausableSystems.run_if(in_state(Pause(false))));
     app.configure_sets(FixedUpdate, PausableSystems.run_if(in_state(Pause(false))));
-
-    app.add_systems(Startup, spawn_camera);
 }
 
 const ENEMY_SIZE: f32 = 32.0;
@@ -81,6 +99,8 @@ const XP_GAIN_GEM: f32 = 10.;
 const SPAWN_RADIUS: f32 = 200.0;
 const SPAWN_ATTEMPTS: usize = 10;
 const SPAWN_RADIUS_BUFFER: f32 = 80.0;
+/// How far outside the camera viewport enemies spawn.
+const SPAWN_MARGIN: f32 = 48.0;
 
 /// How quickly should the camera snap to the desired location.
 const CAMERA_DECAY_RATE: f32 = 2.;
@@ -88,6 +108,8 @@ const CAMERA_DECAY_RATE: f32 = 2.;
 /// High-level groupings of systems for gameplay in the `FixedUpdate` schedule.
 #[derive(SystemSet, Debug, Clone, Copy, Eq, PartialEq, Hash, PartialOrd, Ord)]
 pub(crate) enum GameplaySystems {
+    /// Record or replay the player's input
+    Input,
     /// Things that modify velocity
     MovementModify,
     /// Movement that applies velocity to entities
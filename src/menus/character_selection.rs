@@ -1,10 +1,15 @@
 use bevy::text::FontSmoothing;
-use bevy::{prelude::*, ui::Val::*, ui_widgets::observe};
+use bevy::{
+    prelude::*,
+    ui::{Val::*, auto_directional_navigation::AutoDirectionalNavigation},
+    ui_widgets::observe,
+};
 
 use crate::gameplay::player::Player;
 use crate::gameplay::player::characters::Characters;
 use crate::gameplay::simple_animation::{AnimationIndices, AnimationTimer};
 use crate::menus::Menu;
+use crate::save::SaveData;
 use crate::screens::Screen;
 use crate::theme::palette::{
     BUTTON_BACKGROUND, BUTTON_HOVERED_BACKGROUND, BUTTON_PRESSED_BACKGROUND,
@@ -20,6 +25,7 @@ fn spawn_character_screen(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
     texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    save: Res<SaveData>,
 ) {
     let font: Handle<Font> = asset_server.load("ui/compass.ttf");
 
@@ -48,7 +54,7 @@ fn spawn_character_screen(
                         ..default()
                     },
                 )),
-                Spawn(spawn_character_grid(asset_server, texture_atlases)),
+                Spawn(spawn_character_grid(asset_server, texture_atlases, &save)),
                 Spawn(widget::button("Back", back)),
             )),
         ))),
@@ -62,6 +68,7 @@ fn back(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
 fn spawn_character_grid(
     asset_server: Res<AssetServer>,
     texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    save: &SaveData,
 ) -> impl Bundle {
     (
         Node {
@@ -75,13 +82,14 @@ fn spawn_character_grid(
             row_gap: Px(20.0),
             ..default()
         },
-        spawn_character_cards(asset_server, texture_atlases),
+        spawn_character_cards(asset_server, texture_atlases, save),
     )
 }
 
 fn spawn_character_cards(
     asset_server: Res<AssetServer>,
     mut texture_atlases: ResMut<Assets<TextureAtlasLayout>>,
+    save: &SaveData,
 ) -> impl Bundle {
     let assets: Vec<_> = Characters::all()
         .into_iter()
@@ -90,13 +98,14 @@ fn spawn_character_cards(
             let layout = c.get_texture_atlas();
             let atlas_handle = texture_atlases.add(layout);
             let idle = c.get_idle_indicies();
+            let unlocked = save.unlocked_characters.contains(&c);
 
-            (c, texture, atlas_handle, idle)
+            (c, texture, atlas_handle, idle, unlocked)
         })
         .collect();
 
     Children::spawn(SpawnIter(assets.into_iter().map(
-        |(character, texture, atlas, idle)| {
+        |(character, texture, atlas, idle, unlocked)| {
             (
                 Node {
                     width: Percent(100.0),
@@ -141,7 +150,7 @@ fn spawn_character_cards(
                         },
                         TextLayout::new_with_justify(Justify::Center),
                         character,
-                        widget::label("Select"),
+                        widget::label(if unlocked { "Select" } else { "Locked" }),
                         BackgroundColor(BUTTON_BACKGROUND.into()),
                         InteractionPalette {
                             none: BUTTON_BACKGROUND.into(),
@@ -149,6 +158,7 @@ fn spawn_character_cards(
                             pressed: BUTTON_PRESSED_BACKGROUND.into(),
                         },
                         Button,
+                        AutoDirectionalNavigation::default(),
                         observe(select),
                     )),
                 )),
@@ -162,12 +172,15 @@ fn select(
     mut commands: Commands,
     mut next_screen: ResMut<NextState<Screen>>,
     character: Query<&Characters>,
+    save: Res<SaveData>,
 ) {
     let selected_character = trigger.entity;
-    if let Ok(character) = character.get(selected_character) {
+    if let Ok(character) = character.get(selected_character)
+        && save.unlocked_characters.contains(character)
+    {
         commands.spawn((Player, *character));
 
-        // Transition to the gameplay
-        next_screen.set(Screen::Gameplay);
+        // Pick a quest in the hub before the run starts
+        next_screen.set(Screen::Hub);
     }
 }
@@ -4,13 +4,39 @@ use bevy::prelude::*;
 
 use crate::{
     AssetStates,
+    gameplay::{replay::replay_last_run, rng::RunSeed},
     menus::Menu,
     screens::Screen,
     theme::{palette::SCREEN_BACKGROUND, widget},
 };
 
+/// Enough digits for any `u64` seed.
+const MAX_SEED_DIGITS: usize = 19;
+
 pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SeedEntry>();
     app.add_systems(OnEnter(Menu::Main), spawn_main_menu);
+    app.add_systems(OnExit(Menu::Main), commit_seed_entry);
+    app.add_systems(
+        Update,
+        (
+            type_seed.run_if(in_state(Menu::Main).and(is_editing_seed)),
+            update_seed_label.run_if(in_state(Menu::Main)),
+        )
+            .chain(),
+    );
+}
+
+/// The digits typed into the seed button, `None` while not editing.
+#[derive(Resource, Default, Debug)]
+struct SeedEntry(Option<String>);
+
+/// The button showing the seed of the next run.
+#[derive(Component)]
+struct SeedLabel;
+
+fn is_editing_seed(entry: Res<SeedEntry>) -> bool {
+    entry.0.is_some()
 }
 
 fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
@@ -31,6 +57,8 @@ fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                 ImageNode::new(asset_server.load("splash_bs.png",)),
             ),
             widget::button("Play", start),
+            widget::button("Replay", replay),
+            widget::button_medium("", edit_seed, SeedLabel),
             widget::button("Settings", open_settings_menu),
             widget::button("Exit", exit_app),
         ],
@@ -49,6 +77,14 @@ fn start(
     }
 }
 
+/// Watch the last run again, once the shared assets are loaded.
+fn replay(_: On<Pointer<Click>>, asset_state: Res<State<AssetStates>>, mut commands: Commands) {
+    match asset_state.get() {
+        AssetStates::AssetLoading => info!("Still loading, try the replay again in a moment"),
+        AssetStates::Next => commands.run_system_cached(replay_last_run),
+    }
+}
+
 fn open_settings_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Settings);
 }
@@ -56,3 +92,91 @@ fn open_settings_menu(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Men
 fn exit_app(_: On<Pointer<Click>>, mut app_exit: MessageWriter<AppExit>) {
     app_exit.write(AppExit::Success);
 }
+
+/// Start typing a seed, or confirm the typed one.
+fn edit_seed(_: On<Pointer<Click>>, mut entry: ResMut<SeedEntry>, mut seed: ResMut<RunSeed>) {
+    match entry.0.take() {
+        Some(digits) => seed.fixed = digits.parse().ok(),
+        None => entry.0 = Some(seed.fixed.map(|seed| seed.to_string()).unwrap_or_default()),
+    }
+}
+
+fn type_seed(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut entry: ResMut<SeedEntry>,
+    mut seed: ResMut<RunSeed>,
+) {
+    let Some(digits) = entry.0.as_mut() else {
+        return;
+    };
+
+    for key in keyboard.get_just_pressed() {
+        match key {
+            KeyCode::Backspace => {
+                digits.pop();
+            }
+            KeyCode::Enter | KeyCode::NumpadEnter => {
+                seed.fixed = digits.parse().ok();
+                entry.0 = None;
+                return;
+            }
+            KeyCode::Escape => {
+                entry.0 = None;
+                return;
+            }
+            key => {
+                if let Some(digit) = key_digit(*key)
+                    && digits.len() < MAX_SEED_DIGITS
+                {
+                    digits.push(digit);
+                }
+            }
+        }
+    }
+}
+
+fn key_digit(key: KeyCode) -> Option<char> {
+    let digit = match key {
+        KeyCode::Digit0 | KeyCode::Numpad0 => 0,
+        KeyCode::Digit1 | KeyCode::Numpad1 => 1,
+        KeyCode::Digit2 | KeyCode::Numpad2 => 2,
+        KeyCode::Digit3 | KeyCode::Numpad3 => 3,
+        KeyCode::Digit4 | KeyCode::Numpad4 => 4,
+        KeyCode::Digit5 | KeyCode::Numpad5 => 5,
+        KeyCode::Digit6 | KeyCode::Numpad6 => 6,
+        KeyCode::Digit7 | KeyCode::Numpad7 => 7,
+        KeyCode::Digit8 | KeyCode::Numpad8 => 8,
+        KeyCode::Digit9 | KeyCode::Numpad9 => 9,
+        _ => return None,
+    };
+    char::from_digit(digit, 10)
+}
+
+/// Keep a typed seed when leaving the menu without confirming it, e.g. by pressing play.
+fn commit_seed_entry(mut entry: ResMut<SeedEntry>, mut seed: ResMut<RunSeed>) {
+    if let Some(digits) = entry.0.take() {
+        seed.fixed = digits.parse().ok();
+    }
+}
+
+fn update_seed_label(
+    entry: Res<SeedEntry>,
+    seed: Res<RunSeed>,
+    label_q: Query<Entity, With<SeedLabel>>,
+    children_q: Query<&Children>,
+    mut text_q: Query<&mut Text>,
+) {
+    let value = match (&entry.0, seed.fixed) {
+        (Some(digits), _) => format!("Seed: {digits}_"),
+        (None, Some(seed)) => format!("Seed: {seed}"),
+        (None, None) => "Seed: Random".to_string(),
+    };
+
+    for entity in &label_q {
+        let mut texts = text_q
+            .iter_many_mut(std::iter::once(entity).chain(children_q.iter_descendants(entity)));
+        while let Some(mut text) = texts.fetch_next() {
+            text.set_if_neq(Text(value.clone()));
+        }
+    }
+}
@@ -2,7 +2,9 @@
 
 mod character_selection;
 mod main;
+mod navigation;
 mod pause;
+mod quests;
 mod settings;
 
 use bevy::prelude::*;
@@ -14,7 +16,9 @@ pub(super) fn plugin(app: &mut App) {
         main::plugin,
         settings::plugin,
         pause::plugin,
+        quests::plugin,
         character_selection::plugin,
+        navigation::plugin,
     ));
 }
 
@@ -26,4 +30,5 @@ pub enum Menu {
     Pause,
     Settings,
     CharacterSelection,
+    QuestBoard,
 }
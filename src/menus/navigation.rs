@@ -0,0 +1,178 @@
+//! Keyboard and gamepad navigation for menus and overlays.
+//!
+//! Focusable widgets are marked with [`AutoDirectionalNavigation`]. Confirming
+//! sends a [`Pointer<Click>`] to the focused entity, so the same observers
+//! handle mouse, keyboard and gamepad input.
+
+use std::time::Duration;
+
+use bevy::{
+    camera::NormalizedRenderTarget,
+    input_focus::{InputFocus, directional_navigation::DirectionalNavigationPlugin},
+    math::CompassOctant,
+    picking::{backend::HitData, pointer::Location, pointer::PointerId},
+    prelude::*,
+    ui::auto_directional_navigation::{AutoDirectionalNavigation, AutoDirectionalNavigator},
+};
+use bevy_enhanced_input::prelude::*;
+use bevy_enhanced_input::{action::Action, actions};
+
+use crate::{controls::Rebinding, gameplay::overlays::Overlay, menus::Menu, screens::Screen};
+
+/// Interval between focus steps while a direction is held.
+const NAVIGATION_REPEAT: f32 = 0.2;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<InputFocus>();
+    app.add_plugins(DirectionalNavigationPlugin);
+    app.add_input_context::<MenuNavigation>();
+
+    app.add_systems(Startup, spawn_menu_navigation);
+    app.add_systems(Update, toggle_menu_navigation);
+
+    app.add_observer(navigate);
+    app.add_observer(confirm);
+    app.add_observer(go_back);
+}
+
+/// Input context for moving focus through menus.
+/// Only active while a [`Menu`] or [`Overlay`] is open.
+#[derive(Component)]
+pub(crate) struct MenuNavigation;
+
+#[derive(InputAction)]
+#[action_output(Vec2)]
+pub(crate) struct Navigate;
+
+#[derive(InputAction)]
+#[action_output(bool)]
+pub(crate) struct Confirm;
+
+#[derive(InputAction)]
+#[action_output(bool)]
+pub(crate) struct Back;
+
+fn spawn_menu_navigation(mut commands: Commands) {
+    commands.spawn((
+        Name::new("Menu Navigation"),
+        MenuNavigation,
+        ContextActivity::<MenuNavigation>::INACTIVE,
+        actions!(MenuNavigation[
+            (
+                Action::<Navigate>::new(),
+                Pulse::new(NAVIGATION_REPEAT),
+                Bindings::spawn((
+                    Cardinal::arrows(),
+                    Cardinal::dpad(),
+                    Axial::left_stick(),
+                )),
+            ),
+            (
+                Action::<Confirm>::new(),
+                bindings![KeyCode::Enter, KeyCode::Space, GamepadButton::South],
+            ),
+            (
+                Action::<Back>::new(),
+                bindings![GamepadButton::East],
+            ),
+        ]),
+    ));
+}
+
+fn toggle_menu_navigation(
+    mut commands: Commands,
+    menu: Res<State<Menu>>,
+    overlay: Res<State<Overlay>>,
+    rebinding: Res<Rebinding>,
+    context: Single<(Entity, &ContextActivity<MenuNavigation>)>,
+) {
+    let (entity, activity) = context.into_inner();
+    let active =
+        (*menu.get() != Menu::None || *overlay.get() != Overlay::None) && rebinding.0.is_none();
+
+    if **activity != active {
+        commands
+            .entity(entity)
+            .insert(ContextActivity::<MenuNavigation>::new(active));
+    }
+}
+
+fn navigate(
+    trigger: On<Fire<Navigate>>,
+    mut navigator: AutoDirectionalNavigator,
+    focusable: Query<
+        (Entity, &UiGlobalTransform, &InheritedVisibility),
+        With<AutoDirectionalNavigation>,
+    >,
+) {
+    let focus = navigator.manual_directional_navigation.focus.0;
+    if focus.is_none_or(|entity| !focusable.contains(entity)) {
+        // Nothing focused yet, start at the top left widget.
+        let first = focusable
+            .iter()
+            .filter(|(.., visibility)| visibility.get())
+            .min_by(|(_, a, _), (_, b, _)| {
+                let (a, b) = (a.translation, b.translation);
+                a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x))
+            });
+        if let Some((entity, ..)) = first {
+            navigator.manual_directional_navigation.focus.set(entity);
+        }
+        return;
+    }
+
+    let Ok(direction) = Dir2::new(trigger.value) else {
+        return;
+    };
+    let _ = navigator.navigate(CompassOctant::from(direction));
+}
+
+fn confirm(
+    _: On<Start<Confirm>>,
+    input_focus: Res<InputFocus>,
+    focusable: Query<(), With<AutoDirectionalNavigation>>,
+    mut commands: Commands,
+) {
+    let Some(entity) = input_focus.0.filter(|entity| focusable.contains(*entity)) else {
+        return;
+    };
+
+    commands.trigger(Pointer::<Click> {
+        entity,
+        pointer_id: PointerId::Mouse,
+        pointer_location: Location {
+            target: NormalizedRenderTarget::None {
+                width: 0,
+                height: 0,
+            },
+            position: Vec2::ZERO,
+        },
+        event: Click {
+            button: PointerButton::Primary,
+            hit: HitData {
+                camera: Entity::PLACEHOLDER,
+                depth: 0.0,
+                position: None,
+                normal: None,
+            },
+            duration: Duration::ZERO,
+        },
+    });
+}
+
+/// Gamepad counterpart of the Escape handling in the individual menus.
+fn go_back(
+    _: On<Start<Back>>,
+    menu: Res<State<Menu>>,
+    screen: Res<State<Screen>>,
+    mut next_menu: ResMut<NextState<Menu>>,
+) {
+    match menu.get() {
+        Menu::Pause => next_menu.set(Menu::None),
+        Menu::Settings if *screen.get() == Screen::Title => next_menu.set(Menu::Main),
+        Menu::Settings => next_menu.set(Menu::Pause),
+        Menu::CharacterSelection => next_menu.set(Menu::Main),
+        Menu::QuestBoard => next_menu.set(Menu::None),
+        Menu::None | Menu::Main => {}
+    }
+}
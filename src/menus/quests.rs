@@ -0,0 +1,68 @@
+//! The quest picker, opened at the hub's quest board.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    gameplay::hub::{Quest, SelectedQuest},
+    menus::Menu,
+    theme::widget,
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Menu::QuestBoard), spawn_quest_menu);
+    app.add_systems(
+        Update,
+        go_back.run_if(in_state(Menu::QuestBoard).and(input_just_pressed(KeyCode::Escape))),
+    );
+}
+
+fn spawn_quest_menu(mut commands: Commands, selected_quest: Res<SelectedQuest>) {
+    let accepted_quest = selected_quest.0;
+
+    commands.spawn((
+        widget::ui_root("Quest Menu"),
+        GlobalZIndex(2),
+        DespawnOnExit(Menu::QuestBoard),
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.8)),
+        Children::spawn((
+            Spawn(widget::header("Quest Board")),
+            SpawnIter(Quest::ALL.into_iter().map(move |quest| {
+                let accepted = accepted_quest == Some(quest);
+                (
+                    Name::new(quest.title()),
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: px(4),
+                        ..default()
+                    },
+                    children![
+                        widget::button(
+                            if accepted {
+                                format!("{} *", quest.title())
+                            } else {
+                                quest.title().to_string()
+                            },
+                            move |_: On<Pointer<Click>>,
+                                  mut selected_quest: ResMut<SelectedQuest>,
+                                  mut next_menu: ResMut<NextState<Menu>>| {
+                                selected_quest.0 = Some(quest);
+                                next_menu.set(Menu::None);
+                            },
+                        ),
+                        widget::label(quest.description()),
+                    ],
+                )
+            })),
+            Spawn(widget::button("Back", go_back_on_click)),
+        )),
+    ));
+}
+
+fn go_back_on_click(_: On<Pointer<Click>>, mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::None);
+}
+
+fn go_back(mut next_menu: ResMut<NextState<Menu>>) {
+    next_menu.set(Menu::None);
+}
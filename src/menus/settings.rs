@@ -3,20 +3,34 @@
 //! Additional settings and accessibility options should go here.
 
 use bevy::{input::common_conditions::input_just_pressed, prelude::*};
-use bevy_seedling::prelude::*;
 
-use crate::{menus::Menu, screens::Screen, theme::prelude::*};
+use crate::{
+    controls::{BindingSlot, Control, Controls, Rebinding, button_name, key_name},
+    menus::Menu,
+    screens::Screen,
+    settings::{Settings, VolumeSettings},
+    theme::{palette::BUTTON_TEXT, prelude::*},
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
     app.add_systems(
         Update,
-        go_back.run_if(in_state(Menu::Settings).and(input_just_pressed(KeyCode::Escape))),
+        (
+            go_back.run_if(
+                in_state(Menu::Settings)
+                    .and(input_just_pressed(KeyCode::Escape))
+                    .and(not(is_rebinding)),
+            ),
+            capture_binding.run_if(in_state(Menu::Settings).and(is_rebinding)),
+        )
+            .chain(),
     );
+    app.add_systems(OnExit(Menu::Settings), cancel_rebinding);
 
     app.add_systems(
         Update,
-        update_global_volume_label.run_if(in_state(Menu::Settings)),
+        (update_setting_labels, update_binding_labels).run_if(in_state(Menu::Settings)),
     );
 }
 
@@ -27,7 +41,14 @@ fn spawn_settings_menu(mut commands: Commands) {
         DespawnOnExit(Menu::Settings),
         children![
             widget::header("Settings"),
-            settings_grid(),
+            (
+                Name::new("Settings Columns"),
+                Node {
+                    column_gap: px(60),
+                    ..default()
+                },
+                children![settings_grid(), controls_grid()],
+            ),
             widget::button("Back", go_back_on_click),
         ],
     ));
@@ -40,67 +61,229 @@ fn settings_grid() -> impl Bundle {
             display: Display::Grid,
             row_gap: px(10),
             column_gap: px(30),
-            grid_template_columns: RepeatedGridTrack::px(2, 400.0),
+            grid_template_columns: RepeatedGridTrack::px(2, 220.0),
             ..default()
         },
         children![
-            (
-                widget::label("Master Volume"),
-                Node {
-                    justify_self: JustifySelf::End,
-                    ..default()
-                }
+            setting_label("Master Volume"),
+            volume_widget(Bus::Master),
+            setting_label("Music Volume"),
+            volume_widget(Bus::Music),
+            setting_label("SFX Volume"),
+            volume_widget(Bus::Sfx),
+            setting_label("UI Volume"),
+            volume_widget(Bus::Ui),
+            setting_label("Window Mode"),
+            widget::button_medium("", cycle_window_mode, SettingLabel::WindowMode),
+            setting_label("Resolution"),
+            widget::button_medium("", cycle_resolution, SettingLabel::Resolution),
+            setting_label("Damage Numbers"),
+            widget::button_medium("", toggle_damage_numbers, SettingLabel::DamageNumbers),
+            setting_label("Screen Shake"),
+            widget::plus_minus_bar(
+                SettingLabel::ScreenShake,
+                lower_screen_shake,
+                raise_screen_shake
             ),
-            global_volume_widget(),
+            setting_label("Reduced Flashes"),
+            widget::button_medium("", toggle_reduced_flashes, SettingLabel::ReducedFlashes),
+            setting_label("Aiming"),
+            widget::button_medium("", cycle_aim_mode, SettingLabel::AimMode),
         ],
     )
 }
 
-fn global_volume_widget() -> impl Bundle {
+fn setting_label(text: &'static str) -> impl Bundle {
     (
-        Name::new("Global Volume Widget"),
+        widget::label(text),
         Node {
-            justify_self: JustifySelf::Start,
+            justify_self: JustifySelf::End,
+            align_self: AlignSelf::Center,
             ..default()
         },
-        children![
-            widget::button_small("-", lower_global_volume),
+    )
+}
+
+fn controls_grid() -> impl Bundle {
+    (
+        Name::new("Controls Grid"),
+        Node {
+            display: Display::Grid,
+            row_gap: px(6),
+            column_gap: px(20),
+            grid_template_columns: vec![
+                RepeatedGridTrack::px(1, 160.0),
+                RepeatedGridTrack::px(2, 220.0),
+            ],
+            ..default()
+        },
+        Children::spawn(SpawnIter(Control::ALL.into_iter().map(|control| {
             (
-                Name::new("Current Volume"),
                 Node {
-                    padding: UiRect::horizontal(px(10)),
-                    justify_content: JustifyContent::Center,
+                    display: Display::Contents,
                     ..default()
                 },
-                children![(widget::label(""), GlobalVolumeLabel)],
-            ),
-            widget::button_small("+", raise_global_volume),
-        ],
+                children![
+                    setting_label(control.display_name()),
+                    widget::button_medium(
+                        "",
+                        start_rebinding,
+                        BindingButton {
+                            control,
+                            slot: BindingSlot::Keyboard,
+                        },
+                    ),
+                    widget::button_medium(
+                        "",
+                        start_rebinding,
+                        BindingButton {
+                            control,
+                            slot: BindingSlot::Gamepad,
+                        },
+                    ),
+                ],
+            )
+        }))),
+    )
+}
+
+/// An audio bus with its own volume setting.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+enum Bus {
+    Master,
+    Music,
+    Sfx,
+    Ui,
+}
+
+impl Bus {
+    fn volume(self, volume: &VolumeSettings) -> f32 {
+        match self {
+            Bus::Master => volume.master,
+            Bus::Music => volume.music,
+            Bus::Sfx => volume.sfx,
+            Bus::Ui => volume.ui,
+        }
+    }
+
+    fn volume_mut(self, volume: &mut VolumeSettings) -> &mut f32 {
+        match self {
+            Bus::Master => &mut volume.master,
+            Bus::Music => &mut volume.music,
+            Bus::Sfx => &mut volume.sfx,
+            Bus::Ui => &mut volume.ui,
+        }
+    }
+
+    fn max(self) -> f32 {
+        match self {
+            Bus::Master => MAX_MASTER_VOLUME,
+            Bus::Music | Bus::Sfx | Bus::Ui => MAX_POOL_VOLUME,
+        }
+    }
+}
+
+fn volume_widget(bus: Bus) -> impl Bundle {
+    (
+        Name::new("Volume Widget"),
+        widget::plus_minus_bar(
+            SettingLabel::Volume(bus),
+            move |_: On<Pointer<Click>>, mut settings: ResMut<Settings>| {
+                let volume = bus.volume_mut(&mut settings.volume);
+                *volume = (*volume - VOLUME_STEP).max(MIN_VOLUME);
+            },
+            move |_: On<Pointer<Click>>, mut settings: ResMut<Settings>| {
+                let volume = bus.volume_mut(&mut settings.volume);
+                *volume = (*volume + VOLUME_STEP).min(bus.max());
+            },
+        ),
     )
 }
 
 const MIN_VOLUME: f32 = 0.0;
-const MAX_VOLUME: f32 = 3.0;
+const MAX_MASTER_VOLUME: f32 = 3.0;
+const MAX_POOL_VOLUME: f32 = 2.0;
+const VOLUME_STEP: f32 = 0.1;
+const SCREEN_SHAKE_STEP: f32 = 0.25;
+
+fn cycle_window_mode(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.window.mode = settings.window.mode.next();
+}
 
-fn lower_global_volume(_: On<Pointer<Click>>, mut master: Single<&mut VolumeNode, With<MainBus>>) {
-    let linear = (master.volume.linear() - 0.1).max(MIN_VOLUME);
-    master.volume = Volume::Linear(linear);
+fn cycle_resolution(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.window.next_resolution();
 }
 
-fn raise_global_volume(_: On<Pointer<Click>>, mut master: Single<&mut VolumeNode, With<MainBus>>) {
-    let linear = (master.volume.linear() + 0.1).min(MAX_VOLUME);
-    master.volume = Volume::Linear(linear);
+fn toggle_damage_numbers(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.accessibility.damage_numbers ^= true;
 }
+
+fn toggle_reduced_flashes(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.accessibility.reduced_flashes ^= true;
+}
+
+fn cycle_aim_mode(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    settings.gameplay.aim = settings.gameplay.aim.next();
+}
+
+fn lower_screen_shake(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    let shake = &mut settings.accessibility.screen_shake;
+    *shake = (*shake - SCREEN_SHAKE_STEP).max(0.0);
+}
+
+fn raise_screen_shake(_: On<Pointer<Click>>, mut settings: ResMut<Settings>) {
+    let shake = &mut settings.accessibility.screen_shake;
+    *shake = (*shake + SCREEN_SHAKE_STEP).min(1.0);
+}
+
+/// A text showing the current value of a setting.
 #[derive(Component, Reflect)]
 #[reflect(Component)]
-struct GlobalVolumeLabel;
+enum SettingLabel {
+    Volume(Bus),
+    WindowMode,
+    Resolution,
+    DamageNumbers,
+    ScreenShake,
+    ReducedFlashes,
+    AimMode,
+}
 
-fn update_global_volume_label(
-    master: Single<&mut VolumeNode, With<MainBus>>,
-    mut label: Single<&mut Text, With<GlobalVolumeLabel>>,
+fn on_off(enabled: bool) -> String {
+    if enabled { "On" } else { "Off" }.to_string()
+}
+
+/// Update [`SettingLabel`] texts, and the texts of buttons marked with a [`SettingLabel`].
+fn update_setting_labels(
+    settings: Res<Settings>,
+    label_q: Query<(Entity, &SettingLabel)>,
+    children_q: Query<&Children>,
+    mut text_q: Query<&mut Text>,
 ) {
-    let percent = 100.0 * master.volume.linear();
-    label.0 = format!("{percent:3.0}%");
+    for (entity, label) in &label_q {
+        let value = match label {
+            SettingLabel::Volume(bus) => {
+                format!("{:3.0}%", 100.0 * bus.volume(&settings.volume))
+            }
+            SettingLabel::WindowMode => settings.window.mode.display_name().to_string(),
+            SettingLabel::Resolution => {
+                let (width, height) = settings.window.resolution;
+                format!("{width}x{height}")
+            }
+            SettingLabel::DamageNumbers => on_off(settings.accessibility.damage_numbers),
+            SettingLabel::ScreenShake => {
+                format!("{:3.0}%", 100.0 * settings.accessibility.screen_shake)
+            }
+            SettingLabel::ReducedFlashes => on_off(settings.accessibility.reduced_flashes),
+            SettingLabel::AimMode => settings.gameplay.aim.display_name().to_string(),
+        };
+
+        let mut texts = text_q
+            .iter_many_mut(std::iter::once(entity).chain(children_q.iter_descendants(entity)));
+        while let Some(mut text) = texts.fetch_next() {
+            text.set_if_neq(Text(value.clone()));
+        }
+    }
 }
 
 fn go_back_on_click(
@@ -122,3 +305,103 @@ fn go_back(screen: Res<State<Screen>>, mut next_menu: ResMut<NextState<Menu>>) {
         Menu::Pause
     });
 }
+
+/// A button showing and rebinding one slot of a [`Control`].
+#[derive(Component)]
+struct BindingButton {
+    control: Control,
+    slot: BindingSlot,
+}
+
+fn is_rebinding(rebinding: Res<Rebinding>) -> bool {
+    rebinding.0.is_some()
+}
+
+fn start_rebinding(
+    trigger: On<Pointer<Click>>,
+    button_q: Query<&BindingButton>,
+    mut rebinding: ResMut<Rebinding>,
+) {
+    if let Ok(button) = button_q.get(trigger.entity) {
+        rebinding.0 = Some((button.control, button.slot));
+    }
+}
+
+fn cancel_rebinding(mut rebinding: ResMut<Rebinding>) {
+    rebinding.0 = None;
+}
+
+/// Assign the next pressed key or gamepad button. Escape cancels.
+fn capture_binding(
+    mut rebinding: ResMut<Rebinding>,
+    mut controls: ResMut<Controls>,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+) {
+    // Skip the frame the rebinding started, so the confirming input is not captured.
+    if rebinding.is_changed() {
+        return;
+    }
+    let Some((control, slot)) = rebinding.0 else {
+        return;
+    };
+
+    if keyboard.just_pressed(KeyCode::Escape) {
+        rebinding.0 = None;
+        return;
+    }
+
+    match slot {
+        BindingSlot::Keyboard => {
+            if let Some(key) = keyboard.get_just_pressed().next() {
+                if controls.key(control) != Some(*key) {
+                    controls.keys.insert(control, *key);
+                }
+                rebinding.0 = None;
+            }
+        }
+        BindingSlot::Gamepad => {
+            let pressed = gamepads
+                .iter()
+                .find_map(|gamepad| gamepad.get_just_pressed().next().copied());
+            if let Some(button) = pressed {
+                if controls.button(control) != Some(button) {
+                    controls.gamepad.insert(control, button);
+                }
+                rebinding.0 = None;
+            }
+        }
+    }
+}
+
+/// Show the current binding of each [`BindingButton`]. Conflicting bindings are red.
+fn update_binding_labels(
+    controls: Res<Controls>,
+    rebinding: Res<Rebinding>,
+    button_q: Query<(&BindingButton, &Children)>,
+    mut text_q: Query<(&mut Text, &mut TextColor)>,
+) {
+    let conflicts = controls.conflicts();
+
+    for (button, children) in &button_q {
+        let label = if rebinding.0 == Some((button.control, button.slot)) {
+            "...".to_string()
+        } else {
+            match button.slot {
+                BindingSlot::Keyboard => key_name(controls.key(button.control)),
+                BindingSlot::Gamepad => button_name(controls.button(button.control)),
+            }
+        };
+        let color = if conflicts.contains(&button.control) {
+            Color::srgb(0.9, 0.2, 0.2)
+        } else {
+            BUTTON_TEXT.into()
+        };
+
+        let mut texts = text_q.iter_many_mut(children);
+        while let Some((mut text, mut text_color)) = texts.fetch_next() {
+            text.set_if_neq(Text(label.clone()));
+            text_color.set_if_neq(TextColor(color));
+        }
+    }
+}
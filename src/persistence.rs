@@ -0,0 +1,85 @@
+//! Reading and writing RON files in the platform config directory.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Serialize, de::DeserializeOwned};
+use thiserror::Error;
+
+const APP_DIR: &str = "bevy_survivors";
+
+#[derive(Debug, Error)]
+pub(crate) enum PersistenceError {
+    #[error("Could not read or write file: {0}")]
+    Io(#[from] io::Error),
+    #[error("Could not parse RON: {0}")]
+    RonSpannedError(#[from] serde_ron::error::SpannedError),
+    #[error("Could not write RON: {0}")]
+    RonError(#[from] serde_ron::Error),
+    #[error("File version {found} is newer than the supported version {supported}")]
+    UnsupportedVersion { found: u32, supported: u32 },
+}
+
+/// The directory our files are stored in, e.g. `~/.config/bevy_survivors` on Linux.
+///
+/// Returns `None` on platforms without a file system, such as the web.
+pub(crate) fn config_dir() -> Option<PathBuf> {
+    #[cfg(target_arch = "wasm32")]
+    return None;
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let env = |key| std::env::var_os(key).filter(|value| !value.is_empty());
+
+        let base = if cfg!(target_os = "windows") {
+            env("APPDATA").map(PathBuf::from)
+        } else if cfg!(target_os = "macos") {
+            env("HOME").map(|home| PathBuf::from(home).join("Library/Application Support"))
+        } else {
+            env("XDG_CONFIG_HOME")
+                .map(PathBuf::from)
+                .or_else(|| env("HOME").map(|home| PathBuf::from(home).join(".config")))
+        };
+
+        base.map(|base| base.join(APP_DIR))
+    }
+}
+
+/// Path of `file_name` inside the [`config_dir`].
+pub(crate) fn config_path(file_name: &str) -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(file_name))
+}
+
+/// Read a text file. Returns `Ok(None)` if the file does not exist yet.
+pub(crate) fn load_string(path: &Path) -> Result<Option<String>, PersistenceError> {
+    match fs::read_to_string(path) {
+        Ok(text) => Ok(Some(text)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Load a RON file. Returns `Ok(None)` if the file does not exist yet.
+pub(crate) fn load_ron<T: DeserializeOwned>(path: &Path) -> Result<Option<T>, PersistenceError> {
+    load_string(path)?
+        .map(|text| serde_ron::from_str(&text).map_err(Into::into))
+        .transpose()
+}
+
+/// Write a text file, creating parent directories as needed.
+pub(crate) fn save_string(path: &Path, text: &str) -> Result<(), PersistenceError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    fs::write(path, text)?;
+    Ok(())
+}
+
+/// Write `value` as pretty RON, creating parent directories as needed.
+pub(crate) fn save_ron<T: Serialize>(path: &Path, value: &T) -> Result<(), PersistenceError> {
+    let text = serde_ron::ser::to_string_pretty(value, serde_ron::ser::PrettyConfig::default())?;
+    save_string(path, &text)
+}
@@ -0,0 +1,269 @@
+//! Meta-progression that survives between runs, persisted to `save.ron` in the config directory.
+//!
+//! The save is loaded when the title screen is first entered and written whenever a run ends.
+//! Every save file starts with its `version`, saves of a newer version are rejected on load.
+
+use std::path::Path;
+
+use bevy::{
+    platform::collections::{HashMap, HashSet},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    PausableSystems,
+    gameplay::{enemy::EnemyDeathEvent, player::characters::Characters, weapons::kind::WeaponKind},
+    persistence::{self, PersistenceError},
+    screens::Screen,
+};
+
+const SAVE_FILE: &str = "save.ron";
+
+/// Version written by [`SaveData::save`]. Bump it and add a migration when the layout changes.
+pub(crate) const SAVE_VERSION: u32 = 2;
+
+/// Currency earned per enemy killed.
+const CURRENCY_PER_KILL: u32 = 1;
+/// Seconds survived per currency earned.
+const SECONDS_PER_CURRENCY: f32 = 10.0;
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<SaveData>();
+    app.init_resource::<CurrentRun>();
+
+    app.add_systems(OnEnter(Screen::Title), load_save.run_if(run_once));
+    app.add_systems(OnEnter(Screen::Gameplay), reset_current_run);
+    app.add_systems(
+        Update,
+        track_run_time
+            .run_if(in_state(Screen::Gameplay))
+            .in_set(PausableSystems),
+    );
+    app.add_systems(OnExit(Screen::Gameplay), finish_run);
+
+    app.add_observer(count_kill);
+}
+
+/// Everything that is kept between runs.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub(crate) struct SaveData {
+    pub version: u32,
+    /// Spent on permanent upgrades.
+    pub currency: u32,
+    pub unlocked_characters: HashSet<Characters>,
+    /// Weapons never offered. Locked rather than unlocked ones are stored, so weapons added
+    /// to the game are available with older saves.
+    pub locked_weapons: HashSet<WeaponKind>,
+    /// Bought ranks of each permanent upgrade.
+    pub upgrades: HashMap<Upgrade, u32>,
+    pub stats: LifetimeStats,
+}
+
+impl Default for SaveData {
+    fn default() -> Self {
+        Self {
+            version: SAVE_VERSION,
+            currency: 0,
+            unlocked_characters: Characters::all().into_iter().collect(),
+            locked_weapons: HashSet::default(),
+            upgrades: HashMap::default(),
+            stats: LifetimeStats::default(),
+        }
+    }
+}
+
+impl SaveData {
+    pub fn upgrade_rank(&self, upgrade: Upgrade) -> u32 {
+        self.upgrades.get(&upgrade).copied().unwrap_or_default()
+    }
+
+    /// Multiplier for the base value affected by `upgrade`.
+    pub fn upgrade_multiplier(&self, upgrade: Upgrade) -> f32 {
+        1.0 + upgrade.bonus_per_rank() * self.upgrade_rank(upgrade) as f32
+    }
+
+    /// Load a save. Returns `Ok(None)` if there is no save yet.
+    pub fn load(path: &Path) -> Result<Option<Self>, PersistenceError> {
+        persistence::load_string(path)?
+            .map(|text| Self::from_ron(&text))
+            .transpose()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), PersistenceError> {
+        persistence::save_ron(path, self)
+    }
+
+    fn from_ron(text: &str) -> Result<Self, PersistenceError> {
+        let header: SaveHeader = serde_ron::from_str(text)?;
+
+        match header.version {
+            SAVE_VERSION => Ok(serde_ron::from_str(text)?),
+            // Version 1 stored `unlocked_weapons`, but nothing locked weapons yet.
+            1 => Ok(Self {
+                version: SAVE_VERSION,
+                ..serde_ron::from_str::<Self>(text)?
+            }),
+            found => Err(PersistenceError::UnsupportedVersion {
+                found,
+                supported: SAVE_VERSION,
+            }),
+        }
+    }
+
+    /// Fold a finished run into the save.
+    fn record_run(&mut self, run: &CurrentRun) {
+        let earned = run.kills * CURRENCY_PER_KILL + (run.time / SECONDS_PER_CURRENCY) as u32;
+        self.currency += earned;
+
+        let stats = &mut self.stats;
+        stats.runs += 1;
+        stats.total_kills += run.kills;
+        stats.total_time += run.time;
+        stats.best_time = stats.best_time.max(run.time);
+        stats.most_kills = stats.most_kills.max(run.kills);
+    }
+}
+
+/// Only the version, to decide how to parse the rest of the file.
+#[derive(Deserialize)]
+struct SaveHeader {
+    version: u32,
+}
+
+/// Permanent upgrades bought with currency.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub(crate) enum Upgrade {
+    MoveSpeed,
+    Damage,
+}
+
+impl Upgrade {
+    /// Added per rank, as a fraction of the base value.
+    pub fn bonus_per_rank(self) -> f32 {
+        match self {
+            Upgrade::MoveSpeed => 0.05,
+            Upgrade::Damage => 0.1,
+        }
+    }
+}
+
+/// Totals over all finished runs.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(default)]
+pub(crate) struct LifetimeStats {
+    pub runs: u32,
+    pub total_kills: u32,
+    /// In seconds.
+    pub total_time: f32,
+    /// Longest run in seconds.
+    pub best_time: f32,
+    pub most_kills: u32,
+}
+
+/// Progress of the run in progress, folded into the [`SaveData`] when it ends.
+#[derive(Resource, Default, Debug)]
+pub(crate) struct CurrentRun {
+    pub kills: u32,
+    /// Unpaused seconds since the run started.
+    pub time: f32,
+}
+
+fn load_save(mut save: ResMut<SaveData>) {
+    let Some(path) = persistence::config_path(SAVE_FILE) else {
+        return;
+    };
+
+    match SaveData::load(&path) {
+        Ok(Some(loaded)) => *save = loaded,
+        Ok(None) => {}
+        Err(err) => warn!("Failed to load save, starting fresh: {err}"),
+    }
+}
+
+fn reset_current_run(mut run: ResMut<CurrentRun>) {
+    *run = CurrentRun::default();
+}
+
+fn track_run_time(time: Res<Time>, mut run: ResMut<CurrentRun>) {
+    run.time += time.delta_secs();
+}
+
+fn count_kill(_: On<EnemyDeathEvent>, mut run: ResMut<CurrentRun>) {
+    run.kills += 1;
+}
+
+fn finish_run(run: Res<CurrentRun>, mut save: ResMut<SaveData>) {
+    save.record_run(&run);
+
+    let Some(path) = persistence::config_path(SAVE_FILE) else {
+        return;
+    };
+
+    if let Err(err) = save.save(&path) {
+        warn!("Failed to write save: {err}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn temp_save_path(name: &str) -> PathBuf {
+        std::env::temp_dir()
+            .join(format!("bevy_survivors_test_{}", std::process::id()))
+            .join(name)
+    }
+
+    #[test]
+    fn save_round_trips() {
+        let path = temp_save_path("round_trip.ron");
+
+        let mut save = SaveData::default();
+        save.unlocked_characters.remove(&Characters::Knight);
+        save.upgrades.insert(Upgrade::Damage, 3);
+        save.record_run(&CurrentRun {
+            kills: 42,
+            time: 95.0,
+        });
+
+        save.save(&path).expect("save should be written");
+        let loaded = SaveData::load(&path)
+            .expect("save should be readable")
+            .expect("save should exist");
+        let _ = std::fs::remove_file(&path);
+
+        assert_eq!(loaded, save);
+        assert_eq!(loaded.currency, 42 + 9);
+        assert_eq!(loaded.upgrade_rank(Upgrade::Damage), 3);
+        assert_eq!(loaded.stats.runs, 1);
+    }
+
+    #[test]
+    fn missing_save_is_none() {
+        let path = temp_save_path("missing.ron");
+        assert_eq!(SaveData::load(&path).unwrap(), None);
+    }
+
+    #[test]
+    fn weapons_are_available_with_version_1_saves() {
+        let save =
+            SaveData::from_ron("(version: 1, currency: 5, unlocked_weapons: [Orb, Fireball])")
+                .expect("version 1 saves should load");
+
+        assert_eq!(save.version, SAVE_VERSION);
+        assert_eq!(save.currency, 5);
+        assert!(save.locked_weapons.is_empty());
+    }
+
+    #[test]
+    fn rejects_newer_save() {
+        let result = SaveData::from_ron(&format!("(version: {})", SAVE_VERSION + 1));
+        assert!(matches!(
+            result,
+            Err(PersistenceError::UnsupportedVersion { .. })
+        ));
+    }
+}
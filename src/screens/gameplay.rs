@@ -4,6 +4,7 @@ use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 
 use crate::{
     Pause,
+    controls::{Control, control_just_pressed},
     gameplay::{level::spawn_level, overlays::Overlay},
     menus::Menu,
     screens::Screen,
@@ -17,19 +18,23 @@ pub(super) fn plugin(app: &mut App) {
         Update,
         (
             (pause, spawn_pause_overlay, open_pause_menu).run_if(
-                in_state(Screen::Gameplay)
-                    .and(in_state(Menu::None))
-                    .and(input_just_pressed(KeyCode::KeyP).or(input_just_pressed(KeyCode::Escape))),
+                in_state(Screen::Gameplay).and(in_state(Menu::None)).and(
+                    control_just_pressed(Control::Pause).or(input_just_pressed(KeyCode::Escape)),
+                ),
             ),
             close_menu.run_if(
                 in_state(Screen::Gameplay)
                     .and(not(in_state(Menu::None)))
-                    .and(input_just_pressed(KeyCode::KeyP)),
+                    .and(control_just_pressed(Control::Pause)),
             ),
         ),
     );
     app.add_systems(OnEnter(Overlay::LevelUp), pause);
     app.add_systems(OnExit(Overlay::LevelUp), (close_menu, unpause));
+    app.add_systems(OnEnter(Overlay::Crafting), pause);
+    app.add_systems(OnExit(Overlay::Crafting), (close_menu, unpause));
+    app.add_systems(OnEnter(Overlay::Chest), pause);
+    app.add_systems(OnExit(Overlay::Chest), (close_menu, unpause));
 
     app.add_systems(OnExit(Screen::Gameplay), (close_menu, unpause));
     app.add_systems(
@@ -38,17 +43,17 @@ pub(super) fn plugin(app: &mut App) {
     );
 }
 
-fn unpause(mut next_pause: ResMut<NextState<Pause>>, mut time: ResMut<Time<Virtual>>) {
+pub(super) fn unpause(mut next_pause: ResMut<NextState<Pause>>, mut time: ResMut<Time<Virtual>>) {
     time.unpause();
     next_pause.set(Pause(false));
 }
 
-fn pause(mut next_pause: ResMut<NextState<Pause>>, mut time: ResMut<Time<Virtual>>) {
+pub(super) fn pause(mut next_pause: ResMut<NextState<Pause>>, mut time: ResMut<Time<Virtual>>) {
     time.pause();
     next_pause.set(Pause(true));
 }
 
-fn spawn_pause_overlay(mut commands: Commands) {
+pub(super) fn spawn_pause_overlay(mut commands: Commands) {
     commands.spawn((
         Name::new("Pause Overlay"),
         Node {
@@ -62,10 +67,10 @@ fn spawn_pause_overlay(mut commands: Commands) {
     ));
 }
 
-fn open_pause_menu(mut next_menu: ResMut<NextState<Menu>>) {
+pub(super) fn open_pause_menu(mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::Pause);
 }
 
-fn close_menu(mut next_menu: ResMut<NextState<Menu>>) {
+pub(super) fn close_menu(mut next_menu: ResMut<NextState<Menu>>) {
     next_menu.set(Menu::None);
 }
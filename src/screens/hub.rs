@@ -0,0 +1,45 @@
+//! The village hub screen between character selection and a run.
+
+use bevy::{input::common_conditions::input_just_pressed, prelude::*};
+
+use crate::{
+    controls::{Control, control_just_pressed},
+    gameplay::hub::{SelectedQuest, spawn_hub},
+    menus::Menu,
+    screens::{
+        Screen,
+        gameplay::{close_menu, open_pause_menu, pause, spawn_pause_overlay, unpause},
+    },
+};
+
+pub(super) fn plugin(app: &mut App) {
+    app.add_systems(OnEnter(Screen::Hub), (spawn_hub, reset_selected_quest));
+
+    app.add_systems(
+        Update,
+        (pause, spawn_pause_overlay, open_pause_menu).run_if(
+            in_state(Screen::Hub)
+                .and(in_state(Menu::None))
+                .and(control_just_pressed(Control::Pause).or(input_just_pressed(KeyCode::Escape))),
+        ),
+    );
+    app.add_systems(OnEnter(Menu::QuestBoard), pause);
+    app.add_systems(OnEnter(Menu::None), unpause.run_if(in_state(Screen::Hub)));
+    app.add_systems(OnExit(Screen::Hub), (close_menu, unpause));
+
+    // The player and their weapons only outlive the hub when travelling to a level.
+    app.add_systems(OnEnter(Screen::Title), despawn_run_entities);
+}
+
+fn reset_selected_quest(mut selected_quest: ResMut<SelectedQuest>) {
+    selected_quest.0 = None;
+}
+
+/// Despawn entities that were spawned for a run which never started.
+fn despawn_run_entities(mut commands: Commands, entity_q: Query<(Entity, &DespawnOnExit<Screen>)>) {
+    for (entity, despawn_on_exit) in &entity_q {
+        if despawn_on_exit.0 == Screen::Gameplay {
+            commands.entity(entity).despawn();
+        }
+    }
+}
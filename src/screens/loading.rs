@@ -3,14 +3,25 @@
 
 use bevy::prelude::*;
 
-use crate::{AssetStates, screens::Screen, theme::prelude::*};
+use crate::{
+    AssetStates,
+    gameplay::level::{CurrentLevel, load_selected_level},
+    screens::Screen,
+    theme::prelude::*,
+};
 
 pub(super) fn plugin(app: &mut App) {
     app.add_systems(OnEnter(Screen::Loading), spawn_loading_screen);
 
     app.add_systems(
         Update,
-        enter_gameplay_screen.run_if(in_state(AssetStates::Next).and(in_state(Screen::Loading))),
+        (
+            load_selected_level.run_if(not(resource_exists::<CurrentLevel>)),
+            return_to_hub.run_if(level_failed),
+            enter_gameplay_screen.run_if(level_loaded),
+        )
+            .chain()
+            .run_if(in_state(AssetStates::Next).and(in_state(Screen::Loading))),
     );
 }
 
@@ -22,6 +33,28 @@ fn spawn_loading_screen(mut commands: Commands) {
     ));
 }
 
+/// Only the chosen level's map and music are loaded, after the shared assets.
+fn level_loaded(level: Option<Res<CurrentLevel>>, asset_server: Res<AssetServer>) -> bool {
+    level.is_some_and(|level| level.is_loaded(&asset_server))
+}
+
+fn level_failed(level: Option<Res<CurrentLevel>>, asset_server: Res<AssetServer>) -> bool {
+    level.is_some_and(|level| level.is_failed(&asset_server))
+}
+
+fn return_to_hub(
+    mut commands: Commands,
+    level: Res<CurrentLevel>,
+    mut next_screen: ResMut<NextState<Screen>>,
+) {
+    error!(
+        "Failed to load the level {}, returning to the hub",
+        level.definition.name
+    );
+    commands.remove_resource::<CurrentLevel>();
+    next_screen.set(Screen::Hub);
+}
+
 fn enter_gameplay_screen(mut next_screen: ResMut<NextState<Screen>>) {
     next_screen.set(Screen::Gameplay);
 }
@@ -1,6 +1,7 @@
 //! The game's main screen states and transitions between them.
 
 mod gameplay;
+mod hub;
 mod loading;
 mod splash;
 mod title;
@@ -12,6 +13,7 @@ pub(super) fn plugin(app: &mut App) {
 
     app.add_plugins((
         gameplay::plugin,
+        hub::plugin,
         loading::plugin,
         splash::plugin,
         title::plugin,
@@ -25,5 +27,6 @@ pub enum Screen {
     Splash,
     Title,
     Loading,
+    Hub,
     Gameplay,
 }
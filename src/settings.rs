@@ -0,0 +1,221 @@
+//! Player settings, persisted to `settings.ron` in the config directory.
+//!
+//! The settings menu only changes the [`Settings`] resource. Systems in this module
+//! and in [`crate::audio`] apply the changes to the window and the audio graph.
+
+use bevy::{
+    prelude::*,
+    window::{MonitorSelection, PrimaryWindow, VideoModeSelection, WindowMode},
+};
+use serde::{Deserialize, Serialize};
+
+use crate::persistence;
+
+const SETTINGS_FILE: &str = "settings.ron";
+
+/// Resolutions offered in the settings menu.
+pub(crate) const RESOLUTIONS: [(u32, u32); 4] =
+    [(1280, 720), (1600, 900), (1920, 1080), (2560, 1440)];
+
+pub(super) fn plugin(app: &mut App) {
+    // Loaded right away, so startup systems like `initialize_audio` can read it.
+    app.insert_resource(load_settings());
+    app.add_systems(
+        Update,
+        (
+            apply_window_settings.run_if(resource_changed::<Settings>),
+            save_settings.run_if(resource_changed::<Settings>.and(not(resource_added::<Settings>))),
+        ),
+    );
+}
+
+#[derive(Resource, Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(default)]
+pub(crate) struct Settings {
+    pub volume: VolumeSettings,
+    pub window: WindowSettings,
+    pub accessibility: AccessibilitySettings,
+    pub gameplay: GameplaySettings,
+}
+
+/// Linear volume of the main bus and scale factors for each sampler pool.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub(crate) struct VolumeSettings {
+    pub master: f32,
+    pub music: f32,
+    pub sfx: f32,
+    pub ui: f32,
+}
+
+impl Default for VolumeSettings {
+    fn default() -> Self {
+        Self {
+            master: crate::audio::DEFAULT_MAIN_VOLUME.linear(),
+            music: 1.0,
+            sfx: 1.0,
+            ui: 1.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub(crate) enum WindowModeSetting {
+    #[default]
+    Windowed,
+    Borderless,
+    Fullscreen,
+}
+
+impl WindowModeSetting {
+    pub fn next(self) -> Self {
+        match self {
+            WindowModeSetting::Windowed => WindowModeSetting::Borderless,
+            WindowModeSetting::Borderless => WindowModeSetting::Fullscreen,
+            WindowModeSetting::Fullscreen => WindowModeSetting::Windowed,
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            WindowModeSetting::Windowed => "Windowed",
+            WindowModeSetting::Borderless => "Borderless",
+            WindowModeSetting::Fullscreen => "Fullscreen",
+        }
+    }
+
+    fn window_mode(self) -> WindowMode {
+        match self {
+            WindowModeSetting::Windowed => WindowMode::Windowed,
+            WindowModeSetting::Borderless => {
+                WindowMode::BorderlessFullscreen(MonitorSelection::Current)
+            }
+            WindowModeSetting::Fullscreen => {
+                WindowMode::Fullscreen(MonitorSelection::Current, VideoModeSelection::Current)
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub(crate) struct WindowSettings {
+    pub mode: WindowModeSetting,
+    /// Logical size of the window. Only used in [`WindowModeSetting::Windowed`].
+    pub resolution: (u32, u32),
+}
+
+impl Default for WindowSettings {
+    fn default() -> Self {
+        Self {
+            mode: WindowModeSetting::default(),
+            resolution: RESOLUTIONS[0],
+        }
+    }
+}
+
+impl WindowSettings {
+    /// Cycle to the next entry of [`RESOLUTIONS`].
+    pub fn next_resolution(&mut self) {
+        let index = RESOLUTIONS
+            .iter()
+            .position(|resolution| *resolution == self.resolution)
+            .map_or(0, |index| (index + 1) % RESOLUTIONS.len());
+        self.resolution = RESOLUTIONS[index];
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub(crate) struct AccessibilitySettings {
+    pub damage_numbers: bool,
+    /// Scale of the camera shake, from `0.0` (off) to `1.0`.
+    pub screen_shake: f32,
+    /// Use softer tints for `hurt_flash` and `root_flash`.
+    pub reduced_flashes: bool,
+}
+
+impl Default for AccessibilitySettings {
+    fn default() -> Self {
+        Self {
+            damage_numbers: true,
+            screen_shake: 1.0,
+            reduced_flashes: false,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Default)]
+#[serde(default)]
+pub(crate) struct GameplaySettings {
+    pub aim: AimMode,
+}
+
+/// Which way melee cones, shots and novas fire.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub(crate) enum AimMode {
+    /// Follow each weapon's targeting.
+    #[default]
+    Auto,
+    /// Follow the mouse cursor or the right stick.
+    Manual,
+}
+
+impl AimMode {
+    pub fn next(self) -> Self {
+        match self {
+            AimMode::Auto => AimMode::Manual,
+            AimMode::Manual => AimMode::Auto,
+        }
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            AimMode::Auto => "Auto",
+            AimMode::Manual => "Manual",
+        }
+    }
+}
+
+fn load_settings() -> Settings {
+    persistence::config_path(SETTINGS_FILE)
+        .map(|path| persistence::load_ron::<Settings>(&path))
+        .transpose()
+        .unwrap_or_else(|err| {
+            warn!("Failed to load settings, using defaults: {err}");
+            None
+        })
+        .flatten()
+        .unwrap_or_default()
+}
+
+fn save_settings(settings: Res<Settings>) {
+    let Some(path) = persistence::config_path(SETTINGS_FILE) else {
+        return;
+    };
+
+    if let Err(err) = persistence::save_ron(&path, &*settings) {
+        warn!("Failed to save settings: {err}");
+    }
+}
+
+fn apply_window_settings(
+    settings: Res<Settings>,
+    mut window: Single<&mut Window, With<PrimaryWindow>>,
+) {
+    // The canvas size on the web is controlled by the page.
+    if cfg!(target_arch = "wasm32") {
+        return;
+    }
+
+    let mode = settings.window.mode.window_mode();
+    if window.mode != mode {
+        window.mode = mode;
+    }
+
+    let (width, height) = settings.window.resolution;
+    let size = Vec2::new(width as f32, height as f32);
+    if settings.window.mode == WindowModeSetting::Windowed && window.resolution.size() != size {
+        window.resolution.set(size.x, size.y);
+    }
+}
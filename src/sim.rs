@@ -0,0 +1,546 @@
+//! A headless simulation of a run, for balance testing.
+//!
+//! [`headless_app`] builds an [`App`] from [`MinimalPlugins`], avian and the gameplay plugins,
+//! without rendering, audio, menus or a Tiled map. A scripted [`MovementPolicy`] steers the
+//! player, every level up takes the first offer and chests are claimed right away. The run is
+//! recorded like a live one, so it can be replayed.
+//!
+//! Used by the `tests/simulation.rs` integration tests and the `simulate` binary. Tests skip
+//! themselves when the assets aren't there, see [`assets_checked_out`]. The weapon behaviour
+//! tests use a variant without the assets, `weapon_test_app`.
+
+use std::{
+    fmt,
+    path::Path,
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+use avian2d::prelude::{Gravity, PhysicsPlugins};
+use bevy::{
+    asset::LoadState, input::InputPlugin, prelude::*, state::app::StatesPlugin,
+    time::TimeUpdateStrategy,
+};
+use bevy_asset_loader::prelude::*;
+use bevy_ecs_tiled::prelude::TiledMapAsset;
+use bevy_enhanced_input::EnhancedInputPlugin;
+use bevy_rand::{plugin::EntropyPlugin, prelude::WyRand};
+use bevy_seedling::sample::AudioSample;
+
+#[cfg(test)]
+use crate::gameplay::{
+    enemy::{Root, walker::WalkerSpawnEvent},
+    replay::InputLog,
+    waves::Wave,
+    weapons::{components::Weapon, systems::attack::WeaponAttack},
+};
+pub use crate::gameplay::{waves::WavePlanKind, weapons::kind::WeaponKind};
+use crate::{
+    AssetStates, GameplaySystems, PausableSystems,
+    controls::{Controls, Rebinding},
+    fixed_update_inspection, gameplay,
+    gameplay::{
+        Health,
+        damage_numbers::DamageAssets,
+        enemy::Enemy,
+        level::{CurrentLevel, DUNGEON, LevelId, definition::LevelDefinition},
+        overlays::{Overlay, chest::claim_now, level_up::pick_first_offer},
+        player::{
+            Level, Player, PlayerAssets, characters::Characters, movement::AccumulatedInput,
+            xp_curve::XpCurve,
+        },
+        replay::{InputSource, record_input, replaying},
+        rng::RunSeed,
+        run_stats::RunStats,
+        weapons::{
+            spec::{WeaponMap, components::WeaponSpec, loader::weapon_ron_files},
+            systems::pickup::PickUpWeaponEvent,
+        },
+    },
+    menus::Menu,
+    save::SaveData,
+    screens::Screen,
+    settings::Settings,
+};
+
+/// Length of one simulated frame, the same as the default fixed timestep.
+const TIMESTEP: Duration = Duration::from_micros(15_625);
+/// How long to wait for the weapon specs and the XP curve to load.
+const LOAD_TIMEOUT: Duration = Duration::from_secs(10);
+/// Enemies closer than this push a kiting player away.
+const KITE_RADIUS: f32 = 160.0;
+
+/// What to simulate.
+#[derive(Debug, Clone)]
+pub struct SimConfig {
+    pub minutes: f32,
+    pub wave_plan: WavePlanKind,
+    pub policy: MovementPolicy,
+    pub seed: u64,
+    /// Picked up at the start, in addition to the starting weapon.
+    pub weapons: Vec<WeaponKind>,
+}
+
+impl Default for SimConfig {
+    fn default() -> Self {
+        Self {
+            minutes: 5.0,
+            wave_plan: WavePlanKind::Standard,
+            policy: MovementPolicy::Kite,
+            seed: 0,
+            weapons: Vec::new(),
+        }
+    }
+}
+
+/// How the simulated player moves.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Default)]
+pub enum MovementPolicy {
+    /// Stand still.
+    Idle,
+    /// Walk in a circle of this radius around the start.
+    Circle(f32),
+    /// Walk away from nearby enemies.
+    #[default]
+    Kite,
+}
+
+impl FromStr for MovementPolicy {
+    type Err = String;
+
+    /// Parses `idle`, `kite`, `circle` or `circle:<radius>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "idle" => Ok(MovementPolicy::Idle),
+            None if s == "kite" => Ok(MovementPolicy::Kite),
+            None if s == "circle" => Ok(MovementPolicy::Circle(200.0)),
+            Some(("circle", radius)) => radius
+                .parse()
+                .map(MovementPolicy::Circle)
+                .map_err(|err| format!("invalid circle radius {radius:?}: {err}")),
+            _ => Err(format!("unknown movement policy {s:?}")),
+        }
+    }
+}
+
+/// Parses a [`WavePlanKind`] by name, ignoring case.
+pub fn parse_wave_plan(name: &str) -> Option<WavePlanKind> {
+    [WavePlanKind::Standard, WavePlanKind::Rush]
+        .into_iter()
+        .find(|plan| format!("{plan:?}").eq_ignore_ascii_case(name))
+}
+
+/// Parses a [`WeaponKind`] by name, ignoring case.
+pub fn parse_weapon(name: &str) -> Option<WeaponKind> {
+    WeaponKind::ALL
+        .iter()
+        .copied()
+        .find(|kind| format!("{kind:?}").eq_ignore_ascii_case(name))
+}
+
+/// The outcome of a simulated run.
+#[derive(Debug, Clone, Default)]
+pub struct SimReport {
+    pub seconds: f32,
+    pub kills: u32,
+    pub damage_taken: f32,
+    /// Seconds into the run the player's health first dropped to zero.
+    /// The run goes on regardless, there is no game over yet.
+    pub died_at: Option<f32>,
+    pub level: u32,
+    /// Damage per second of every weapon that hit something, highest first.
+    pub dps: Vec<(WeaponKind, f32)>,
+}
+
+impl fmt::Display for SimReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "simulated {:.0}s", self.seconds)?;
+        writeln!(f, "kills:        {}", self.kills)?;
+        writeln!(f, "damage taken: {:.0}", self.damage_taken)?;
+        if let Some(died_at) = self.died_at {
+            writeln!(f, "died at:      {died_at:.0}s")?;
+        }
+        writeln!(f, "level:        {}", self.level)?;
+        for (kind, dps) in &self.dps {
+            writeln!(f, "{:<13} {dps:.1} dps", format!("{kind:?}:"))?;
+        }
+        Ok(())
+    }
+}
+
+/// Tracked from the player's health while the simulation runs, the rest comes from [`RunStats`].
+#[derive(Resource, Default, Debug)]
+struct SimStats {
+    damage_taken: f32,
+    died_at: Option<f32>,
+    last_health: Option<f32>,
+    /// Elapsed time when the run started, after loading.
+    started_at: f32,
+}
+
+/// Whether the assets submodule is checked out, which [`headless_app`] needs for the weapon specs.
+///
+/// Tests that need the assets return early when this is false, so a checkout without them stays
+/// green. The skip is printed, `cargo test -- --nocapture` shows it.
+pub fn assets_checked_out() -> bool {
+    let spec_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets/weapons/rons");
+    let checked_out = spec_dir.is_dir();
+    if !checked_out {
+        eprintln!(
+            "Skipped, {} is missing. Check out the assets submodule to run this test.",
+            spec_dir.display()
+        );
+    }
+    checked_out
+}
+
+/// Simulate a run and report how it went.
+pub fn run(config: &SimConfig) -> SimReport {
+    let mut app = headless_app(config);
+
+    let frames = (config.minutes * 60.0 / TIMESTEP.as_secs_f32()).ceil() as u32;
+    for _ in 0..frames {
+        app.update();
+    }
+
+    report(&mut app, frames as f32 * TIMESTEP.as_secs_f32())
+}
+
+/// An [`App`] in [`Screen::Gameplay`] with a spawned player, ready to be updated.
+pub fn headless_app(config: &SimConfig) -> App {
+    build_app(config, InputSource::Live, None)
+}
+
+/// Like [`headless_app`], but the player's input and overlay choices come from `log`.
+#[cfg(test)]
+pub(crate) fn replay_app(config: &SimConfig, log: InputLog) -> App {
+    build_app(config, InputSource::replay(log), None)
+}
+
+/// Like [`headless_app`], but without the assets or enemy waves, and the player standing still
+/// at the origin with the weapons in `specs`. For testing how weapons behave.
+#[cfg(test)]
+pub(crate) fn weapon_test_app(specs: impl IntoIterator<Item = WeaponSpec>) -> App {
+    let weapons: WeaponMap = specs.into_iter().collect();
+    let config = SimConfig {
+        policy: MovementPolicy::Idle,
+        weapons: weapons.keys().copied().collect(),
+        ..default()
+    };
+    let mut app = build_app(&config, InputSource::Live, Some(weapons));
+
+    // The tests place their own enemies.
+    let world = app.world_mut();
+    let spawned: Vec<Entity> = world
+        .query_filtered::<Entity, Or<(With<Wave>, With<Enemy>)>>()
+        .iter(world)
+        .collect();
+    for entity in spawned {
+        world.despawn(entity);
+    }
+
+    app
+}
+
+/// Spawn a walker at `position`.
+#[cfg(test)]
+pub(crate) fn spawn_walker(app: &mut App, position: Vec2) {
+    app.world_mut().trigger(WalkerSpawnEvent(Some(position)));
+}
+
+/// Keep every enemy spawned so far where it stands.
+#[cfg(test)]
+pub(crate) fn root_enemies(app: &mut App) {
+    let world = app.world_mut();
+    let enemies: Vec<Entity> = world
+        .query_filtered::<Entity, With<Enemy>>()
+        .iter(world)
+        .collect();
+    for enemy in enemies {
+        world
+            .entity_mut(enemy)
+            .insert(Root(Timer::from_seconds(1000.0, TimerMode::Once)));
+    }
+}
+
+/// Teleport the player to `position`.
+#[cfg(test)]
+pub(crate) fn move_player(app: &mut App, position: Vec2) {
+    let world = app.world_mut();
+    let (mut transform, mut body_position) = world
+        .query_filtered::<(&mut Transform, &mut avian2d::prelude::Position), With<Player>>()
+        .single_mut(world)
+        .expect("the player spawned");
+    transform.translation = position.extend(transform.translation.z);
+    body_position.0 = position;
+}
+
+/// Attack once with every weapon, as if their cooldowns ran out.
+#[cfg(test)]
+pub(crate) fn attack(app: &mut App) {
+    let world = app.world_mut();
+    let weapons: Vec<Entity> = world
+        .query_filtered::<Entity, With<Weapon>>()
+        .iter(world)
+        .collect();
+    for entity in weapons {
+        world.trigger(WeaponAttack { entity });
+    }
+}
+
+/// Update `app` for `seconds` of game time.
+#[cfg(test)]
+pub(crate) fn advance(app: &mut App, seconds: f32) {
+    let frames = (seconds / TIMESTEP.as_secs_f32()).ceil() as u32;
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+/// Enemies hit by weapons of `kind` so far.
+#[cfg(test)]
+pub(crate) fn hits(app: &App, kind: WeaponKind) -> u32 {
+    app.world()
+        .resource::<RunStats>()
+        .weapons
+        .get(&kind)
+        .map_or(0, |stats| stats.hits)
+}
+
+fn build_app(config: &SimConfig, source: InputSource, weapons: Option<WeaponMap>) -> App {
+    let asset_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+
+    let mut app = App::new();
+    // Asset types the gameplay code hands out handles for. Without loaders for images, fonts
+    // and audio those loads fail quietly, which is fine without anyone watching.
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin {
+            file_path: asset_root.to_string_lossy().into_owned(),
+            ..default()
+        },
+    ))
+    .init_asset::<Image>()
+    .init_asset::<TextureAtlasLayout>()
+    .init_asset::<Mesh>()
+    .init_asset::<Font>()
+    .init_asset::<AudioSample>()
+    .init_asset::<TiledMapAsset>();
+
+    app.add_plugins((
+        StatesPlugin,
+        TransformPlugin,
+        InputPlugin,
+        EnhancedInputPlugin,
+        EntropyPlugin::<WyRand>::default(),
+        PhysicsPlugins::default().with_length_unit(32.0),
+    ));
+    app.insert_resource(Gravity(Vec2::ZERO));
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(TIMESTEP));
+
+    app.init_state::<AssetStates>()
+        .init_state::<Screen>()
+        .init_state::<Menu>();
+
+    let mut settings = Settings::default();
+    settings.accessibility.damage_numbers = false;
+    app.insert_resource(settings)
+        .init_resource::<Controls>()
+        .init_resource::<Rebinding>()
+        .init_resource::<SaveData>();
+
+    app.add_plugins((fixed_update_inspection::plugin, gameplay::plugin));
+    crate::configure_sets(&mut app);
+
+    app.init_collection::<PlayerAssets>()
+        .init_collection::<DamageAssets>();
+    match weapons {
+        Some(weapons) => {
+            app.insert_resource(weapons);
+        }
+        None => load_game_data(&mut app, &asset_root),
+    }
+
+    app.insert_resource(CurrentLevel {
+        definition: LevelDefinition {
+            id: LevelId::from(DUNGEON),
+            name: "Simulation".to_string(),
+            map: String::new(),
+            music: String::new(),
+            wave_plan: config.wave_plan,
+            enemy_sprites: default(),
+        },
+        map: Handle::default(),
+        music: Handle::default(),
+    });
+    app.insert_resource(RunSeed {
+        fixed: Some(config.seed),
+        current: 0,
+    });
+    app.insert_resource(config.policy);
+    app.insert_resource(source);
+
+    // Steer before the input is recorded, like the live input arriving before the fixed tick.
+    app.add_systems(
+        FixedUpdate,
+        steer_player
+            .before(record_input)
+            .run_if(in_state(Screen::Gameplay).and(not(replaying)))
+            .in_set(PausableSystems)
+            .in_set(GameplaySystems::Input),
+    );
+    app.add_systems(
+        Update,
+        (
+            (
+                pick_first_offer.run_if(in_state(Overlay::LevelUp)),
+                claim_now.run_if(in_state(Overlay::Chest)),
+            )
+                .run_if(not(replaying)),
+            track_health.run_if(in_state(Screen::Gameplay)),
+        ),
+    );
+
+    app.world_mut()
+        .resource_mut::<NextState<AssetStates>>()
+        .set(AssetStates::Next);
+    app.world_mut()
+        .resource_mut::<NextState<Screen>>()
+        .set(Screen::Gameplay);
+    app.update();
+
+    let started_at = app.world().resource::<Time>().elapsed_secs();
+    app.insert_resource(SimStats {
+        started_at,
+        ..default()
+    });
+    app.world_mut()
+        .spawn((Player, Characters::Wizzard, Transform::default()));
+    for kind in &config.weapons {
+        app.world_mut().trigger(PickUpWeaponEvent { kind: *kind });
+    }
+    app.update();
+
+    app
+}
+
+/// Load the weapon specs, which the loading screen does in the game, and wait for the XP curve.
+///
+/// Panics without weapon specs, a run without weapons would only measure the enemies.
+fn load_game_data(app: &mut App, asset_root: &Path) {
+    let asset_server = app.world().resource::<AssetServer>().clone();
+
+    let spec_dir = asset_root.join("weapons/rons");
+    let spec_paths = weapon_ron_files(&spec_dir);
+    let specs: Vec<Handle<WeaponSpec>> = spec_paths
+        .iter()
+        .filter_map(|path| path.strip_prefix(asset_root).ok())
+        .map(|path| asset_server.load(path.to_path_buf()))
+        .collect();
+    let curve: Handle<XpCurve> = asset_server.load("progression/xp.curve.ron");
+
+    let settled = |id: UntypedAssetId| {
+        matches!(
+            asset_server.load_state(id),
+            LoadState::Loaded | LoadState::Failed(_)
+        )
+    };
+    let start = Instant::now();
+    while !(specs.iter().all(|spec| settled(spec.id().untyped())) && settled(curve.id().untyped()))
+    {
+        if start.elapsed() > LOAD_TIMEOUT {
+            warn!("Timed out loading the weapon specs and XP curve");
+            break;
+        }
+        app.update();
+        std::thread::sleep(Duration::from_millis(1));
+    }
+
+    let world = app.world_mut();
+    let weapons: WeaponMap = specs
+        .iter()
+        .filter_map(|spec| world.resource::<Assets<WeaponSpec>>().get(spec).cloned())
+        .collect();
+    assert!(
+        !weapons.is_empty(),
+        "No weapon specs loaded from {}, is the assets submodule checked out?",
+        spec_dir.display()
+    );
+    let curve = world
+        .resource::<Assets<XpCurve>>()
+        .get(&curve)
+        .cloned()
+        .unwrap_or_default();
+    world.insert_resource(weapons);
+    world.insert_resource(curve);
+}
+
+fn steer_player(
+    policy: Res<MovementPolicy>,
+    player: Single<(&Transform, &mut AccumulatedInput), With<Player>>,
+    enemy_q: Query<&Transform, (With<Enemy>, Without<Player>)>,
+) {
+    let (transform, mut input) = player.into_inner();
+    let position = transform.translation.truncate();
+
+    input.last_move = match *policy {
+        MovementPolicy::Idle => Vec2::ZERO,
+        MovementPolicy::Circle(radius) => {
+            // Walk along the circle, drifting back onto it when off course.
+            let outward = position.normalize_or(Vec2::X);
+            let correction = (radius - position.length()) / radius.max(1.0);
+            (outward.perp() + outward * correction).normalize_or_zero()
+        }
+        MovementPolicy::Kite => enemy_q
+            .iter()
+            .map(|enemy| position - enemy.translation.truncate())
+            .filter(|away| away.length_squared() < KITE_RADIUS.powi(2))
+            .map(|away| away.normalize_or_zero() * (KITE_RADIUS - away.length()))
+            .sum::<Vec2>()
+            .normalize_or_zero(),
+    };
+}
+
+fn track_health(
+    player: Single<&Health, With<Player>>,
+    mut stats: ResMut<SimStats>,
+    time: Res<Time>,
+) {
+    let health = player.0;
+    if let Some(last_health) = stats.last_health
+        && health < last_health
+    {
+        stats.damage_taken += last_health - health;
+    }
+    stats.last_health = Some(health);
+
+    if health <= 0.0 && stats.died_at.is_none() {
+        stats.died_at = Some(time.elapsed_secs() - stats.started_at);
+    }
+}
+
+fn report(app: &mut App, seconds: f32) -> SimReport {
+    let level = app
+        .world_mut()
+        .query_filtered::<&Level, With<Player>>()
+        .single(app.world())
+        .map_or(1, |level| level.0 as u32);
+    let stats = app.world().resource::<SimStats>();
+    let run_stats = app.world().resource::<RunStats>();
+
+    // Weapons by damage are also sorted by damage per second over the same time.
+    let dps = run_stats
+        .weapons_by_damage()
+        .into_iter()
+        .map(|(kind, weapon)| (kind, weapon.damage / seconds.max(f32::EPSILON)))
+        .collect();
+
+    SimReport {
+        seconds,
+        kills: run_stats.kills,
+        damage_taken: stats.damage_taken,
+        died_at: stats.died_at,
+        level,
+        dps,
+    }
+}
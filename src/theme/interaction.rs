@@ -1,4 +1,4 @@
-use bevy::prelude::*;
+use bevy::{input_focus::InputFocus, prelude::*};
 use bevy_asset_loader::prelude::*;
 use bevy_seedling::sample::{AudioSample, SamplePlayer};
 
@@ -12,6 +12,7 @@ pub(super) fn plugin(app: &mut App) {
             trigger_on_press,
             apply_interaction_palette,
             trigger_interaction_sound_effect,
+            trigger_focus_sound_effect.run_if(resource_changed::<InputFocus>),
         )
             .run_if(resource_exists::<InteractionAssets>),
     );
@@ -19,7 +20,7 @@ pub(super) fn plugin(app: &mut App) {
 
 /// Palette for widget interactions. Add this to an entity that supports
 /// [`Interaction`]s, such as a button, to change its [`BackgroundColor`] based
-/// on the current interaction state.
+/// on the current interaction state. A focused entity uses the hovered color.
 #[derive(Component, Debug, Reflect)]
 #[reflect(Component)]
 pub(crate) struct InteractionPalette {
@@ -47,18 +48,22 @@ fn trigger_on_press(
 }
 
 fn apply_interaction_palette(
-    mut palette_query: Query<
-        (&Interaction, &InteractionPalette, &mut BackgroundColor),
-        Changed<Interaction>,
-    >,
+    input_focus: Res<InputFocus>,
+    mut palette_query: Query<(
+        Entity,
+        &Interaction,
+        &InteractionPalette,
+        &mut BackgroundColor,
+    )>,
 ) {
-    for (interaction, palette, mut background) in &mut palette_query {
-        *background = match interaction {
+    for (entity, interaction, palette, mut background) in &mut palette_query {
+        let color = match interaction {
+            Interaction::None if input_focus.0 == Some(entity) => palette.hovered,
             Interaction::None => palette.none,
             Interaction::Hovered => palette.hovered,
             Interaction::Pressed => palette.pressed,
-        }
-        .into();
+        };
+        background.set_if_neq(color.into());
     }
 }
 
@@ -84,3 +89,17 @@ fn trigger_interaction_sound_effect(
         commands.spawn((SamplePlayer::new(source), SfxPool));
     }
 }
+
+fn trigger_focus_sound_effect(
+    input_focus: Res<InputFocus>,
+    palette_query: Query<(), With<InteractionPalette>>,
+    interaction_assets: Res<InteractionAssets>,
+    mut commands: Commands,
+) {
+    if input_focus
+        .0
+        .is_some_and(|entity| palette_query.contains(entity))
+    {
+        commands.spawn((SamplePlayer::new(interaction_assets.hover.clone()), SfxPool));
+    }
+}
@@ -14,4 +14,11 @@ pub(crate) const BUTTON_HOVERED_BACKGROUND: Srgba = tailwind::SLATE_700;
 
 pub(crate) const BUTTON_PRESSED_BACKGROUND: Srgba = tailwind::SLATE_600;
 
+/// Dark semi-transparent background of item and recipe cards.
+pub(crate) const CARD_BACKGROUND: Srgba = Srgba::new(0.1, 0.1, 0.1, 0.6);
+
+pub(crate) const CARD_HOVERED_BACKGROUND: Srgba = Srgba::new(0.3, 0.3, 0.3, 0.8);
+
+pub(crate) const CARD_PRESSED_BACKGROUND: Srgba = Srgba::new(0.2, 0.2, 0.2, 0.8);
+
 pub(crate) const SCREEN_BACKGROUND: Color = Color::srgb(0.16862746, 0.17254902, 0.18431373);
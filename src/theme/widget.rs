@@ -5,7 +5,7 @@ use std::borrow::Cow;
 use bevy::{
     ecs::{spawn::SpawnWith, system::IntoObserverSystem},
     prelude::*,
-    ui::Val::*,
+    ui::{Val::*, auto_directional_navigation::AutoDirectionalNavigation},
 };
 
 use crate::theme::{interaction::InteractionPalette, palette::*};
@@ -68,6 +68,7 @@ where
     button_base(
         text,
         action,
+        40.0,
         (Node {
             width: Px(380.0),
             height: Px(80.0),
@@ -79,6 +80,36 @@ where
     )
 }
 
+/// A medium sized button with text and an action defined as an [`Observer`].
+/// `extra` is inserted on the button itself, e.g. a marker to update its text later.
+pub(crate) fn button_medium<E, B, M, I>(
+    text: impl Into<String>,
+    action: I,
+    extra: impl Bundle,
+) -> impl Bundle
+where
+    E: EntityEvent,
+    B: Bundle,
+    I: IntoObserverSystem<E, B, M>,
+{
+    button_base(
+        text,
+        action,
+        24.0,
+        (
+            Node {
+                width: Px(220.0),
+                height: Px(40.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                border_radius: BorderRadius::MAX,
+                ..default()
+            },
+            extra,
+        ),
+    )
+}
+
 /// A small square button with text and an action defined as an [`Observer`].
 pub(crate) fn button_small<E, B, M, I>(text: impl Into<String>, action: I) -> impl Bundle
 where
@@ -89,6 +120,7 @@ where
     button_base(
         text,
         action,
+        40.0,
         Node {
             width: Px(30.0),
             height: Px(30.0),
@@ -103,6 +135,7 @@ where
 fn button_base<E, B, M, I>(
     text: impl Into<String>,
     action: I,
+    font_size: f32,
     button_bundle: impl Bundle,
 ) -> impl Bundle
 where
@@ -120,6 +153,7 @@ where
                 .spawn((
                     Name::new("Button Inner"),
                     Button,
+                    AutoDirectionalNavigation::default(),
                     BackgroundColor(BUTTON_BACKGROUND.into()),
                     InteractionPalette {
                         none: BUTTON_BACKGROUND.into(),
@@ -129,7 +163,7 @@ where
                     children![(
                         Name::new("Button Text"),
                         Text(text),
-                        TextFont::from_font_size(40.0),
+                        TextFont::from_font_size(font_size),
                         TextColor(BUTTON_TEXT.into()),
                         // Don't bubble picking events from the text up to the button.
                         Pickable::IGNORE,
@@ -0,0 +1,39 @@
+//! Headless balance simulations, see `bevy_survivors::sim`.
+//!
+//! These need the assets submodule for the weapon specs and skip themselves without it.
+
+use bevy_survivors::sim::{self, MovementPolicy, SimConfig, WavePlanKind};
+
+#[test]
+fn standard_plan_kills_enemies_in_the_first_minute() {
+    if !sim::assets_checked_out() {
+        return;
+    }
+
+    let report = sim::run(&SimConfig {
+        minutes: 1.0,
+        wave_plan: WavePlanKind::Standard,
+        policy: MovementPolicy::Kite,
+        ..Default::default()
+    });
+
+    assert!((report.seconds - 60.0).abs() < 0.1, "{report}");
+    assert!(report.kills > 0, "{report}");
+    assert!(!report.dps.is_empty(), "{report}");
+}
+
+#[test]
+fn standing_still_takes_damage() {
+    if !sim::assets_checked_out() {
+        return;
+    }
+
+    let report = sim::run(&SimConfig {
+        minutes: 1.0,
+        wave_plan: WavePlanKind::Rush,
+        policy: MovementPolicy::Idle,
+        ..Default::default()
+    });
+
+    assert!(report.damage_taken > 0.0, "{report}");
+}